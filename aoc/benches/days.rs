@@ -0,0 +1,146 @@
+// Benchmarks parsing and both parts of every day, on the puzzle's worked
+// example and on the real input when it's present, e.g.
+//
+//     cargo bench -p aoc --bench days
+//     cargo bench -p aoc --bench days -- day16
+//
+// With AOC_BENCH_SIZE set, each day also gets a random input of that size
+// from aoc::generate, seeded with AOC_BENCH_SEED or 0:
+//
+//     AOC_BENCH_SIZE=100000 cargo bench -p aoc --bench days -- day18
+//
+// Alternative solvers for the same part are grouped together so that
+// criterion reports them side by side.
+
+use aoc::{days, generate};
+use common::fetch::Fetcher;
+use common::Solution;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::path::Path;
+
+// Reads a file named relative to the workspace root, since criterion runs
+// from the package directory.  Absolute paths are read as they are.
+fn read(path: &str) -> Option<String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("..").join(path);
+    std::fs::read_to_string(path).ok()
+}
+
+type Params = &'static [(&'static str, &'static str)];
+
+fn env_number<T: std::str::FromStr>(name: &str) -> Option<T> {
+    let value = std::env::var(name).ok()?;
+    Some(
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("{} should be a number, not {:?}", name, value)),
+    )
+}
+
+// The example, the real input and a generated one for a day, with the
+// parameters each needs.
+fn inputs(day: u32) -> Vec<(&'static str, String, Params)> {
+    let entry = days::find(day).expect("day is registered");
+    // Only an input that's already been fetched is used.
+    let input = Fetcher::from_env().input_path(day);
+    let input = input.to_str().expect("input path is UTF-8");
+    let mut inputs: Vec<_> = [
+        ("example", entry.example, entry.example_params),
+        ("input", input, &[][..]),
+    ]
+    .into_iter()
+    .filter_map(|(name, path, params)| Some((name, read(path)?, params)))
+    .collect();
+
+    if let Some(size) = env_number("AOC_BENCH_SIZE") {
+        let seed = env_number("AOC_BENCH_SEED").unwrap_or(0);
+        let generated = generate::input(day, size, seed).expect("every day has a generator");
+        inputs.push(("generated", generated, &[][..]));
+    }
+    inputs
+}
+
+fn bench_day<S: Solution + Default>(c: &mut Criterion, day: u32) {
+    let mut group = c.benchmark_group(format!("day{}", day));
+    // Some parts take seconds; criterion's default of 100 samples is too
+    // many for those.
+    group.sample_size(10);
+
+    for (name, input, params) in inputs(day) {
+        let mut solution = S::default();
+        for (param, value) in params {
+            solution
+                .set_param(param, value)
+                .expect("example parameters are valid");
+        }
+
+        group.bench_with_input(BenchmarkId::new("parse", name), &input, |b, input| {
+            b.iter(|| S::parse(input))
+        });
+
+        // Parts that fail, such as ones not solved yet, are left out.
+        let Ok(parsed) = S::parse(&input) else {
+            continue;
+        };
+        if solution.part1(&parsed).is_ok() {
+            group.bench_function(BenchmarkId::new("part1", name), |b| {
+                b.iter(|| solution.part1(&parsed))
+            });
+        }
+        if solution.part2(&parsed).is_ok() {
+            group.bench_function(BenchmarkId::new("part2", name), |b| {
+                b.iter(|| solution.part2(&parsed))
+            });
+        }
+    }
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<day1::Day1>(c, 1);
+    bench_day::<day2::Day2>(c, 2);
+    bench_day::<day3::Day3>(c, 3);
+    bench_day::<day4::Day4>(c, 4);
+    bench_day::<day5::Day5>(c, 5);
+    bench_day::<day6::Day6>(c, 6);
+    bench_day::<day7::Day7>(c, 7);
+    bench_day::<day8::Day8>(c, 8);
+    bench_day::<day9::Day9>(c, 9);
+    bench_day::<day10::Day10>(c, 10);
+    bench_day::<day11::Day11>(c, 11);
+    bench_day::<day12::Day12>(c, 12);
+    bench_day::<day13::Day13>(c, 13);
+    bench_day::<day14::Day14>(c, 14);
+    bench_day::<day15::Day15>(c, 15);
+    bench_day::<day16::Day16>(c, 16);
+    bench_day::<day17::Day17>(c, 17);
+    bench_day::<day18::Day18>(c, 18);
+    bench_day::<day19::Day19>(c, 19);
+    bench_day::<day20::Day20>(c, 20);
+    bench_day::<day21::Day21>(c, 21);
+    bench_day::<day22::Day22>(c, 22);
+}
+
+// Day 16 part 1 can be solved by dynamic programming over (valve, open
+// valves, time) or by the search that part 2 uses.
+fn day16_part1_solvers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day16/part1 solvers");
+    group.sample_size(10);
+
+    for (name, input, _) in inputs(16) {
+        let valves = day16::parse_valves(&input).expect("valid input");
+        group.bench_with_input(
+            BenchmarkId::new("dynamic_programming", name),
+            &valves,
+            |b, valves| {
+                b.iter(|| day16::dynamic_programming::find_optimal_total_flow(0, valves, 30))
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("search", name), &valves, |b, valves| {
+            b.iter(|| day16::search::find_optimal_total_flow(&[0], valves, 30))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, days, day16_part1_solvers);
+criterion_main!(benches);
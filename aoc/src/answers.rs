@@ -0,0 +1,127 @@
+// Recorded answers, kept in each day's answers.txt in the same form the
+// day binaries print them:
+//
+//     part 1: 13060
+//     part 2:
+//     ####...##.#..#.###..#..#.#....###..####.
+//     ...
+//
+// An answer that spans several lines starts on the line after its header.
+
+use common::fetch::workspace_root;
+use std::io::ErrorKind;
+use std::path::Path;
+
+// The recorded answer to each part, if there is one.
+pub type Answers = [Option<String>; 2];
+
+pub fn parse_answers(s: &str) -> Result<Answers, String> {
+    let mut answers: Answers = [None, None];
+    // The part whose multi-line answer is being read.
+    let mut current: Option<usize> = None;
+
+    for (lineindex, line) in s.lines().enumerate() {
+        if let Some((part, rest)) = parse_header(line) {
+            if answers[part - 1].is_some() {
+                return Err(format!(
+                    "line {}: part {} is recorded twice",
+                    lineindex + 1,
+                    part
+                ));
+            }
+            let rest = rest.trim();
+            answers[part - 1] = Some(rest.to_string());
+            current = if rest.is_empty() { Some(part) } else { None };
+        } else if let Some(part) = current {
+            let answer = answers[part - 1].as_mut().unwrap();
+            if !answer.is_empty() {
+                answer.push('\n');
+            }
+            answer.push_str(line.trim_end());
+        } else if !line.trim().is_empty() {
+            return Err(format!(
+                "line {}: expected \"part 1:\" or \"part 2:\", found {:?}",
+                lineindex + 1,
+                line
+            ));
+        }
+    }
+
+    for answer in answers.iter_mut() {
+        if let Some(s) = answer {
+            let trimmed = s.trim_end().len();
+            s.truncate(trimmed);
+            if s.is_empty() {
+                *answer = None;
+            }
+        }
+    }
+    Ok(answers)
+}
+
+// Splits "part N: rest" into N and rest.
+fn parse_header(line: &str) -> Option<(usize, &str)> {
+    let (header, rest) = line.split_once(':')?;
+    match header.trim() {
+        "part 1" => Some((1, rest)),
+        "part 2" => Some((2, rest)),
+        _ => None,
+    }
+}
+
+// Loads the answers recorded at path, relative to the workspace root so
+// that it doesn't matter where aoc runs from.  A missing file records
+// nothing.
+pub fn load_answers(path: impl AsRef<Path>) -> Result<Answers, String> {
+    let path = workspace_root().join(path);
+    match std::fs::read_to_string(&path) {
+        Ok(s) => parse_answers(&s).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok([None, None]),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            parse_answers("part 1: 6181\npart 2: 2386\n").unwrap(),
+            [Some("6181".into()), Some("2386".into())]
+        );
+        assert_eq!(
+            parse_answers("part 2: 12\n").unwrap(),
+            [None, Some("12".into())]
+        );
+        assert_eq!(parse_answers("").unwrap(), [None, None]);
+    }
+
+    #[test]
+    fn test_parse_answers_multi_line() {
+        assert_eq!(
+            parse_answers("part 1: 13060\npart 2:\n##..\n.##.\n\n").unwrap(),
+            [Some("13060".into()), Some("##..\n.##.".into())]
+        );
+        assert_eq!(
+            parse_answers("part 1:\npart 2: 5\n").unwrap(),
+            [None, Some("5".into())]
+        );
+    }
+
+    #[test]
+    fn test_parse_answers_errors() {
+        assert!(parse_answers("part 1: 1\npart 1: 2\n").is_err());
+        assert!(parse_answers("6181\n").is_err());
+        assert!(parse_answers("part 1: 1\n6181\n").is_err());
+    }
+
+    #[test]
+    fn test_load_answers() {
+        // Found from the workspace root, wherever the tests run.
+        let answers = load_answers("day1/answers.txt").unwrap();
+        assert!(answers[0].is_some());
+        assert_eq!(load_answers("no/such/answers.txt").unwrap(), [None, None]);
+    }
+}
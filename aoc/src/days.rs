@@ -0,0 +1,205 @@
+// Registry of every day's solvers.  Each day implements
+// common::Solution; here they're erased to a common shape.
+
+use common::Solution;
+
+// Solves one part for the given input and parameter overrides.
+pub type Solver = fn(&str, &[(String, String)]) -> Result<String, common::Error>;
+
+#[derive(Debug)]
+pub struct Day {
+    pub day: u32,
+    // The puzzle's worked example and recorded answers, relative to the
+    // workspace root.  The real input comes from common::fetch.
+    pub example: &'static str,
+    // Parameter overrides the worked example needs, such as a smaller row.
+    pub example_params: &'static [(&'static str, &'static str)],
+    pub answers: &'static str,
+    // None if the day has no solution for that part yet.
+    pub parts: [Option<Solver>; 2],
+}
+
+fn solve<S: Solution + Default>(
+    input: &str,
+    params: &[(String, String)],
+    part: usize,
+) -> Result<String, common::Error> {
+    let mut solution = S::default();
+    common::set_params(&mut solution, params)?;
+    common::solve(&solution, input, part)
+}
+
+fn part1<S: Solution + Default>(
+    input: &str,
+    params: &[(String, String)],
+) -> Result<String, common::Error> {
+    solve::<S>(input, params, 1)
+}
+
+fn part2<S: Solution + Default>(
+    input: &str,
+    params: &[(String, String)],
+) -> Result<String, common::Error> {
+    solve::<S>(input, params, 2)
+}
+
+pub const DAYS: &[Day] = &[
+    Day {
+        day: 1,
+        example: "day1/example.txt",
+        example_params: &[],
+        answers: "day1/answers.txt",
+        parts: [Some(part1::<day1::Day1>), Some(part2::<day1::Day1>)],
+    },
+    Day {
+        day: 2,
+        example: "day2/example.txt",
+        example_params: &[],
+        answers: "day2/answers.txt",
+        parts: [Some(part1::<day2::Day2>), Some(part2::<day2::Day2>)],
+    },
+    Day {
+        day: 3,
+        example: "day3/example.txt",
+        example_params: &[],
+        answers: "day3/answers.txt",
+        parts: [Some(part1::<day3::Day3>), Some(part2::<day3::Day3>)],
+    },
+    Day {
+        day: 4,
+        example: "day4/example.txt",
+        example_params: &[],
+        answers: "day4/answers.txt",
+        parts: [Some(part1::<day4::Day4>), Some(part2::<day4::Day4>)],
+    },
+    Day {
+        day: 5,
+        example: "day5/example.txt",
+        example_params: &[],
+        answers: "day5/answers.txt",
+        parts: [Some(part1::<day5::Day5>), Some(part2::<day5::Day5>)],
+    },
+    Day {
+        day: 6,
+        example: "day6/example.txt",
+        example_params: &[],
+        answers: "day6/answers.txt",
+        parts: [Some(part1::<day6::Day6>), Some(part2::<day6::Day6>)],
+    },
+    Day {
+        day: 7,
+        example: "day7/example.txt",
+        example_params: &[],
+        answers: "day7/answers.txt",
+        parts: [Some(part1::<day7::Day7>), Some(part2::<day7::Day7>)],
+    },
+    Day {
+        day: 8,
+        example: "day8/example.txt",
+        example_params: &[],
+        answers: "day8/answers.txt",
+        parts: [Some(part1::<day8::Day8>), Some(part2::<day8::Day8>)],
+    },
+    Day {
+        day: 9,
+        example: "day9/example.txt",
+        example_params: &[],
+        answers: "day9/answers.txt",
+        parts: [Some(part1::<day9::Day9>), Some(part2::<day9::Day9>)],
+    },
+    Day {
+        day: 10,
+        example: "day10/example.txt",
+        example_params: &[],
+        answers: "day10/answers.txt",
+        parts: [Some(part1::<day10::Day10>), Some(part2::<day10::Day10>)],
+    },
+    Day {
+        day: 11,
+        example: "day11/example.txt",
+        example_params: &[],
+        answers: "day11/answers.txt",
+        parts: [Some(part1::<day11::Day11>), Some(part2::<day11::Day11>)],
+    },
+    Day {
+        day: 12,
+        example: "day12/example.txt",
+        example_params: &[],
+        answers: "day12/answers.txt",
+        parts: [Some(part1::<day12::Day12>), Some(part2::<day12::Day12>)],
+    },
+    Day {
+        day: 13,
+        example: "day13/example.txt",
+        example_params: &[],
+        answers: "day13/answers.txt",
+        parts: [Some(part1::<day13::Day13>), Some(part2::<day13::Day13>)],
+    },
+    Day {
+        day: 14,
+        example: "day14/example.txt",
+        example_params: &[],
+        answers: "day14/answers.txt",
+        parts: [Some(part1::<day14::Day14>), Some(part2::<day14::Day14>)],
+    },
+    Day {
+        day: 15,
+        example: "day15/example.txt",
+        example_params: &[("row", "10"), ("bound", "20")],
+        answers: "day15/answers.txt",
+        parts: [Some(part1::<day15::Day15>), Some(part2::<day15::Day15>)],
+    },
+    Day {
+        day: 16,
+        example: "day16/example.txt",
+        example_params: &[],
+        answers: "day16/answers.txt",
+        parts: [Some(part1::<day16::Day16>), Some(part2::<day16::Day16>)],
+    },
+    Day {
+        day: 17,
+        example: "day17/example.txt",
+        example_params: &[],
+        answers: "day17/answers.txt",
+        parts: [Some(part1::<day17::Day17>), Some(part2::<day17::Day17>)],
+    },
+    Day {
+        day: 18,
+        example: "day18/example.txt",
+        example_params: &[],
+        answers: "day18/answers.txt",
+        parts: [Some(part1::<day18::Day18>), Some(part2::<day18::Day18>)],
+    },
+    Day {
+        day: 19,
+        example: "day19/example.txt",
+        example_params: &[],
+        answers: "day19/answers.txt",
+        parts: [Some(part1::<day19::Day19>), Some(part2::<day19::Day19>)],
+    },
+    Day {
+        day: 20,
+        example: "day20/example.txt",
+        example_params: &[],
+        answers: "day20/answers.txt",
+        parts: [Some(part1::<day20::Day20>), Some(part2::<day20::Day20>)],
+    },
+    Day {
+        day: 21,
+        example: "day21/example.txt",
+        example_params: &[],
+        answers: "day21/answers.txt",
+        parts: [Some(part1::<day21::Day21>), Some(part2::<day21::Day21>)],
+    },
+    Day {
+        day: 22,
+        example: "day22/example.txt",
+        example_params: &[],
+        answers: "day22/answers.txt",
+        parts: [Some(part1::<day22::Day22>), None],
+    },
+];
+
+pub fn find(day: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.day == day)
+}
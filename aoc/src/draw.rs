@@ -0,0 +1,106 @@
+// Pictures of the grid-based days, for aoc draw.  Each day draws its
+// puzzle as text, which its palette colors in; see grid::image.  The days
+// that are simulations can also save each step as a frame.
+
+use common::Solution;
+use grid::image::{ImageFrames, Palette};
+
+// Draws the day's puzzle from its input, saving each step to the frames,
+// if there are any, as it goes.
+pub type Drawer = fn(&str, &mut Option<ImageFrames>) -> Result<String, common::Error>;
+
+#[derive(Debug)]
+pub struct Drawing {
+    pub day: u32,
+    pub palette: fn() -> Palette,
+    pub draw: Drawer,
+    // Whether there are steps to save as frames.
+    pub animated: bool,
+}
+
+pub const DRAWINGS: &[Drawing] = &[
+    // The tree heights as a heatmap.
+    Drawing {
+        day: 8,
+        palette: day8::palette,
+        draw: |input, _| Ok(day8::Day8::parse(input)?.to_string()),
+        animated: false,
+    },
+    // The CRT screen of part 2.
+    Drawing {
+        day: 10,
+        palette: day10::palette,
+        draw: |input, _| Ok(day10::render_crt(&day10::Day10::parse(input)?)),
+        animated: false,
+    },
+    // The height map and the shortest path from S to E.
+    Drawing {
+        day: 12,
+        palette: day12::palette,
+        draw: |input, _| {
+            let map = day12::Day12::parse(input)?;
+            Ok(day12::path_picture(&map).ok_or("no path from S to E")?)
+        },
+        animated: false,
+    },
+    // The rock and the sand of part 2, once the source is blocked.
+    Drawing {
+        day: 14,
+        palette: day14::palette,
+        draw: |input, frames| {
+            let paths = day14::Day14::parse(input)?;
+            Ok(day14::blocked_cave_with(&paths, frames).to_string())
+        },
+        animated: true,
+    },
+    // The tower of part 1's rocks.
+    Drawing {
+        day: 17,
+        palette: day17::palette,
+        draw: |input, frames| {
+            let jets = day17::Day17::parse(input)?;
+            let rocks = day17::Day17::default().part1_rocks;
+            Ok(day17::tower_with(&jets, rocks, frames).to_string())
+        },
+        animated: true,
+    },
+    // The board and the path taken across it.
+    Drawing {
+        day: 22,
+        palette: day22::palette,
+        draw: |input, frames| {
+            let problem = day22::Day22::parse(input)?;
+            Ok(day22::trail_with(&problem, frames).ok_or("no open tile to start from")?)
+        },
+        animated: true,
+    },
+];
+
+pub fn find(day: u32) -> Option<&'static Drawing> {
+    DRAWINGS.iter().find(|drawing| drawing.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+    use grid::image::Image;
+
+    #[test]
+    fn test_draw_examples() {
+        for drawing in DRAWINGS {
+            let day = days::find(drawing.day).expect("day is registered");
+            let path = format!("{}/../{}", env!("CARGO_MANIFEST_DIR"), day.example);
+            let example =
+                std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+            let text = (drawing.draw)(&example, &mut None)
+                .unwrap_or_else(|e| panic!("day {}: {}", drawing.day, e));
+            let image = Image::from_text(&text, &(drawing.palette)());
+            assert!(
+                image.width() > 0 && image.height() > 0,
+                "day {}",
+                drawing.day
+            );
+        }
+    }
+}
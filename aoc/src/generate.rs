@@ -0,0 +1,837 @@
+// Random puzzle inputs in each day's format, for pushing the solvers past
+// the sizes of the examples and real inputs, e.g.
+//
+//     aoc generate 18 --size 100000 --seed 7 > cubes.txt
+//
+// The same day, size and seed always give the same input.  What the size
+// counts depends on the day: elves, lines, monkeys, valves and so on; see
+// each generator.  Sizes too small for the format are rounded up.
+//
+// Inputs are valid as well as well-formed: the moves in day 5 never take
+// from an empty stack, day 12 always has a path up to E, day 15 leaves
+// exactly one place for the distress beacon, day 21 has an integer answer
+// for humn, and so on, so every solved part should have an answer.
+
+use rand::rngs::Xoshiro256PlusPlus;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{RngExt, SeedableRng};
+use std::collections::HashSet;
+
+type Rng = Xoshiro256PlusPlus;
+
+/// A random input for the day, or None if there's no such day.
+pub fn input(day: u32, size: usize, seed: u64) -> Option<String> {
+    let generate = match day {
+        1 => calories,
+        2 => strategy_guide,
+        3 => rucksacks,
+        4 => assignments,
+        5 => crate_stacks,
+        6 => datastream,
+        7 => terminal_output,
+        8 => tree_heights,
+        9 => rope_moves,
+        10 => cpu_program,
+        11 => monkeys,
+        12 => height_map,
+        13 => packet_pairs,
+        14 => rock_paths,
+        15 => sensors,
+        16 => valves,
+        17 => jets,
+        18 => cubes,
+        19 => blueprints,
+        20 => encrypted_file,
+        21 => monkey_jobs,
+        22 => board,
+        _ => return None,
+    };
+    Some(generate(&mut Rng::seed_from_u64(seed), size))
+}
+
+fn lines(lines: Vec<String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+fn word(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| rng.random_range('a'..='z')).collect()
+}
+
+// Day 1: size elves, each carrying a few snacks.
+fn calories(rng: &mut Rng, size: usize) -> String {
+    let elves: Vec<String> = (0..size.max(3))
+        .map(|_| {
+            let snacks: Vec<String> = (0..rng.random_range(1..=15))
+                .map(|_| format!("{}\n", rng.random_range(1000..=60000)))
+                .collect();
+            snacks.concat()
+        })
+        .collect();
+    elves.join("\n")
+}
+
+// Day 2: size rounds.
+fn strategy_guide(rng: &mut Rng, size: usize) -> String {
+    lines(
+        (0..size.max(1))
+            .map(|_| {
+                format!(
+                    "{} {}",
+                    rng.random_range('A'..='C'),
+                    rng.random_range('X'..='Z')
+                )
+            })
+            .collect(),
+    )
+}
+
+// Day 3: size groups of three elves.  Each rucksack has one item in both
+// compartments, and each group has one item, its badge, in all three.
+fn rucksacks(rng: &mut Rng, size: usize) -> String {
+    let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let mut sacks = Vec::new();
+    for _ in 0..size.max(1) {
+        items.shuffle(rng);
+        let badge = items[0];
+        // Each elf has seventeen items of their own: one for both
+        // compartments and eight for each.
+        for own in items[1..].chunks(17) {
+            let shared = own[0];
+            let len = rng.random_range(2..=16);
+            let mut first = vec![shared, badge];
+            first.extend((2..len).map(|_| own[rng.random_range(1..9)]));
+            let mut second = vec![shared];
+            second.extend((1..len).map(|_| own[rng.random_range(9..17)]));
+            first.shuffle(rng);
+            second.shuffle(rng);
+            sacks.push(first.into_iter().chain(second).collect());
+        }
+    }
+    lines(sacks)
+}
+
+// Day 4: size pairs of section assignments.
+fn assignments(rng: &mut Rng, size: usize) -> String {
+    let range = |rng: &mut Rng| {
+        let start = rng.random_range(1..=99);
+        (start, rng.random_range(start..=99))
+    };
+    lines(
+        (0..size.max(1))
+            .map(|_| {
+                let (a, b) = range(rng);
+                let (c, d) = range(rng);
+                format!("{}-{},{}-{}", a, b, c, d)
+            })
+            .collect(),
+    )
+}
+
+// Day 5: nine stacks and size moves.  No move takes a stack's last crate,
+// so there's always a crate on top at the end.
+fn crate_stacks(rng: &mut Rng, size: usize) -> String {
+    let mut stacks: Vec<Vec<char>> = (0..9)
+        .map(|_| {
+            (0..rng.random_range(1..=8))
+                .map(|_| rng.random_range('A'..='Z'))
+                .collect()
+        })
+        .collect();
+
+    let height = stacks.iter().map(Vec::len).max().unwrap_or(0);
+    let mut drawing: Vec<String> = (0..height)
+        .rev()
+        .map(|level| {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(ch) => format!("[{}]", ch),
+                    None => String::from("   "),
+                })
+                .collect();
+            row.join(" ")
+        })
+        .collect();
+    let labels: Vec<String> = (1..=stacks.len()).map(|i| format!(" {} ", i)).collect();
+    drawing.push(labels.join(" "));
+
+    let mut moves = Vec::new();
+    while moves.len() < size.max(1) {
+        let from = rng.random_range(0..stacks.len());
+        let to = rng.random_range(0..stacks.len());
+        if from == to || stacks[from].len() < 2 {
+            continue;
+        }
+        let how_many = rng.random_range(1..stacks[from].len());
+        let split = stacks[from].len() - how_many;
+        let moved = stacks[from].split_off(split);
+        stacks[to].extend(moved);
+        moves.push(format!("move {} from {} to {}", how_many, from + 1, to + 1));
+    }
+    lines(drawing) + "\n" + &lines(moves)
+}
+
+// Day 6: a datastream of size characters.  Markers only turn up towards
+// the end: before them, the stream has too few different letters for a
+// start-of-message marker.
+fn datastream(rng: &mut Rng, size: usize) -> String {
+    let mut letters: Vec<char> = ('a'..='z').collect();
+    letters.shuffle(rng);
+    let few = &letters[..rng.random_range(3..=13)];
+    let mut stream: Vec<char> = (0..size.max(14) - 14)
+        .map(|_| *few.choose(rng).expect("letters to choose from"))
+        .collect();
+    letters.shuffle(rng);
+    stream.extend(&letters[..14]);
+    stream.into_iter().collect::<String>() + "\n"
+}
+
+// Day 7: a file system of size directories, explored depth first.  The
+// files add up to about 50,000,000, so that part 2 has to free some space.
+fn terminal_output(rng: &mut Rng, size: usize) -> String {
+    const EXTENSIONS: &[&str] = &["", ".txt", ".dat", ".log", ".lst", ".ext"];
+
+    let dirs = size.max(1);
+    let mut names: Vec<HashSet<String>> = vec![HashSet::new(); dirs];
+    let mut unique_name = |rng: &mut Rng, dir: usize, extension: &str| loop {
+        let len = rng.random_range(1..=8);
+        let name = word(rng, len) + extension;
+        if names[dir].insert(name.clone()) {
+            return name;
+        }
+    };
+
+    // Directory 0 is the root; every other one is inside an earlier one.
+    let mut subdirs: Vec<Vec<(String, usize)>> = vec![Vec::new(); dirs];
+    for dir in 1..dirs {
+        let parent = rng.random_range(0..dir);
+        let name = unique_name(rng, parent, "");
+        subdirs[parent].push((name, dir));
+    }
+    let files: Vec<Vec<String>> = (0..dirs)
+        .map(|dir| {
+            (0..rng.random_range(0..=5))
+                .map(|_| {
+                    let extension = EXTENSIONS.choose(rng).expect("extensions to choose from");
+                    unique_name(rng, dir, extension)
+                })
+                .collect()
+        })
+        .collect();
+    let average = 50_000_000 / files.iter().map(Vec::len).sum::<usize>().max(1);
+
+    let mut output = vec![String::from("$ cd /")];
+    let mut listed = vec![false; dirs];
+    let mut path = vec![0];
+    while let Some(&dir) = path.last() {
+        if !listed[dir] {
+            listed[dir] = true;
+            let mut listing: Vec<String> = subdirs[dir]
+                .iter()
+                .map(|(name, _)| format!("dir {}", name))
+                .chain(
+                    files[dir]
+                        .iter()
+                        .map(|name| format!("{} {}", rng.random_range(1..2 * average), name)),
+                )
+                .collect();
+            listing.shuffle(rng);
+            output.push(String::from("$ ls"));
+            output.extend(listing);
+        }
+        match subdirs[dir].pop() {
+            Some((name, subdir)) => {
+                output.push(format!("$ cd {}", name));
+                path.push(subdir);
+            }
+            None => {
+                path.pop();
+                if !path.is_empty() {
+                    output.push(String::from("$ cd .."));
+                }
+            }
+        }
+    }
+    lines(output)
+}
+
+// Day 8: a size by size patch of trees.
+fn tree_heights(rng: &mut Rng, size: usize) -> String {
+    let side = size.max(1);
+    lines(
+        (0..side)
+            .map(|_| (0..side).map(|_| rng.random_range('0'..='9')).collect())
+            .collect(),
+    )
+}
+
+// Day 9: size moves of the rope's head.
+fn rope_moves(rng: &mut Rng, size: usize) -> String {
+    lines(
+        (0..size.max(1))
+            .map(|_| {
+                let direction = ['R', 'L', 'U', 'D'].choose(rng).expect("directions");
+                format!("{} {}", direction, rng.random_range(1..=20))
+            })
+            .collect(),
+    )
+}
+
+// Day 10: size instructions.  X stays on the screen, as it does in the
+// real programs, so that the sprite gets drawn.
+fn cpu_program(rng: &mut Rng, size: usize) -> String {
+    let mut x = 1;
+    lines(
+        (0..size.max(1))
+            .map(|_| {
+                let target = rng.random_range(0..40);
+                if target == x || rng.random_bool(0.3) {
+                    String::from("noop")
+                } else {
+                    let step = target - x;
+                    x = target;
+                    format!("addx {}", step)
+                }
+            })
+            .collect(),
+    )
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Worry {
+    Add(u64),
+    Multiply(u64),
+    Square,
+}
+
+struct Monkey {
+    items: Vec<u64>,
+    worry: Worry,
+    divisor: u64,
+    throw_to: [usize; 2],
+}
+
+// Day 11: size monkeys.  The tests divide by primes below 25, so that the
+// worry levels of part 2 stay small.  A monkey whose operation would
+// overflow a u64 during part 1's twenty rounds adds instead.
+fn monkeys(rng: &mut Rng, size: usize) -> String {
+    const PRIMES: &[u64] = &[2, 3, 5, 7, 11, 13, 17, 19, 23];
+    let count = size.max(2);
+
+    let mut monkeys: Vec<Monkey> = (0..count)
+        .map(|id| {
+            let mut other = || loop {
+                let other = rng.random_range(0..count);
+                if other != id {
+                    return other;
+                }
+            };
+            let mut throw_to = [other(), other()];
+            while count > 2 && throw_to[0] == throw_to[1] {
+                throw_to[1] = other();
+            }
+            Monkey {
+                items: (0..rng.random_range(1..=7))
+                    .map(|_| rng.random_range(50..100))
+                    .collect(),
+                worry: match rng.random_range(0..8) {
+                    0 => Worry::Square,
+                    1..=3 => Worry::Multiply(rng.random_range(2..20)),
+                    _ => Worry::Add(rng.random_range(1..=8)),
+                },
+                divisor: *PRIMES.choose(rng).expect("primes to choose from"),
+                throw_to,
+            }
+        })
+        .collect();
+    while let Some(id) = first_overflow(&monkeys) {
+        monkeys[id].worry = Worry::Add(rng.random_range(1..=8));
+    }
+
+    let blocks: Vec<String> = monkeys
+        .iter()
+        .enumerate()
+        .map(|(id, monkey)| {
+            let items: Vec<String> = monkey.items.iter().map(u64::to_string).collect();
+            let operation = match monkey.worry {
+                Worry::Add(n) => format!("old + {}", n),
+                Worry::Multiply(n) => format!("old * {}", n),
+                Worry::Square => String::from("old * old"),
+            };
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                id,
+                items.join(", "),
+                operation,
+                monkey.divisor,
+                monkey.throw_to[0],
+                monkey.throw_to[1]
+            )
+        })
+        .collect();
+    blocks.join("\n")
+}
+
+// Plays part 1's twenty rounds, stopping at the first monkey to take a
+// worry level past a twentieth of a u64.  Adding can't do that, as long as
+// everything before stayed under it, and multiplying by less than twenty
+// can't overflow.
+fn first_overflow(monkeys: &[Monkey]) -> Option<usize> {
+    const LIMIT: u64 = u64::MAX / 20;
+    let mut items: Vec<Vec<u64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+    for _ in 0..20 {
+        for (id, monkey) in monkeys.iter().enumerate() {
+            for item in std::mem::take(&mut items[id]) {
+                let worry = match monkey.worry {
+                    Worry::Add(n) => item.checked_add(n),
+                    Worry::Multiply(n) => item.checked_mul(n),
+                    Worry::Square => item.checked_mul(item),
+                };
+                let worry = match worry.map(|worry| worry / 3) {
+                    Some(worry) if worry <= LIMIT => worry,
+                    _ => return Some(id),
+                };
+                let target = monkey.throw_to[usize::from(worry % monkey.divisor != 0)];
+                items[target].push(worry);
+            }
+        }
+    }
+    None
+}
+
+// Day 12: a map size rows high and twice as wide.  A path from S climbs
+// one step at a time to E; the rest of the map is noise.
+fn height_map(rng: &mut Rng, size: usize) -> String {
+    let rows = size.max(14);
+    let cols = rows * 2;
+    let mut map: Vec<Vec<char>> = (0..rows)
+        .map(|_| (0..cols).map(|_| rng.random_range('a'..='z')).collect())
+        .collect();
+
+    // The path heads right and up or down, so it never crosses itself.
+    // It's at least 26 squares long, enough to climb from a to z.
+    let (mut row, mut col) = (rng.random_range(0..rows), 0);
+    let end = (
+        rng.random_range(0..rows),
+        rng.random_range(25.max(cols / 2)..cols),
+    );
+    let mut path = vec![(row, col)];
+    while (row, col) != end {
+        if row != end.0 && (col == end.1 || rng.random_bool(0.5)) {
+            row = if row < end.0 { row + 1 } else { row - 1 };
+        } else {
+            col += 1;
+        }
+        path.push((row, col));
+    }
+    let last = path.len() - 1;
+    for (i, &(row, col)) in path.iter().enumerate() {
+        map[row][col] = (b'a' + (i * 25 / last) as u8) as char;
+    }
+    map[path[0].0][path[0].1] = 'S';
+    map[end.0][end.1] = 'E';
+    lines(
+        map.into_iter()
+            .map(|row| row.into_iter().collect())
+            .collect(),
+    )
+}
+
+// Day 13: size pairs of packets.
+fn packet_pairs(rng: &mut Rng, size: usize) -> String {
+    fn packet(rng: &mut Rng, depth: usize) -> String {
+        let items: Vec<String> = (0..rng.random_range(0..=5))
+            .map(|_| {
+                if depth < 4 && rng.random_bool(0.3) {
+                    packet(rng, depth + 1)
+                } else {
+                    rng.random_range(0..=10).to_string()
+                }
+            })
+            .collect();
+        format!("[{}]", items.join(","))
+    }
+    let pairs: Vec<String> = (0..size.max(1))
+        .map(|_| format!("{}\n{}\n", packet(rng, 0), packet(rng, 0)))
+        .collect();
+    pairs.join("\n")
+}
+
+// Day 14: size paths of rock.  The cave is deeper the more rock there is,
+// up to the depth of the real ones.
+fn rock_paths(rng: &mut Rng, size: usize) -> String {
+    let depth = 10 + size.min(160) as i32;
+    let x_range = (500 - depth / 2)..=(500 + depth / 2);
+    lines(
+        (0..size.max(1))
+            .map(|_| {
+                let mut x = rng.random_range(x_range.clone());
+                let mut y = rng.random_range(1..=depth);
+                let mut points = vec![format!("{},{}", x, y)];
+                for i in 0..rng.random_range(1..=6) {
+                    let step = rng.random_range(1..=8) * if rng.random_bool(0.5) { 1 } else { -1 };
+                    if i % 2 == 0 {
+                        x = (x + step).clamp(*x_range.start(), *x_range.end());
+                    } else {
+                        y = (y + step).clamp(1, depth);
+                    }
+                    points.push(format!("{},{}", x, y));
+                }
+                points.join(" -> ")
+            })
+            .collect(),
+    )
+}
+
+// Day 15: sensors for the puzzle's row 2000000 and bound 4000000, with
+// size more sensors out past the edge of the search area.
+//
+// Rotated, as u = x + y and v = x - y, a sensor's range is a square.  Four
+// large squares cover everything but the distress beacon: one on each
+// side of it, with each sensor's beacon on the far corner, where the
+// other squares don't reach.  The extra sensors sit just beyond those
+// corners and pick up the same beacons.
+fn sensors(rng: &mut Rng, size: usize) -> String {
+    const BOUND: i64 = 4_000_000;
+    // Large enough to cover the search area, and odd, so that each
+    // square's center is a point with whole coordinates.
+    const R: i64 = 2 * BOUND + 1;
+
+    let xy = |(u, v): (i64, i64)| ((u + v) / 2, (u - v) / 2);
+    let (x, y) = (rng.random_range(0..=BOUND), rng.random_range(0..=BOUND));
+    let (u, v) = (x + y, x - y);
+
+    // Each sensor and its beacon, and the way out from the beacon.
+    let sides = [
+        ((u - 1 - R, v), (u - 1 - 2 * R, v - R), (-1, -1)),
+        ((u + 1 + R, v), (u + 1 + 2 * R, v + R), (1, 1)),
+        ((u, v - 1 - R), (u + R, v - 1 - 2 * R), (1, -1)),
+        ((u, v + 1 + R), (u - R, v + 1 + 2 * R), (-1, 1)),
+    ];
+    let mut readings: Vec<((i64, i64), (i64, i64))> = sides
+        .iter()
+        .map(|&(sensor, beacon, _)| (xy(sensor), xy(beacon)))
+        .collect();
+    for _ in 0..size {
+        let &(_, beacon, (du, dv)) = sides.choose(rng).expect("sides to choose from");
+        let a = rng.random_range(0..=1_000_000);
+        // a and b have to be both even or both odd.
+        let b = 2 * rng.random_range(0..=500_000) + a % 2;
+        if a == 0 && b == 0 {
+            continue;
+        }
+        readings.push((xy((beacon.0 + du * a, beacon.1 + dv * b)), xy(beacon)));
+    }
+    readings.shuffle(rng);
+    lines(
+        readings
+            .into_iter()
+            .map(|((sx, sy), (bx, by))| {
+                format!(
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                    sx, sy, bx, by
+                )
+            })
+            .collect(),
+    )
+}
+
+// Day 16: size valves, connected, about a third of them with some flow.
+fn valves(rng: &mut Rng, size: usize) -> String {
+    let count = size.clamp(2, 26 * 26);
+    let mut names: Vec<String> = ('A'..='Z')
+        .flat_map(|a| ('A'..='Z').map(move |b| format!("{}{}", a, b)))
+        .filter(|name| name != "AA")
+        .collect();
+    names.shuffle(rng);
+    names.insert(0, String::from("AA"));
+    names.truncate(count);
+
+    // A tree keeps them connected, and a few more tunnels add loops.
+    let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); count];
+    let mut dig = |a: usize, b: usize| {
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    };
+    for valve in 1..count {
+        dig(valve, rng.random_range(0..valve));
+    }
+    for _ in 0..count / 2 {
+        dig(rng.random_range(0..count), rng.random_range(0..count));
+    }
+
+    let mut scan: Vec<String> = (0..count)
+        .map(|valve| {
+            let flow = if valve > 0 && rng.random_bool(0.35) {
+                rng.random_range(3..=25)
+            } else {
+                0
+            };
+            let exits: Vec<&str> = tunnels[valve].iter().map(|&i| names[i].as_str()).collect();
+            let leads = if exits.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            format!(
+                "Valve {} has flow rate={}; {} {}",
+                names[valve],
+                flow,
+                leads,
+                exits.join(", ")
+            )
+        })
+        .collect();
+    scan.shuffle(rng);
+    lines(scan)
+}
+
+// Day 17: a jet pattern size long.
+fn jets(rng: &mut Rng, size: usize) -> String {
+    (0..size.max(1))
+        .map(|_| if rng.random_bool(0.5) { '<' } else { '>' })
+        .collect::<String>()
+        + "\n"
+}
+
+// Day 18: size different cubes, filling about a quarter of their space.
+fn cubes(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(1);
+    let side = ((count * 4) as f64).cbrt().ceil() as i32;
+    let mut cubes = HashSet::new();
+    let mut scan = Vec::new();
+    while scan.len() < count {
+        let cube = [(); 3].map(|_| rng.random_range(0..side));
+        if cubes.insert(cube) {
+            scan.push(format!("{},{},{}", cube[0], cube[1], cube[2]));
+        }
+    }
+    lines(scan)
+}
+
+// Day 19: size blueprints, with costs in the ranges of the real ones.
+fn blueprints(rng: &mut Rng, size: usize) -> String {
+    lines(
+        (1..=size.max(1))
+            .map(|id| {
+                format!(
+                    "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.",
+                    id,
+                    rng.random_range(2..=4),
+                    rng.random_range(2..=4),
+                    rng.random_range(2..=4),
+                    rng.random_range(4..=20),
+                    rng.random_range(2..=4),
+                    rng.random_range(5..=20)
+                )
+            })
+            .collect(),
+    )
+}
+
+// Day 20: size numbers, exactly one of them 0.
+fn encrypted_file(rng: &mut Rng, size: usize) -> String {
+    let count = size.max(1);
+    let zero = rng.random_range(0..count);
+    lines(
+        (0..count)
+            .map(|i| {
+                let n = if i == zero {
+                    0
+                } else if rng.random_bool(0.5) {
+                    rng.random_range(1..=10000)
+                } else {
+                    -rng.random_range(1..=10000)
+                };
+                n.to_string()
+            })
+            .collect(),
+    )
+}
+
+// Day 21: size monkeys yelling numbers, humn among them, and the monkeys
+// that work on them.  Every division comes out even, so part 1 has an
+// exact answer, and the two sides of root are equal, so humn's own number
+// is the answer to part 2.  humn is never a divisor, so there's only one.
+fn monkey_jobs(rng: &mut Rng, size: usize) -> String {
+    // Keeps the numbers exact as f64s.
+    const LIMIT: i64 = 1 << 50;
+
+    struct Expr {
+        name: String,
+        value: i64,
+        has_humn: bool,
+    }
+
+    let mut taken: HashSet<String> = [String::from("root"), String::from("humn")].into();
+    let mut jobs = Vec::new();
+    let mut job = |rng: &mut Rng, jobs: &mut Vec<String>, what: String, value, has_humn| {
+        let name = loop {
+            let name = word(rng, 4);
+            if taken.insert(name.clone()) {
+                break name;
+            }
+        };
+        jobs.push(format!("{}: {}", name, what));
+        Expr {
+            name,
+            value,
+            has_humn,
+        }
+    };
+
+    let humn = rng.random_range(1..=5000);
+    jobs.push(format!("humn: {}", humn));
+    let mut exprs = vec![Expr {
+        name: String::from("humn"),
+        value: humn,
+        has_humn: true,
+    }];
+    for _ in 1..size.max(2) {
+        let n = rng.random_range(1..=20);
+        exprs.push(job(rng, &mut jobs, n.to_string(), n, false));
+    }
+
+    while exprs.len() > 2 {
+        let mut a = exprs.swap_remove(rng.random_range(0..exprs.len()));
+        let mut b = exprs.swap_remove(rng.random_range(0..exprs.len()));
+        let mut ops = vec!['-'];
+        if a.value + b.value <= LIMIT {
+            ops.push('+');
+        }
+        if a.value != 0 && b.value != 0 && a.value.checked_mul(b.value).is_some_and(|n| n <= LIMIT)
+        {
+            ops.push('*');
+        }
+        if b.has_humn {
+            std::mem::swap(&mut a, &mut b);
+        }
+        if b.value != 0 && a.value % b.value == 0 {
+            ops.push('/');
+        }
+        let op = *ops.choose(rng).expect("subtraction is always allowed");
+        if op == '-' && a.value < b.value {
+            std::mem::swap(&mut a, &mut b);
+        }
+        let value = match op {
+            '+' => a.value + b.value,
+            '-' => a.value - b.value,
+            '*' => a.value * b.value,
+            _ => a.value / b.value,
+        };
+        let what = format!("{} {} {}", a.name, op, b.name);
+        exprs.push(job(rng, &mut jobs, what, value, a.has_humn || b.has_humn));
+    }
+
+    // Evens up the side without humn.
+    exprs.sort_by_key(|expr| !expr.has_humn);
+    let (humn_side, other) = (&exprs[0], &exprs[1]);
+    let difference = humn_side.value - other.value;
+    let n = job(
+        rng,
+        &mut jobs,
+        difference.abs().to_string(),
+        difference.abs(),
+        false,
+    );
+    let op = if difference >= 0 { '+' } else { '-' };
+    let other = job(
+        rng,
+        &mut jobs,
+        format!("{} {} {}", other.name, op, n.name),
+        humn_side.value,
+        false,
+    );
+    jobs.push(format!("root: {} + {}", humn_side.name, other.name));
+    jobs.shuffle(rng);
+    lines(jobs)
+}
+
+// Day 22: a board folded from six size by size faces, and a path across
+// it.  The faces are laid out as in the example or as in the real input.
+fn board(rng: &mut Rng, size: usize) -> String {
+    const NETS: &[&[&str]] = &[&["..#.", "###.", "..##"], &[".##", ".#.", "##.", "#.."]];
+    let side = size.max(2);
+    let net = NETS.choose(rng).expect("nets to choose from");
+
+    let mut map: Vec<String> = Vec::new();
+    for faces in net.iter() {
+        for _ in 0..side {
+            let row: String = faces
+                .chars()
+                .flat_map(|face| {
+                    let tiles: Vec<char> = (0..side)
+                        .map(|_| match face {
+                            '#' if rng.random_bool(0.1) => '#',
+                            '#' => '.',
+                            _ => ' ',
+                        })
+                        .collect();
+                    tiles
+                })
+                .collect();
+            map.push(row.trim_end().to_string());
+        }
+    }
+    // The path starts on the leftmost open tile of the top row.
+    let start = map[0].find(['.', '#']).expect("the top row has a face");
+    map[0].replace_range(start..start + 1, ".");
+
+    let mut path = rng.random_range(1..=2 * side).to_string();
+    for _ in 0..side * 4 {
+        let turn = if rng.random_bool(0.5) { 'L' } else { 'R' };
+        path += &format!("{}{}", turn, rng.random_range(1..=2 * side));
+    }
+    lines(map) + "\n" + &path + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn test_same_seed_same_input() {
+        for day in 1..=22 {
+            assert_eq!(input(day, 20, 7), input(day, 20, 7));
+            assert_ne!(input(day, 20, 7), input(day, 20, 8), "day {}", day);
+        }
+        assert_eq!(input(23, 20, 7), None);
+    }
+
+    #[test]
+    fn test_every_part_has_an_answer() {
+        for day in days::DAYS {
+            // Day 19 takes a while per blueprint, so it gets fewer.
+            let size = if day.day == 19 { 3 } else { 12 };
+            let input = input(day.day, size, 1).expect("every day has a generator");
+            // Day 15's part 2 searches four million rows, which takes too
+            // long without optimizations.
+            let parts = if day.day == 15 {
+                &day.parts[..1]
+            } else {
+                &day.parts[..]
+            };
+            for part in parts.iter().flatten() {
+                if let Err(e) = part(&input, &[]) {
+                    panic!("day {}: {}\n{}", day.day, e, input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_humn_is_the_answer() {
+        let input = input(21, 30, 3).unwrap();
+        let humn: i64 = input
+            .lines()
+            .find_map(|line| line.strip_prefix("humn: "))
+            .unwrap()
+            .parse()
+            .unwrap();
+        let jobs = day21::parse_all_jobs(&input).unwrap();
+        assert_eq!(jobs.loss(humn as f64), 0.0);
+    }
+}
@@ -0,0 +1,7 @@
+// The registry of days, their recorded answers, generated inputs and
+// pictures, shared by the aoc binary, the benchmarks and the tests.
+
+pub mod answers;
+pub mod days;
+pub mod draw;
+pub mod generate;
@@ -0,0 +1,788 @@
+// Runs any or all of the days from a single binary, e.g.
+//
+//     aoc run 16 --part 2 --input day16/input.txt
+//     aoc run 15 --input - --row 10 --bound 20 < example.txt
+//     aoc run all --format json
+//     aoc verify
+//     aoc fetch all
+//     aoc submit 3 1
+//     aoc generate 18 --size 5000 --seed 7
+//     aoc draw 14 cave.png --scale 4 --frames frames --every 100
+//
+// Any other --NAME VALUE pair overrides one of a single day's parameters.
+// --format json prints one JSON object per part instead of a table; see
+// common::report.
+// verify checks each day's real input against its recorded answers.txt,
+// and fails only on a wrong answer or an error; parts with no recorded
+// answer are reported as missing.
+// Real inputs are downloaded on first use, or ahead of time with fetch;
+// see common::fetch for the settings.  submit sends an answer, worked out
+// from the real input unless it's given, to the puzzle site; see
+// common::submit for the checks made first.  generate prints a random
+// input for a day; see aoc::generate.  draw saves a picture of a grid-based
+// day as PNG, or PPM for any other extension, and for a simulation maybe
+// one frame per step; see aoc::draw.
+// Built with --features parallel, run and verify work on the days at
+// once, as do the slowest days on their own loops; see common::parallel.
+
+use aoc::answers;
+use aoc::days::{self, Day};
+use aoc::{draw, generate};
+use common::cli::Format;
+use common::fetch::Fetcher;
+use common::parallel;
+use common::report::{self, Report};
+use common::submit::{self, Verdict};
+use grid::image::{Image, ImageFrames, ImageWriter};
+use std::error::Error;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "usage: aoc run <DAY|all> [--part <1|2>] [--input <PATH|->]
+               [--format text|json] [--NAME VALUE]...
+       aoc verify [DAY|all]
+       aoc fetch [DAY|all]
+       aoc submit DAY PART [ANSWER]
+       aoc generate DAY [--size N] [--seed N]
+       aoc draw DAY OUTPUT [--input <PATH|->] [--scale N]
+                [--colors CHAR=RRGGBB,...] [--frames DIR] [--every N]";
+
+#[derive(Debug)]
+struct RunArgs {
+    days: Vec<&'static Day>,
+    part: Option<usize>,
+    input: Option<String>,
+    format: Format,
+    params: Vec<(String, String)>,
+}
+
+fn parse_days(arg: &str) -> Result<Vec<&'static Day>, Box<dyn Error>> {
+    match arg {
+        "all" => Ok(days::DAYS.iter().collect()),
+        day => {
+            let day = day
+                .parse::<u32>()
+                .map_err(|_| format!("not a day: {:?}", day))?;
+            Ok(vec![days::find(day).ok_or(format!("no such day: {}", day))?])
+        }
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, Box<dyn Error>> {
+    let mut args = args.iter();
+
+    let days = parse_days(args.next().ok_or("missing day")?)?;
+
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut params = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next().ok_or("--part needs a value")?;
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("part should be 1 or 2, not {:?}", value).into()),
+                };
+            }
+            "--input" => {
+                input = Some(args.next().ok_or("--input needs a value")?.clone());
+            }
+            "--format" => {
+                format = args.next().ok_or("--format needs a value")?.parse()?;
+            }
+            flag if flag.starts_with("--") => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| format!("{} needs a value", flag))?;
+                params.push((flag[2..].to_string(), value.clone()));
+            }
+            _ => return Err(format!("unexpected argument {:?}", arg).into()),
+        }
+    }
+
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used with a single day".into());
+    }
+    if !params.is_empty() && days.len() > 1 {
+        return Err("parameters can only be used with a single day".into());
+    }
+
+    Ok(RunArgs {
+        days,
+        part,
+        input,
+        format,
+        params,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+struct GenerateArgs {
+    day: u32,
+    size: usize,
+    seed: u64,
+}
+
+fn parse_generate_args(args: &[String]) -> Result<GenerateArgs, Box<dyn Error>> {
+    let mut args = args.iter();
+    let day = args.next().ok_or("missing day")?;
+    let day = parse_days(day)?;
+    let [day] = day[..] else {
+        return Err("generate needs a single day".into());
+    };
+
+    let mut generate = GenerateArgs {
+        day: day.day,
+        size: 100,
+        seed: 0,
+    };
+    while let Some(flag) = args.next() {
+        if flag != "--size" && flag != "--seed" {
+            return Err(format!("unexpected argument {:?}", flag).into());
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{} needs a value", flag))?;
+        let not_a_number = |_| format!("{} should be a number, not {:?}", &flag[2..], value);
+        if flag == "--size" {
+            generate.size = value.parse().map_err(not_a_number)?;
+        } else {
+            generate.seed = value.parse().map_err(not_a_number)?;
+        }
+    }
+    Ok(generate)
+}
+
+#[derive(Debug, PartialEq)]
+struct DrawArgs {
+    day: u32,
+    output: String,
+    input: Option<String>,
+    scale: usize,
+    // Palette overrides; see grid::image::Palette::apply.
+    colors: String,
+    frames: Option<String>,
+    every: usize,
+}
+
+fn parse_draw_args(args: &[String]) -> Result<DrawArgs, Box<dyn Error>> {
+    let mut args = args.iter();
+    let day = parse_days(args.next().ok_or("missing day")?)?;
+    let [day] = day[..] else {
+        return Err("draw needs a single day".into());
+    };
+    let output = args.next().ok_or("missing output path")?;
+
+    let mut draw = DrawArgs {
+        day: day.day,
+        output: output.clone(),
+        input: None,
+        scale: 1,
+        colors: String::new(),
+        frames: None,
+        every: 1,
+    };
+    while let Some(flag) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("{} needs a value", flag))?
+            .clone();
+        let number = || {
+            value
+                .parse()
+                .map_err(|_| format!("{} should be a number, not {:?}", &flag[2..], value))
+        };
+        match flag.as_str() {
+            "--input" => draw.input = Some(value.clone()),
+            "--scale" => draw.scale = number()?,
+            "--colors" => draw.colors = value.clone(),
+            "--frames" => draw.frames = Some(value.clone()),
+            "--every" => draw.every = number()?,
+            _ => return Err(format!("unexpected argument {:?}", flag).into()),
+        }
+    }
+    Ok(draw)
+}
+
+// Saves the picture, and the frames if asked, returning how many frames
+// there were.
+fn draw_day(args: &DrawArgs, fetcher: &Fetcher) -> Result<usize, Box<dyn Error>> {
+    let drawing = draw::find(args.day).ok_or_else(|| format!("day {} has no picture", args.day))?;
+    if args.frames.is_some() && !drawing.animated {
+        return Err(format!("day {} isn't a simulation, so it has no frames", args.day).into());
+    }
+    let palette = (drawing.palette)().apply(&args.colors)?;
+    let input = match &args.input {
+        Some(path) => common::cli::read_input(path)?,
+        None => fetcher.load(args.day)?,
+    };
+
+    let mut frames = args.frames.as_ref().map(|dir| {
+        ImageFrames::with_writer(dir, ImageWriter::new(palette.clone()).scale(args.scale))
+            .every(args.every)
+    });
+    let text = (drawing.draw)(&input, &mut frames)?;
+    Image::from_text(&text, &palette)
+        .scaled(args.scale)
+        .save(&args.output)
+        .map_err(|e| format!("{}: {}", args.output, e))?;
+    match frames {
+        Some(frames) => Ok(frames.finish()?),
+        None => Ok(0),
+    }
+}
+
+#[derive(Debug)]
+struct Row {
+    day: u32,
+    part: usize,
+    // None if the day has no solver for this part.
+    answer: Option<Result<String, String>>,
+    elapsed: Option<Duration>,
+    // None if the input couldn't be read.
+    input_hash: Option<String>,
+}
+
+fn run_day(
+    day: &Day,
+    part: Option<usize>,
+    input_path: Option<&str>,
+    params: &[(String, String)],
+    fetcher: &Fetcher,
+) -> Vec<Row> {
+    let parts: Vec<usize> = match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let input = match input_path {
+        Some(path) => common::cli::read_input(path),
+        None => fetcher.load(day.day),
+    }
+    .map_err(|e| e.to_string());
+    let input_hash = input.as_deref().ok().map(report::input_hash);
+
+    parts
+        .into_iter()
+        .map(|part| {
+            let (answer, elapsed) = match (&input, day.parts[part - 1]) {
+                (_, None) => (None, None),
+                (Err(e), _) => (Some(Err(e.clone())), None),
+                (Ok(input), Some(solver)) => {
+                    let start = Instant::now();
+                    let answer = solver(input, params).map_err(|e| e.to_string());
+                    (Some(answer), Some(start.elapsed()))
+                }
+            };
+            Row {
+                day: day.day,
+                part,
+                answer,
+                elapsed,
+                input_hash: input_hash.clone(),
+            }
+        })
+        .collect()
+}
+
+fn print_table(rows: &[Row]) {
+    let answer_lines = |row: &Row| -> Vec<String> {
+        match &row.answer {
+            Some(Ok(answer)) => answer.lines().map(String::from).collect(),
+            Some(Err(e)) => vec![format!("error: {}", e)],
+            None => vec![String::from("(not implemented)")],
+        }
+    };
+    let width = rows
+        .iter()
+        .flat_map(answer_lines)
+        .map(|line| line.chars().count())
+        .chain(["answer".len()])
+        .max()
+        .unwrap_or(0);
+
+    println!(
+        "{:>3}  {:>4}  {:<width$}  {:>10}",
+        "day", "part", "answer", "time"
+    );
+    for row in rows {
+        let elapsed = row
+            .elapsed
+            .map(|elapsed| format!("{:.2?}", elapsed))
+            .unwrap_or_default();
+        let lines = answer_lines(row);
+        let first = lines.first().map(String::as_str).unwrap_or("");
+        println!(
+            "{:>3}  {:>4}  {:<width$}  {:>10}",
+            row.day, row.part, first, elapsed
+        );
+        for line in lines.iter().skip(1) {
+            println!("{:>3}  {:>4}  {}", "", "", line);
+        }
+    }
+}
+
+fn print_json(rows: &[Row]) {
+    for row in rows {
+        let report = Report {
+            day: row.day,
+            part: row.part,
+            answer: row
+                .answer
+                .clone()
+                .unwrap_or_else(|| Err(String::from("not implemented"))),
+            elapsed: row.elapsed,
+            input_hash: row.input_hash.clone(),
+        };
+        println!("{}", report.to_json());
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Status {
+    Pass,
+    Fail(String),
+    // No answer is recorded for this part.
+    Missing,
+}
+
+#[derive(Debug)]
+struct Check {
+    day: u32,
+    part: usize,
+    status: Status,
+}
+
+fn check_answer(expected: &str, answer: Option<Result<String, String>>) -> Status {
+    match answer {
+        None => Status::Fail(String::from("not implemented")),
+        Some(Err(e)) => Status::Fail(format!("error: {}", e)),
+        Some(Ok(answer)) if answer.trim_end() == expected => Status::Pass,
+        Some(Ok(answer)) => Status::Fail(format!(
+            "expected {:?}, got {:?}",
+            expected,
+            answer.trim_end()
+        )),
+    }
+}
+
+// Runs the day's real input for each part that has a recorded answer.
+fn verify_day(day: &Day, fetcher: &Fetcher) -> Vec<Check> {
+    let answers = answers::load_answers(day.answers);
+    (1..=2)
+        .map(|part| {
+            let status = match &answers {
+                Err(e) => Status::Fail(e.clone()),
+                Ok(answers) => match &answers[part - 1] {
+                    None => Status::Missing,
+                    Some(expected) => {
+                        let row = run_day(day, Some(part), None, &[], fetcher).remove(0);
+                        check_answer(expected, row.answer)
+                    }
+                },
+            };
+            Check {
+                day: day.day,
+                part,
+                status,
+            }
+        })
+        .collect()
+}
+
+fn print_checks(checks: &[Check]) {
+    println!("{:>3}  {:>4}  status", "day", "part");
+    for check in checks {
+        let status = match &check.status {
+            Status::Pass => String::from("pass"),
+            Status::Fail(reason) => format!("FAIL     {}", reason),
+            Status::Missing => String::from("missing"),
+        };
+        println!("{:>3}  {:>4}  {}", check.day, check.part, status);
+    }
+
+    let count = |f: fn(&Status) -> bool| checks.iter().filter(|c| f(&c.status)).count();
+    println!(
+        "{} passed, {} failed, {} missing",
+        count(|s| *s == Status::Pass),
+        count(|s| matches!(s, Status::Fail(_))),
+        count(|s| *s == Status::Missing)
+    );
+}
+
+// Parses the optional DAY|all argument of verify and fetch.
+fn parse_optional_days(args: &[String]) -> Result<Vec<&'static Day>, Box<dyn Error>> {
+    match args {
+        [] => parse_days("all"),
+        [day] => parse_days(day),
+        [_, extra, ..] => Err(format!("unexpected argument {:?}", extra).into()),
+    }
+}
+
+fn run(args: &[String]) -> Result<bool, Box<dyn Error>> {
+    let fetcher = Fetcher::from_env();
+    match args.first().map(String::as_str) {
+        Some("run") => {
+            let run_args = parse_run_args(&args[1..])?;
+            let rows: Vec<Row> = parallel::map(&run_args.days, |day| {
+                run_day(
+                    day,
+                    run_args.part,
+                    run_args.input.as_deref(),
+                    &run_args.params,
+                    &fetcher,
+                )
+            })
+            .into_iter()
+            .flatten()
+            .collect();
+            match run_args.format {
+                Format::Text => print_table(&rows),
+                Format::Json => print_json(&rows),
+            }
+            Ok(rows.iter().all(|row| !matches!(row.answer, Some(Err(_)))))
+        }
+        Some("verify") => {
+            let days = parse_optional_days(&args[1..])?;
+            let checks: Vec<Check> = parallel::map(&days, |day| verify_day(day, &fetcher))
+                .into_iter()
+                .flatten()
+                .collect();
+            print_checks(&checks);
+            Ok(checks
+                .iter()
+                .all(|check| !matches!(check.status, Status::Fail(_))))
+        }
+        Some("fetch") => {
+            let days = parse_optional_days(&args[1..])?;
+            let mut ok = true;
+            for day in days {
+                match fetcher.load(day.day) {
+                    Ok(_) => println!("day {}: {}", day.day, fetcher.input_path(day.day).display()),
+                    Err(e) => {
+                        println!("day {}: error: {}", day.day, e);
+                        ok = false;
+                    }
+                }
+            }
+            Ok(ok)
+        }
+        Some("submit") => {
+            let (day, part, answer) = match &args[1..] {
+                [day, part] => (day, part, None),
+                [day, part, answer] => (day, part, Some(answer.clone())),
+                _ => return Err("submit needs a day, a part and maybe an answer".into()),
+            };
+            if day == "all" {
+                return Err("submit needs a single day".into());
+            }
+            let day = parse_days(day)?[0];
+            let part = match part.as_str() {
+                "1" => 1,
+                "2" => 2,
+                _ => return Err(format!("part should be 1 or 2, not {:?}", part).into()),
+            };
+            let answer = match answer {
+                Some(answer) => answer,
+                None => match run_day(day, Some(part), None, &[], &fetcher)
+                    .remove(0)
+                    .answer
+                {
+                    Some(Ok(answer)) => answer,
+                    Some(Err(e)) => return Err(e.into()),
+                    None => {
+                        return Err(format!("day {} part {} isn't solved", day.day, part).into())
+                    }
+                },
+            };
+            let verdict = submit::submit(&fetcher, day.day, part, answer.trim_end())?;
+            println!(
+                "day {} part {}: {}: {}",
+                day.day,
+                part,
+                answer.trim_end(),
+                verdict
+            );
+            Ok(verdict == Verdict::Right)
+        }
+        Some("generate") => {
+            let args = parse_generate_args(&args[1..])?;
+            let input = generate::input(args.day, args.size, args.seed)
+                .ok_or_else(|| format!("no generator for day {}", args.day))?;
+            print!("{}", input);
+            Ok(true)
+        }
+        Some("draw") => {
+            let args = parse_draw_args(&args[1..])?;
+            let frames = draw_day(&args, &fetcher)?;
+            println!("day {}: {}", args.day, args.output);
+            if let Some(dir) = &args.frames {
+                println!("day {}: {} frames in {}", args.day, frames, dir);
+            }
+            Ok(true)
+        }
+        Some(command) => Err(format!("unknown command {:?}", command).into()),
+        None => Err("missing command".into()),
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("{}", USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_run_args_single_day() {
+        let run_args = parse_run_args(&args("16 --part 2 --input path")).unwrap();
+        assert_eq!(run_args.days.len(), 1);
+        assert_eq!(run_args.days[0].day, 16);
+        assert_eq!(run_args.part, Some(2));
+        assert_eq!(run_args.input, Some("path".into()));
+        assert!(run_args.params.is_empty());
+    }
+
+    #[test]
+    fn test_parse_run_args_params() {
+        let run_args = parse_run_args(&args("15 --input - --row 10 --bound 20")).unwrap();
+        assert_eq!(run_args.input, Some("-".into()));
+        assert_eq!(
+            run_args.params,
+            vec![("row".into(), "10".into()), ("bound".into(), "20".into())]
+        );
+    }
+
+    #[test]
+    fn test_parse_run_args_all() {
+        let run_args = parse_run_args(&args("all")).unwrap();
+        assert_eq!(run_args.days.len(), days::DAYS.len());
+        assert_eq!(run_args.part, None);
+        assert_eq!(run_args.input, None);
+        assert_eq!(run_args.format, Format::Text);
+
+        let run_args = parse_run_args(&args("all --format json")).unwrap();
+        assert_eq!(run_args.format, Format::Json);
+        assert!(run_args.params.is_empty());
+    }
+
+    #[test]
+    fn test_parse_run_args_errors() {
+        assert!(parse_run_args(&args("")).is_err());
+        assert!(parse_run_args(&args("99")).is_err());
+        assert!(parse_run_args(&args("1 --part 3")).is_err());
+        assert!(parse_run_args(&args("all --input path")).is_err());
+        assert!(parse_run_args(&args("all --row 10")).is_err());
+        assert!(parse_run_args(&args("15 --row")).is_err());
+        assert!(parse_run_args(&args("1 --format csv")).is_err());
+    }
+
+    #[test]
+    fn test_parse_generate_args() {
+        assert_eq!(
+            parse_generate_args(&args("18 --seed 7")).unwrap(),
+            GenerateArgs {
+                day: 18,
+                size: 100,
+                seed: 7
+            }
+        );
+        assert_eq!(parse_generate_args(&args("5 --size 20")).unwrap().size, 20);
+        assert!(parse_generate_args(&args("all")).is_err());
+        assert!(parse_generate_args(&args("5 --size")).is_err());
+        assert!(parse_generate_args(&args("5 --size big")).is_err());
+        assert!(parse_generate_args(&args("5 --row 10")).is_err());
+    }
+
+    #[test]
+    fn test_parse_draw_args() {
+        assert_eq!(
+            parse_draw_args(&args("14 cave.png --scale 4 --frames out --every 10")).unwrap(),
+            DrawArgs {
+                day: 14,
+                output: String::from("cave.png"),
+                input: None,
+                scale: 4,
+                colors: String::new(),
+                frames: Some(String::from("out")),
+                every: 10,
+            }
+        );
+        assert_eq!(
+            parse_draw_args(&args("8 trees.ppm --colors 9=ffffff"))
+                .unwrap()
+                .colors,
+            "9=ffffff"
+        );
+        assert!(parse_draw_args(&args("all out.png")).is_err());
+        assert!(parse_draw_args(&args("8")).is_err());
+        assert!(parse_draw_args(&args("8 out.png --scale big")).is_err());
+        assert!(parse_draw_args(&args("8 out.png --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_draw_day() {
+        let dir = std::env::temp_dir().join(format!("aoc-draw-{}", std::process::id()));
+        let output = dir.join("cave.ppm");
+        let mut draw_args = parse_draw_args(&args("14 out.ppm --scale 2")).unwrap();
+        draw_args.output = output.to_str().unwrap().to_string();
+        draw_args.input = Some(format!(
+            "{}/../day14/example.txt",
+            env!("CARGO_MANIFEST_DIR")
+        ));
+        draw_args.frames = Some(dir.join("frames").to_str().unwrap().to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+        assert_eq!(draw_day(&draw_args, &Fetcher::from_env()).unwrap(), 93);
+        assert!(std::fs::read(&output).unwrap().starts_with(b"P6\n"));
+        assert!(dir.join("frames/000093.png").exists());
+
+        draw_args.day = 8;
+        assert!(draw_day(&draw_args, &Fetcher::from_env()).is_err());
+        draw_args.day = 1;
+        assert!(draw_day(&draw_args, &Fetcher::from_env()).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_run_day_missing_input() {
+        let fetcher = Fetcher::from_env();
+        let rows = run_day(
+            days::find(1).unwrap(),
+            Some(1),
+            Some("no/such/file"),
+            &[],
+            &fetcher,
+        );
+        assert_eq!(rows.len(), 1);
+        assert!(matches!(rows[0].answer, Some(Err(_))));
+        assert_eq!(rows[0].input_hash, None);
+    }
+
+    #[test]
+    fn test_run_day_with_params() {
+        let path = std::env::temp_dir().join("aoc-test-day11-example.txt");
+        std::fs::write(&path, DAY11_EXAMPLE).unwrap();
+        let path = path.to_str().unwrap();
+        let fetcher = Fetcher::from_env();
+
+        let rows = run_day(days::find(11).unwrap(), None, Some(path), &[], &fetcher);
+        assert_eq!(rows[0].answer, Some(Ok("10605".into())));
+        assert_eq!(rows[1].answer, Some(Ok("2713310158".into())));
+        assert_eq!(rows[0].input_hash, Some(report::input_hash(DAY11_EXAMPLE)));
+
+        let params = [("part2-rounds".to_string(), "20".to_string())];
+        let rows = run_day(
+            days::find(11).unwrap(),
+            Some(2),
+            Some(path),
+            &params,
+            &fetcher,
+        );
+        assert_eq!(rows[0].answer, Some(Ok("10197".into())));
+
+        let params = [("rounds".to_string(), "20".to_string())];
+        let rows = run_day(
+            days::find(11).unwrap(),
+            Some(2),
+            Some(path),
+            &params,
+            &fetcher,
+        );
+        assert!(matches!(rows[0].answer, Some(Err(_))));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_check_answer() {
+        assert_eq!(check_answer("42", Some(Ok("42".into()))), Status::Pass);
+        assert_eq!(
+            check_answer("##\n..", Some(Ok("##\n..\n".into()))),
+            Status::Pass
+        );
+        assert!(matches!(
+            check_answer("42", Some(Ok("43".into()))),
+            Status::Fail(_)
+        ));
+        assert!(matches!(
+            check_answer("42", Some(Err("no input".into()))),
+            Status::Fail(_)
+        ));
+        assert!(matches!(check_answer("42", None), Status::Fail(_)));
+    }
+
+    #[test]
+    fn test_verify_day() {
+        // The input is already in the fetcher's cache, so nothing is
+        // downloaded.
+        let fetcher = Fetcher {
+            session: None,
+            input_dir: std::env::temp_dir().join(format!("aoc-verify-{}", std::process::id())),
+            ..Fetcher::from_env()
+        };
+        let dir = fetcher.input_path(11).parent().unwrap().to_path_buf();
+        std::fs::create_dir_all(&dir).unwrap();
+        let answers = dir.join("answers.txt");
+        std::fs::write(fetcher.input_path(11), DAY11_EXAMPLE).unwrap();
+        std::fs::write(&answers, "part 1: 10605\npart 2: 1\n").unwrap();
+
+        let day = Day {
+            answers: String::leak(answers.to_str().unwrap().to_string()),
+            ..*days::find(11).unwrap()
+        };
+        let checks = verify_day(&day, &fetcher);
+        assert_eq!(checks[0].status, Status::Pass);
+        assert!(matches!(checks[1].status, Status::Fail(_)));
+
+        std::fs::write(&answers, "part 1: 10605\n").unwrap();
+        let checks = verify_day(&day, &fetcher);
+        assert_eq!(checks[0].status, Status::Pass);
+        assert_eq!(checks[1].status, Status::Missing);
+        std::fs::remove_dir_all(&fetcher.input_dir).unwrap();
+    }
+
+    const DAY11_EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+}
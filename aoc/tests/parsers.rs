@@ -0,0 +1,314 @@
+// Property tests for every text parser in the workspace.
+//
+// Whatever they're given, parsers must return Ok or Err, and promptly: a
+// panic fails the test, and so does taking longer than TIMEOUT, which is
+// how a parser stuck in a loop shows up.  Each parser is fed arbitrary
+// text, and the day's worked example with a few random edits, which gets
+// much further into the parser than noise does.  Whatever a day parses it
+// has to solve on the same terms, if only with an error.  Where a printer
+// exists, parsing what it prints has to give back what was printed.
+//
+// For a longer fuzzing run, raise the number of cases:
+//
+//     PROPTEST_CASES=100000 cargo test --release -p aoc --test parsers
+
+use aoc::generate;
+use common::submit::{self, Attempt, Verdict};
+use common::Solution;
+use grid::Grid;
+use proptest::prelude::*;
+use std::panic;
+use std::str::FromStr;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+// Runs f on its own thread, failing if it panics or doesn't finish in time.
+// A thread that's stuck is left behind; the test fails either way.
+fn promptly<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
+        // The receiver is only gone if we've already given up.
+        let _ = tx.send(f());
+    });
+    match rx.recv_timeout(TIMEOUT) {
+        Ok(value) => value,
+        Err(RecvTimeoutError::Timeout) => panic!("gave up after {:?}", TIMEOUT),
+        Err(RecvTimeoutError::Disconnected) => {
+            panic::resume_unwind(handle.join().expect_err("f panicked"))
+        }
+    }
+}
+
+fn example(day: u32) -> String {
+    let path = format!("{}/../day{}/example.txt", env!("CARGO_MANIFEST_DIR"), day);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
+
+// One of the example's non-blank lines, for parsers that read a line.
+fn example_line(day: u32, index: usize) -> String {
+    let example = example(day);
+    let lines: Vec<&str> = example.lines().filter(|l| !l.trim().is_empty()).collect();
+    lines[index % lines.len()].to_string()
+}
+
+#[derive(Debug, Clone)]
+enum Edit {
+    Delete(usize),
+    Insert(usize, char),
+    Replace(usize, char),
+    Truncate(usize),
+    // Copies a stretch of the text to somewhere else.
+    Duplicate(usize, usize, usize),
+}
+
+// Characters that turn up in the puzzle inputs, and a few that don't.
+const NOISE: &str = "0123456789-+ \n\t,:;=[]<>()#.^v~abcxyzLRUDAB\u{0}é";
+
+fn edit() -> impl Strategy<Value = Edit> {
+    let ch = proptest::sample::select(NOISE.chars().collect::<Vec<_>>());
+    prop_oneof![
+        any::<usize>().prop_map(Edit::Delete),
+        (any::<usize>(), ch.clone()).prop_map(|(i, ch)| Edit::Insert(i, ch)),
+        (any::<usize>(), ch).prop_map(|(i, ch)| Edit::Replace(i, ch)),
+        any::<usize>().prop_map(Edit::Truncate),
+        (any::<usize>(), any::<usize>(), 1..20usize)
+            .prop_map(|(from, to, len)| Edit::Duplicate(from, to, len)),
+    ]
+}
+
+// Applies the edits, with positions wrapped around to fit the text.
+fn apply(text: &str, edits: &[Edit]) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for edit in edits {
+        let n = chars.len();
+        match *edit {
+            Edit::Delete(i) if n > 0 => {
+                chars.remove(i % n);
+            }
+            Edit::Insert(i, ch) => chars.insert(i % (n + 1), ch),
+            Edit::Replace(i, ch) if n > 0 => chars[i % n] = ch,
+            Edit::Truncate(i) => chars.truncate(i % (n + 1)),
+            Edit::Duplicate(from, to, len) if n > 0 => {
+                let from = from % n;
+                let stretch: Vec<char> = chars[from..(from + len).min(n)].to_vec();
+                let to = to % (n + 1);
+                chars.splice(to..to, stretch);
+            }
+            _ => {}
+        }
+    }
+    chars.into_iter().collect()
+}
+
+// Parses the input and, if that works, has the solution run both parts
+// on it.
+fn solves<S: Solution + Send + 'static>(solution: S, input: String) {
+    promptly(move || {
+        if let Ok(input) = S::parse(&input) {
+            let _ = solution.part1(&input);
+            let _ = solution.part2(&input);
+        }
+    })
+}
+
+fn parses_as<T: FromStr + 'static>(input: String) {
+    promptly(move || {
+        let _ = input.parse::<T>();
+    })
+}
+
+// The solution to run, by default the one the day's binary runs.
+macro_rules! instance {
+    ($solution:ty) => {
+        <$solution>::default()
+    };
+    ($solution:ty, $instance:expr) => {
+        $instance
+    };
+}
+
+// Every day's Solution::parse, which is the parser its binary uses.  The
+// inputs from aoc::generate are meant to be valid, so they have to parse.
+// Those are as big as the real puzzles, which some days take minutes over
+// in a debug build; those days are given a smaller puzzle to solve.
+macro_rules! solution_parsers {
+    ($($name:ident: $day:literal => $solution:ty $(= $instance:expr)?,)*) => {
+        proptest! {
+            $(
+                #[test]
+                fn $name(
+                    text in "\\PC{0,200}",
+                    edits in prop::collection::vec(edit(), 1..8),
+                    size in 0..50usize,
+                    seed in any::<u64>(),
+                ) {
+                    solves(instance!($solution $(, $instance)?), text);
+                    solves(instance!($solution $(, $instance)?), apply(&example($day), &edits));
+                    let generated = generate::input($day, size, seed).expect("every day has a generator");
+                    let parsed = {
+                        let generated = generated.clone();
+                        promptly(move || <$solution>::parse(&generated).map_err(|e| e.to_string()))
+                    };
+                    prop_assert!(parsed.is_ok(), "{}", parsed.unwrap_err());
+                    solves(instance!($solution $(, $instance)?), generated);
+                }
+            )*
+        }
+    };
+}
+
+solution_parsers! {
+    day1_parse: 1 => day1::Day1,
+    day2_parse: 2 => day2::Day2,
+    day3_parse: 3 => day3::Day3,
+    day4_parse: 4 => day4::Day4,
+    day5_parse: 5 => day5::Day5,
+    day6_parse: 6 => day6::Day6,
+    day7_parse: 7 => day7::Day7,
+    day8_parse: 8 => day8::Day8,
+    day9_parse: 9 => day9::Day9,
+    day10_parse: 10 => day10::Day10,
+    day11_parse: 11 => day11::Day11 = day11::Day11 { part1_rounds: 20, part2_rounds: 100 },
+    day12_parse: 12 => day12::Day12,
+    day13_parse: 13 => day13::Day13,
+    day14_parse: 14 => day14::Day14,
+    day15_parse: 15 => day15::Day15 = day15::Day15 { row: 10, bound: 20 },
+    day16_parse: 16 => day16::Day16 = day16::Day16 { part1_minutes: 8, part2_minutes: 6 },
+    day17_parse: 17 => day17::Day17,
+    day18_parse: 18 => day18::Day18,
+    day19_parse: 19 => day19::Day19 = day19::Day19 { part1_minutes: 12, part2_minutes: 14 },
+    day20_parse: 20 => day20::Day20,
+    day21_parse: 21 => day21::Day21,
+    day22_parse: 22 => day22::Day22,
+}
+
+// Empty input is where solving what parses most often goes wrong, so every
+// day gets it whatever proptest comes up with.
+fn solves_empty<S: Solution + Default + Send + 'static>() {
+    solves(S::default(), String::new())
+}
+
+#[test]
+fn empty_inputs() {
+    solves_empty::<day1::Day1>();
+    solves_empty::<day2::Day2>();
+    solves_empty::<day3::Day3>();
+    solves_empty::<day4::Day4>();
+    solves_empty::<day5::Day5>();
+    solves_empty::<day6::Day6>();
+    solves_empty::<day7::Day7>();
+    solves_empty::<day8::Day8>();
+    solves_empty::<day9::Day9>();
+    solves_empty::<day10::Day10>();
+    solves_empty::<day11::Day11>();
+    solves_empty::<day12::Day12>();
+    solves_empty::<day13::Day13>();
+    solves_empty::<day14::Day14>();
+    solves_empty::<day15::Day15>();
+    solves_empty::<day16::Day16>();
+    solves_empty::<day17::Day17>();
+    solves_empty::<day18::Day18>();
+    solves_empty::<day19::Day19>();
+    solves_empty::<day20::Day20>();
+    solves_empty::<day21::Day21>();
+    solves_empty::<day22::Day22>();
+}
+
+// The FromStr impls, fed whole examples or single lines as they expect.
+macro_rules! from_str_parsers {
+    ($($name:ident: $day:literal, $whole:literal => $type:ty,)*) => {
+        proptest! {
+            $(
+                #[test]
+                fn $name(
+                    text in "\\PC{0,100}",
+                    line in any::<usize>(),
+                    edits in prop::collection::vec(edit(), 1..4),
+                ) {
+                    parses_as::<$type>(text);
+                    let sample = if $whole { example($day) } else { example_line($day, line) };
+                    parses_as::<$type>(apply(&sample, &edits));
+                }
+            )*
+        }
+    };
+}
+
+from_str_parsers! {
+    day4_assignment: 4, false => day4::Assignment,
+    day5_move: 5, false => day5::Move,
+    day8_height_map: 8, true => day8::HeightMap,
+    day12_height_map: 12, true => day12::HeightMap,
+    day15_sensor_data: 15, false => day15::SensorData,
+    day16_valve: 16, false => day16::Valve,
+    verdict: 1, false => Verdict,
+    format: 1, false => common::cli::Format,
+}
+
+fn packet() -> impl Strategy<Value = day13::Data> {
+    let leaf = any::<u32>().prop_map(day13::Data::Num);
+    leaf.prop_recursive(6, 64, 5, |inner| {
+        prop::collection::vec(inner, 0..5).prop_map(day13::Data::List)
+    })
+}
+
+fn attempt() -> impl Strategy<Value = Attempt> {
+    let verdict = prop_oneof![
+        Just(Verdict::Right),
+        Just(Verdict::Wrong),
+        Just(Verdict::TooHigh),
+        Just(Verdict::TooLow),
+        Just(Verdict::WrongLevel),
+        any::<u32>().prop_map(|secs| Verdict::Wait(Duration::from_secs(secs.into()))),
+    ];
+    (any::<u64>(), 1..=2usize, verdict, "[!-~]{1,20}").prop_map(|(time, part, verdict, answer)| {
+        Attempt {
+            time,
+            part,
+            verdict,
+            answer,
+        }
+    })
+}
+
+proptest! {
+    #[test]
+    fn grid_parse(text in "\\PC{0,200}", edits in prop::collection::vec(edit(), 1..8)) {
+        let edited = apply(&example(8), &edits);
+        promptly(move || {
+            let _ = Grid::parse(&text, |ch| ch.to_digit(10));
+            let _ = Grid::parse_padded(&edited, ' ', Some);
+            let _ = grid::SparseGrid::parse(&edited, '.', |ch| (ch == '#').then_some(()));
+        });
+    }
+
+    #[test]
+    fn attempts_parse(text in "\\PC{0,200}") {
+        promptly(move || {
+            let _ = submit::parse_attempts(&text);
+        });
+    }
+
+    #[test]
+    fn day13_round_trip(packets in prop::collection::vec(packet(), 0..4)) {
+        let printed: String = packets.iter().map(|p| format!("{}\n", p)).collect();
+        prop_assert_eq!(day13::parse_packets(&printed), Ok(packets));
+    }
+
+    #[test]
+    fn grid_round_trip(rows in (1..10usize).prop_flat_map(|width| {
+        prop::collection::vec(prop::collection::vec(prop::sample::select(vec!['.', '#', 'S']), width), 0..10)
+    })) {
+        let grid = Grid::from_rows(rows).unwrap();
+        prop_assert_eq!(Grid::parse(&grid.render(|&ch| ch), Some), Ok(grid));
+    }
+
+    #[test]
+    fn attempts_round_trip(attempts in prop::collection::vec(attempt(), 0..5)) {
+        let printed: String = attempts.iter().map(|a| format!("{}\n", a)).collect();
+        prop_assert_eq!(submit::parse_attempts(&printed), Ok(attempts));
+    }
+}
@@ -0,0 +1,198 @@
+// Command-line handling shared by the day binaries:
+//
+//     dayN [INPUT|-] [--format text|json] [--NAME VALUE]...
+//
+// INPUT defaults to the day's own input, downloaded on first use (see
+// fetch), and "-" reads standard input.  Each --NAME VALUE pair overrides
+// one of the day's parameters.  --format json prints one JSON object per
+// part; see report.
+
+use crate::fetch::Fetcher;
+use crate::report::{self, Report};
+use crate::{Error, Solution};
+use std::io::Read;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Instant;
+
+pub const USAGE: &str = "usage: [INPUT|-] [--format text|json] [--NAME VALUE]...";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("format should be text or json, not {:?}", s).into()),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    // None to use the day's default input.
+    pub input: Option<String>,
+    pub format: Format,
+    pub params: Vec<(String, String)>,
+}
+
+pub fn parse_args(args: &[String]) -> Result<Args, Error> {
+    let mut result = Args::default();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if let Some(name) = arg.strip_prefix("--") {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("--{} needs a value", name))?;
+            if name == "format" {
+                result.format = value.parse()?;
+            } else {
+                result.params.push((name.to_string(), value.clone()));
+            }
+        } else if result.input.is_none() {
+            result.input = Some(arg.clone());
+        } else {
+            return Err(format!("unexpected argument {:?}", arg).into());
+        }
+    }
+    Ok(result)
+}
+
+/// Reads the puzzle input from a path, or from standard input for "-".
+pub fn read_input(path: &str) -> Result<String, Error> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e).into())
+    }
+}
+
+/// Reads the input named on the command line, or the day's own input if
+/// none was.
+pub fn load_input(path: Option<&str>, day: u32) -> Result<String, Error> {
+    match path {
+        Some(path) => read_input(path),
+        None => Fetcher::from_env().load(day),
+    }
+}
+
+/// The body of a day's main: reads the input named on the command line,
+/// applies any parameter overrides, and prints both answers.
+pub fn run<S: Solution>(solution: S, day: u32) -> Result<(), Error> {
+    run_with(solution, day, |_, _| {})
+}
+
+/// As `run`, but calls `before` with the solution and the parsed input
+/// ahead of the answers, e.g. to warn about the input.  It isn't called
+/// if the input doesn't parse.
+pub fn run_with<S: Solution>(
+    mut solution: S,
+    day: u32,
+    before: impl FnOnce(&S, &S::Input),
+) -> Result<(), Error> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args = parse_args(&args).map_err(|e| format!("{}\n{}", e, USAGE))?;
+    crate::set_params(&mut solution, &args.params)?;
+    let input = load_input(args.input.as_deref(), day)?;
+    let start = Instant::now();
+    let parsed = solution.parse_input(&input);
+    let parsing = start.elapsed();
+    if let Ok(parsed) = &parsed {
+        before(&solution, parsed);
+    }
+    match (args.format, parsed) {
+        (Format::Text, parsed) => crate::print_parsed_answers(&solution, &parsed?),
+        (Format::Json, Ok(parsed)) => print_reports(
+            &[1, 2]
+                .map(|part| report::report_parsed(&solution, day, &input, &parsed, parsing, part)),
+        ),
+        // Each report says why the input didn't parse.
+        (Format::Json, Err(_)) => print_reports(&[
+            report::report(&solution, day, &input, 1),
+            report::report(&solution, day, &input, 2),
+        ]),
+    }
+}
+
+/// Ends a day binary, printing the error, if there is one, as a message
+/// rather than in its Debug form, and exiting with status 1.
+pub fn exit(result: Result<(), Error>) -> ExitCode {
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Prints each report as a line of JSON, failing if any part did.
+pub fn print_reports(reports: &[Report]) -> Result<(), Error> {
+    for report in reports {
+        println!("{}", report.to_json());
+    }
+    match reports
+        .iter()
+        .find_map(|report| report.answer.as_ref().err())
+    {
+        Some(e) => Err(e.clone().into()),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(parse_args(&args("")).unwrap(), Args::default());
+        assert_eq!(
+            parse_args(&args("- --row 10 --bound 20")).unwrap(),
+            Args {
+                input: Some("-".into()),
+                params: vec![("row".into(), "10".into()), ("bound".into(), "20".into())],
+                ..Args::default()
+            }
+        );
+        assert_eq!(
+            parse_args(&args("--row 10 example.txt")).unwrap(),
+            Args {
+                input: Some("example.txt".into()),
+                params: vec![("row".into(), "10".into())],
+                ..Args::default()
+            }
+        );
+        assert_eq!(
+            parse_args(&args("--format json --row 10")).unwrap(),
+            Args {
+                format: Format::Json,
+                params: vec![("row".into(), "10".into())],
+                ..Args::default()
+            }
+        );
+        assert!(parse_args(&args("--format yaml")).is_err());
+        assert!(parse_args(&args("--row")).is_err());
+        assert!(parse_args(&args("a.txt b.txt")).is_err());
+    }
+
+    #[test]
+    fn test_read_input_missing_file() {
+        let e = read_input("no/such/file").unwrap_err();
+        assert!(e.to_string().starts_with("no/such/file: "));
+    }
+}
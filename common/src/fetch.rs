@@ -0,0 +1,200 @@
+// Puzzle inputs, downloaded once and cached at a canonical path:
+//
+//     $AOC_INPUT_DIR/dayN/input.txt
+//
+// AOC_INPUT_DIR defaults to the workspace root.  Missing inputs are
+// downloaded from $AOC_BASE_URL/2022/day/N/input, https://adventofcode.com
+// by default, with the session cookie in AOC_SESSION.  A cached input is
+// never fetched again, and days that haven't unlocked yet are refused.
+
+use crate::Error;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const YEAR: u32 = 2022;
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+// The puzzle site asks automated tools to say where they come from.
+pub(crate) const USER_AGENT: &str = "github.com/dyoo/advent-2022-rust";
+
+#[derive(Debug, Clone)]
+pub struct Fetcher {
+    pub base_url: String,
+    // None if there's no session token; cached inputs still load.
+    pub session: Option<String>,
+    pub input_dir: PathBuf,
+    // The time that decides which days have unlocked.
+    pub now: SystemTime,
+}
+
+impl Fetcher {
+    /// A fetcher configured from AOC_BASE_URL, AOC_SESSION and
+    /// AOC_INPUT_DIR.
+    pub fn from_env() -> Self {
+        Fetcher {
+            base_url: env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into()),
+            session: env::var("AOC_SESSION")
+                .ok()
+                .map(|session| session.trim().to_string())
+                .filter(|session| !session.is_empty()),
+            input_dir: env::var_os("AOC_INPUT_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(workspace_root),
+            now: SystemTime::now(),
+        }
+    }
+
+    /// Where the day's input is cached.
+    pub fn input_path(&self, day: u32) -> PathBuf {
+        self.input_dir.join(format!("day{}", day)).join("input.txt")
+    }
+
+    /// The day's input, read from the cache if it's there and downloaded
+    /// into it if not.
+    pub fn load(&self, day: u32) -> Result<String, Error> {
+        check_unlocked(day, self.now)?;
+        let path = self.input_path(day);
+        if path.exists() {
+            return fs::read_to_string(&path)
+                .map_err(|e| format!("{}: {}", path.display(), e).into());
+        }
+
+        let session = self.session.as_deref().ok_or_else(|| {
+            format!(
+                "{} is missing; set AOC_SESSION to download it",
+                path.display()
+            )
+        })?;
+        let input = self.download(day, session)?;
+
+        // Written under another name first, so that an interrupted write
+        // never leaves a truncated input in the cache.
+        let partial = path.with_extension("partial");
+        fs::create_dir_all(partial.parent().expect("input path has a directory"))?;
+        fs::write(&partial, &input)?;
+        fs::rename(&partial, &path)?;
+        Ok(input)
+    }
+
+    fn download(&self, day: u32, session: &str) -> Result<String, Error> {
+        let url = format!(
+            "{}/{}/day/{}/input",
+            self.base_url.trim_end_matches('/'),
+            YEAR,
+            day
+        );
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|e| format!("downloading day {}: {}", day, e))?;
+        Ok(response.into_string()?)
+    }
+}
+
+/// The directory above the crates, where each day's files live.
+pub fn workspace_root() -> PathBuf {
+    let common = Path::new(env!("CARGO_MANIFEST_DIR"));
+    common.parent().unwrap_or(common).to_path_buf()
+}
+
+/// When the day's puzzle unlocks: midnight US Eastern time, or 05:00 UTC,
+/// on that day of December.
+pub fn unlocks_at(day: u32) -> SystemTime {
+    // 2022-12-01T05:00:00Z
+    const DAY1: u64 = 1_669_870_800;
+    UNIX_EPOCH + Duration::from_secs(DAY1 + u64::from(day.saturating_sub(1)) * 24 * 60 * 60)
+}
+
+pub(crate) fn check_unlocked(day: u32, now: SystemTime) -> Result<(), Error> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("no such day: {}", day).into());
+    }
+    if now < unlocks_at(day) {
+        return Err(format!("day {} hasn't unlocked yet", day).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::serve_once;
+
+    fn fetcher(name: &str, base_url: String) -> Fetcher {
+        let input_dir = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&input_dir);
+        Fetcher {
+            base_url,
+            session: Some("s3cr3t".into()),
+            input_dir,
+            now: unlocks_at(25),
+        }
+    }
+
+    #[test]
+    fn test_load_downloads_once() {
+        let (url, server) = serve_once("200 OK", "1000\n2000\n");
+        let fetcher = fetcher("once", url);
+
+        assert_eq!(fetcher.load(3).unwrap(), "1000\n2000\n");
+        let request = server.join().unwrap().to_lowercase();
+        assert!(request.starts_with("get /2022/day/3/input "));
+        assert!(request.contains("cookie: session=s3cr3t"));
+        assert_eq!(
+            fs::read_to_string(fetcher.input_path(3)).unwrap(),
+            "1000\n2000\n"
+        );
+
+        // The server is gone, so this can only come from the cache.
+        assert_eq!(fetcher.load(3).unwrap(), "1000\n2000\n");
+        fs::remove_dir_all(&fetcher.input_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_failed_download() {
+        let (url, server) = serve_once(
+            "404 Not Found",
+            "Please don't repeatedly request this endpoint",
+        );
+        let fetcher = fetcher("failed", url);
+
+        assert!(fetcher.load(4).is_err());
+        server.join().unwrap();
+        assert!(!fetcher.input_path(4).exists());
+        let _ = fs::remove_dir_all(&fetcher.input_dir);
+    }
+
+    #[test]
+    fn test_load_without_session() {
+        let fetcher = Fetcher {
+            session: None,
+            ..fetcher("no-session", String::from("http://127.0.0.1:1"))
+        };
+        let e = fetcher.load(1).unwrap_err();
+        assert!(e.to_string().contains("AOC_SESSION"));
+
+        fs::create_dir_all(fetcher.input_path(1).parent().unwrap()).unwrap();
+        fs::write(fetcher.input_path(1), "cached").unwrap();
+        assert_eq!(fetcher.load(1).unwrap(), "cached");
+        fs::remove_dir_all(&fetcher.input_dir).unwrap();
+    }
+
+    #[test]
+    fn test_load_refuses_future_days() {
+        let fetcher = Fetcher {
+            now: unlocks_at(5) - Duration::from_secs(1),
+            ..fetcher("future", String::from("http://127.0.0.1:1"))
+        };
+        fs::create_dir_all(fetcher.input_path(5).parent().unwrap()).unwrap();
+        fs::write(fetcher.input_path(5), "cached").unwrap();
+
+        let e = fetcher.load(5).unwrap_err();
+        assert_eq!(e.to_string(), "day 5 hasn't unlocked yet");
+        assert!(fetcher.load(0).is_err());
+        assert!(fetcher.load(26).is_err());
+        fs::remove_dir_all(&fetcher.input_dir).unwrap();
+    }
+}
@@ -0,0 +1,191 @@
+// Shared plumbing for the day crates, so that runners, benchmarks and
+// verifiers can treat every day alike.
+
+use std::fmt::Display;
+
+pub mod cli;
+pub mod fetch;
+pub mod observe;
+pub mod parallel;
+mod parse_error;
+pub mod report;
+pub mod submit;
+#[cfg(test)]
+mod test_server;
+
+pub use parse_error::ParseError;
+
+pub type Error = Box<dyn std::error::Error>;
+
+/// A day's puzzle.  The input is parsed once and shared by both parts.
+pub trait Solution {
+    /// The parsed puzzle input.
+    type Input;
+    type Part1: Display;
+    type Part2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    /// Parses the input as this solution's parameters say to, e.g.
+    /// leniently.  By default that's just `parse`.
+    fn parse_input(&self, input: &str) -> Result<Self::Input, Error> {
+        Self::parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<Self::Part1, Error>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Part2, Error>;
+
+    /// Overrides a named puzzle parameter, such as the row to scan.  Days
+    /// without parameters reject every name.
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<(), Error> {
+        Err(unknown_param(name, &[]))
+    }
+}
+
+/// The error for a parameter name that a day doesn't know about.
+pub fn unknown_param(name: &str, known: &[&str]) -> Error {
+    if known.is_empty() {
+        format!("unknown parameter {:?}: this day has no parameters", name).into()
+    } else {
+        format!(
+            "unknown parameter {:?}: expected one of {}",
+            name,
+            known.join(", ")
+        )
+        .into()
+    }
+}
+
+/// Applies each (name, value) override in turn.
+pub fn set_params<S: Solution>(solution: &mut S, params: &[(String, String)]) -> Result<(), Error> {
+    for (name, value) in params {
+        solution
+            .set_param(name, value)
+            .map_err(|e| format!("--{} {}: {}", name, value, e))?;
+    }
+    Ok(())
+}
+
+/// Parses the input and solves one part of the puzzle, rendering the answer.
+pub fn solve<S: Solution>(solution: &S, input: &str, part: usize) -> Result<String, Error> {
+    solve_parsed(solution, &solution.parse_input(input)?, part)
+}
+
+/// As `solve`, for input that's already parsed.
+pub fn solve_parsed<S: Solution>(
+    solution: &S,
+    input: &S::Input,
+    part: usize,
+) -> Result<String, Error> {
+    match part {
+        1 => Ok(solution.part1(input)?.to_string()),
+        2 => Ok(solution.part2(input)?.to_string()),
+        _ => Err(format!("no such part: {}", part).into()),
+    }
+}
+
+/// Parses the input once and prints the answers to both parts.
+pub fn print_answers<S: Solution>(solution: &S, input: &str) -> Result<(), Error> {
+    print_parsed_answers(solution, &solution.parse_input(input)?)
+}
+
+/// As `print_answers`, for input that's already parsed.
+pub fn print_parsed_answers<S: Solution>(solution: &S, input: &S::Input) -> Result<(), Error> {
+    print_answer(1, solution.part1(input)?);
+    print_answer(2, solution.part2(input)?);
+    Ok(())
+}
+
+// Multi-line answers, like a rendered screen, start on their own line.
+fn print_answer(part: usize, answer: impl Display) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("part {}:\n{}", part, answer.trim_end());
+    } else {
+        println!("part {}: {}", part, answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Input = Vec<u32>;
+        type Part1 = u32;
+        type Part2 = String;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(input
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part1(&self, input: &Self::Input) -> Result<Self::Part1, Error> {
+            Ok(input.iter().sum())
+        }
+
+        fn part2(&self, input: &Self::Input) -> Result<Self::Part2, Error> {
+            Ok(format!("{} numbers", input.len()))
+        }
+    }
+
+    #[derive(Default)]
+    struct Scaled {
+        factor: u32,
+    }
+
+    impl Solution for Scaled {
+        type Input = u32;
+        type Part1 = u32;
+        type Part2 = u32;
+
+        fn parse(input: &str) -> Result<Self::Input, Error> {
+            Ok(input.trim().parse()?)
+        }
+
+        fn part1(&self, input: &Self::Input) -> Result<Self::Part1, Error> {
+            Ok(input * self.factor)
+        }
+
+        fn part2(&self, input: &Self::Input) -> Result<Self::Part2, Error> {
+            Ok(input + self.factor)
+        }
+
+        fn set_param(&mut self, name: &str, value: &str) -> Result<(), Error> {
+            match name {
+                "factor" => self.factor = value.parse()?,
+                _ => return Err(unknown_param(name, &["factor"])),
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_set_params() {
+        let mut scaled = Scaled::default();
+        let params = [("factor".to_string(), "3".to_string())];
+        set_params(&mut scaled, &params).unwrap();
+        assert_eq!(solve(&scaled, "5", 1).unwrap(), "15");
+
+        let params = [("factor".to_string(), "x".to_string())];
+        assert!(set_params(&mut scaled, &params).is_err());
+        let params = [("size".to_string(), "3".to_string())];
+        let e = set_params(&mut scaled, &params).unwrap_err();
+        assert!(e.to_string().contains("expected one of factor"));
+
+        let params = [("factor".to_string(), "3".to_string())];
+        assert!(set_params(&mut Sum, &params).is_err());
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(&Sum, "1 2 3", 1).unwrap(), "6");
+        assert_eq!(solve(&Sum, "1 2 3", 2).unwrap(), "3 numbers");
+        assert!(solve(&Sum, "1 2 3", 3).is_err());
+        assert!(solve(&Sum, "1 two 3", 1).is_err());
+    }
+}
@@ -0,0 +1,264 @@
+// Watching step-by-step simulations.  A day that simulates something, like
+// a rope being pulled or sand falling, has a `_with` version of its solver
+// that hands its state to an observer after every step:
+//
+//     let mut steps = Counter::default();
+//     day14::sand_at_rest_with(&paths, &mut steps);
+//
+// The plain solver passes `&mut ()`, which ignores everything, as does
+// `None`.  A pair of observers watches the same steps.  `&mut` an observer
+// is an observer too, so the caller keeps it to look at afterwards.
+//
+// The observers here count the steps, write them out as text frames and
+// check invariants.
+
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub trait Observer<S: ?Sized> {
+    /// Called after each step, numbered from 1, with the state it left.
+    fn observe(&mut self, step: usize, state: &S);
+}
+
+impl<S: ?Sized> Observer<S> for () {
+    fn observe(&mut self, _step: usize, _state: &S) {}
+}
+
+impl<S: ?Sized, A: Observer<S>, B: Observer<S>> Observer<S> for (A, B) {
+    fn observe(&mut self, step: usize, state: &S) {
+        self.0.observe(step, state);
+        self.1.observe(step, state);
+    }
+}
+
+impl<S: ?Sized, O: Observer<S> + ?Sized> Observer<S> for &mut O {
+    fn observe(&mut self, step: usize, state: &S) {
+        (**self).observe(step, state);
+    }
+}
+
+// Watches only if there's something to watch with.
+impl<S: ?Sized, O: Observer<S>> Observer<S> for Option<O> {
+    fn observe(&mut self, step: usize, state: &S) {
+        if let Some(observer) = self {
+            observer.observe(step, state);
+        }
+    }
+}
+
+/// Counts the steps, and remembers the last one's number.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Counter {
+    pub steps: usize,
+    pub last: Option<usize>,
+}
+
+impl<S: ?Sized> Observer<S> for Counter {
+    fn observe(&mut self, step: usize, _state: &S) {
+        self.steps += 1;
+        self.last = Some(step);
+    }
+}
+
+/// Calls a function with every step, e.g. to keep what it displays as.
+pub struct Inspect<F>(pub F);
+
+impl<S: ?Sized, F: FnMut(usize, &S)> Observer<S> for Inspect<F> {
+    fn observe(&mut self, step: usize, state: &S) {
+        (self.0)(step, state);
+    }
+}
+
+/// Panics, naming the step, as soon as the state breaks a rule.
+pub struct Invariant<F> {
+    what: String,
+    holds: F,
+}
+
+impl<F> Invariant<F> {
+    /// `what` says what `holds` checks, for the panic message.
+    pub fn new(what: impl Into<String>, holds: F) -> Self {
+        Invariant {
+            what: what.into(),
+            holds,
+        }
+    }
+}
+
+impl<S: ?Sized, F: FnMut(&S) -> bool> Observer<S> for Invariant<F> {
+    fn observe(&mut self, step: usize, state: &S) {
+        if !(self.holds)(state) {
+            panic!("step {}: expected {}", step, self.what);
+        }
+    }
+}
+
+/// How Frames saves one frame, given the state as it displays.
+pub trait FrameWriter {
+    /// The extension of the files, e.g. "txt".
+    fn extension(&self) -> &str;
+
+    fn write_frame(&self, path: &Path, frame: &str) -> io::Result<()>;
+}
+
+/// Saves frames as they are, as text.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TextWriter;
+
+impl FrameWriter for TextWriter {
+    fn extension(&self) -> &str {
+        "txt"
+    }
+
+    fn write_frame(&self, path: &Path, frame: &str) -> io::Result<()> {
+        let mut frame = frame.to_string();
+        if !frame.ends_with('\n') {
+            frame.push('\n');
+        }
+        fs::write(path, frame)
+    }
+}
+
+/// Writes each state, as it displays, to its own numbered file in a
+/// directory, e.g. frames/000001.txt, frames/000002.txt and so on.  Only
+/// every `every`th step is kept, since some simulations run for millions.
+/// The files are text unless another FrameWriter is given.
+///
+/// Writing stops at the first error, which `finish` reports.
+#[derive(Debug)]
+pub struct Frames<W = TextWriter> {
+    dir: PathBuf,
+    writer: W,
+    every: usize,
+    written: usize,
+    error: Option<io::Error>,
+}
+
+impl Frames {
+    /// Text frames in `dir`, which is made if it's missing.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Frames::with_writer(dir, TextWriter)
+    }
+}
+
+impl<W: FrameWriter> Frames<W> {
+    pub fn with_writer(dir: impl Into<PathBuf>, writer: W) -> Self {
+        Frames {
+            dir: dir.into(),
+            writer,
+            every: 1,
+            written: 0,
+            error: None,
+        }
+    }
+
+    /// Keeps only steps that are multiples of `every`.
+    pub fn every(mut self, every: usize) -> Self {
+        self.every = every.max(1);
+        self
+    }
+
+    /// How many frames were written, or the error that stopped them.
+    pub fn finish(self) -> io::Result<usize> {
+        match self.error {
+            Some(e) => Err(e),
+            None => Ok(self.written),
+        }
+    }
+
+    fn write(&mut self, step: usize, state: &impl Display) -> io::Result<()> {
+        if self.written == 0 {
+            fs::create_dir_all(&self.dir)?;
+        }
+        let path = self
+            .dir
+            .join(format!("{:06}.{}", step, self.writer.extension()));
+        self.writer.write_frame(&path, &state.to_string())?;
+        self.written += 1;
+        Ok(())
+    }
+}
+
+impl<S: Display, W: FrameWriter> Observer<S> for Frames<W> {
+    fn observe(&mut self, step: usize, state: &S) {
+        if self.error.is_some() || !step.is_multiple_of(self.every) {
+            return;
+        }
+        if let Err(e) = self.write(step, state) {
+            self.error = Some(e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    // Counts down from `n`, watched after every step.
+    fn countdown(mut n: u32, observer: &mut impl Observer<u32>) {
+        let mut step = 0;
+        while n > 0 {
+            n -= 1;
+            step += 1;
+            observer.observe(step, &n);
+        }
+    }
+
+    #[test]
+    fn test_counter() {
+        let mut counter = Counter::default();
+        countdown(5, &mut counter);
+        assert_eq!(
+            counter,
+            Counter {
+                steps: 5,
+                last: Some(5)
+            }
+        );
+    }
+
+    #[test]
+    fn test_pair() {
+        let mut seen = Vec::new();
+        let mut record = Inspect(|_, &n: &u32| seen.push(n));
+        let mut counter = Counter::default();
+        countdown(3, &mut (&mut record, &mut counter));
+        assert_eq!(seen, vec![2, 1, 0]);
+        assert_eq!(counter.steps, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "step 2: expected an even number")]
+    fn test_invariant_broken() {
+        countdown(
+            5,
+            &mut Invariant::new("an even number", |n: &u32| n.is_multiple_of(2)),
+        );
+    }
+
+    #[test]
+    fn test_frames() {
+        let dir = env::temp_dir().join(format!("aoc-frames-{}", std::process::id()));
+        let mut frames = Frames::new(&dir).every(2);
+        countdown(5, &mut frames);
+        assert_eq!(frames.finish().unwrap(), 2);
+        assert_eq!(fs::read_to_string(dir.join("000002.txt")).unwrap(), "3\n");
+        assert_eq!(fs::read_to_string(dir.join("000004.txt")).unwrap(), "1\n");
+        assert!(!dir.join("000001.txt").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_frames_error() {
+        // A file where the directory should be.
+        let file = env::temp_dir().join(format!("aoc-frames-file-{}", std::process::id()));
+        fs::write(&file, "").unwrap();
+        let mut frames = Frames::new(&file);
+        countdown(2, &mut frames);
+        assert!(frames.finish().is_err());
+        fs::remove_file(&file).unwrap();
+    }
+}
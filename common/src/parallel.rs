@@ -0,0 +1,90 @@
+// Loops that can run on a thread pool.  With the parallel feature they use
+// rayon's; without it, they run in order on the calling thread.  Either way
+// the results come back in the same order, so the answers don't change:
+//
+//     cargo run --release --features parallel -p aoc -- run all
+//
+// Each day crate has a parallel feature of its own that turns this one on.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::ops::RangeInclusive;
+
+/// Applies `f` to every item, giving the results in the items' order.
+pub fn map<T, U, F>(items: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().map(f).collect();
+    #[cfg(not(feature = "parallel"))]
+    return items.iter().map(f).collect();
+}
+
+/// As `map`, with some scratch state for `f`, such as a cache.  Each thread
+/// makes its own with `init`, so `f` mustn't let it change the results.
+pub fn map_with<T, S, U, I, F>(items: &[T], init: I, f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    I: Fn() -> S + Sync + Send,
+    F: Fn(&mut S, &T) -> U + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return items.par_iter().map_init(init, f).collect();
+    #[cfg(not(feature = "parallel"))]
+    return {
+        let mut state = init();
+        items.iter().map(|item| f(&mut state, item)).collect()
+    };
+}
+
+/// The result of `f` for the first number in the range that gives one, as a
+/// search in order would find.  In parallel, later numbers may be tried too.
+pub fn find_map_first<U, F>(range: RangeInclusive<i64>, f: F) -> Option<U>
+where
+    U: Send,
+    F: Fn(i64) -> Option<U> + Sync + Send,
+{
+    #[cfg(feature = "parallel")]
+    return range.into_par_iter().find_map_first(f);
+    #[cfg(not(feature = "parallel"))]
+    return range.into_iter().find_map(f);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map() {
+        let items: Vec<u64> = (0..1000).collect();
+        assert_eq!(
+            map(&items, |n| n * n),
+            items.iter().map(|n| n * n).collect::<Vec<_>>()
+        );
+        assert_eq!(map(&[] as &[u64], |n| n * n), vec![]);
+    }
+
+    #[test]
+    fn test_map_with() {
+        let items: Vec<usize> = (0..1000).collect();
+        // The scratch state doesn't change the answers.
+        let doubled = map_with(&items, Vec::new, |seen: &mut Vec<usize>, &n| {
+            seen.push(n);
+            n * 2
+        });
+        assert_eq!(doubled, items.iter().map(|n| n * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_find_map_first() {
+        let found = find_map_first(0..=1_000_000, |n| (n % 1000 == 999).then_some(n * 2));
+        assert_eq!(found, Some(1998));
+        assert_eq!(find_map_first(0..=100, |_| None::<()>), None);
+        let (start, end) = (5, 4);
+        assert_eq!(find_map_first(start..=end, Some), None);
+    }
+}
@@ -0,0 +1,133 @@
+// A parse error that points at the offending text in the puzzle input.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    // 1-based position of the offending text.
+    pub line: usize,
+    pub column: usize,
+    // The offending text itself; empty at the end of the input.
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.into(),
+            message: message.into(),
+        }
+    }
+
+    /// An error about `fragment`, which should be a slice of `input`; its
+    /// position is worked out from where the slice sits.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> Self {
+        ParseError::new(1, 1, fragment, message).within(input, fragment)
+    }
+
+    /// Moves an error found while parsing a single line to that line
+    /// (1-based) of the input.
+    pub fn on_line(mut self, line: usize) -> Self {
+        self.line += line - 1;
+        self
+    }
+
+    /// Moves an error found while parsing `fragment`, a slice of `input`,
+    /// so that its position is relative to `input` instead.  If `fragment`
+    /// doesn't come from `input`, the error is left where it was.
+    pub fn within(mut self, input: &str, fragment: &str) -> Self {
+        if let Some((line, column)) = locate(input, fragment) {
+            if self.line == 1 {
+                self.column += column - 1;
+            }
+            self.line += line - 1;
+        }
+        self
+    }
+}
+
+// The 1-based line and column where `fragment` starts in `input`.
+fn locate(input: &str, fragment: &str) -> Option<(usize, usize)> {
+    let start = input.as_ptr() as usize;
+    let offset = (fragment.as_ptr() as usize).checked_sub(start)?;
+    if offset + fragment.len() > input.len() {
+        return None;
+    }
+    let before = input.get(..offset)?;
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    Some((line, column))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if self.text.is_empty() {
+            write!(f, " at end of input")
+        } else {
+            write!(f, ": {:?}", self.text)
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at() {
+        let input = "1-2,3-4\n5-6,x-8\n";
+        let fragment = &input[12..15];
+        assert_eq!(fragment, "x-8");
+        assert_eq!(
+            ParseError::at(input, fragment, "not a number"),
+            ParseError::new(2, 5, "x-8", "not a number")
+        );
+    }
+
+    #[test]
+    fn test_within() {
+        let input = "first\nsecond: abc\n";
+        let line = input.lines().nth(1).unwrap();
+        let e = ParseError::new(1, 9, "abc", "bad").within(input, line);
+        assert_eq!((e.line, e.column), (2, 9));
+
+        // Errors on later lines of the fragment keep their column.
+        let e = ParseError::new(2, 3, "x", "bad").within(input, &input[6..]);
+        assert_eq!((e.line, e.column), (3, 3));
+
+        let e = ParseError::new(1, 9, "abc", "bad").on_line(5);
+        assert_eq!((e.line, e.column), (5, 9));
+
+        // A fragment from elsewhere leaves the error alone.
+        let e = ParseError::new(1, 4, "x", "bad").within(input, "elsewhere");
+        assert_eq!((e.line, e.column), (1, 4));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            ParseError::new(3, 7, "zz", "expected a digit").to_string(),
+            "line 3, column 7: expected a digit: \"zz\""
+        );
+        assert_eq!(
+            ParseError::new(4, 1, "", "expected \"]\"").to_string(),
+            "line 4, column 1: expected \"]\" at end of input"
+        );
+    }
+}
@@ -0,0 +1,182 @@
+// Machine-readable answers, one JSON object per line, e.g.
+//
+//     {"day":1,"part":1,"answer":70369,"elapsed_ns":51234,"input_hash":"9d5ed678fe57bcca"}
+//
+// Answers that are whole numbers are written as JSON integers and anything
+// else, such as a rendered screen, as a string.  A part that failed has an
+// "error" in place of its "answer".
+
+use crate::Solution;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u32,
+    pub part: usize,
+    // The rendered answer, or why there isn't one.
+    pub answer: Result<String, String>,
+    // None if the part never ran.
+    pub elapsed: Option<Duration>,
+    // None if the input couldn't be read.
+    pub input_hash: Option<String>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        let mut json = format!("{{\"day\":{},\"part\":{},", self.day, self.part);
+        match &self.answer {
+            Ok(answer) if is_integer(answer) => write!(json, "\"answer\":{}", answer),
+            Ok(answer) => write!(json, "\"answer\":{}", json_string(answer)),
+            Err(e) => write!(json, "\"error\":{}", json_string(e)),
+        }
+        .unwrap();
+        match self.elapsed {
+            Some(elapsed) => write!(json, ",\"elapsed_ns\":{}", elapsed.as_nanos()),
+            None => write!(json, ",\"elapsed_ns\":null"),
+        }
+        .unwrap();
+        match &self.input_hash {
+            Some(hash) => write!(json, ",\"input_hash\":{}", json_string(hash)),
+            None => write!(json, ",\"input_hash\":null"),
+        }
+        .unwrap();
+        json.push('}');
+        json
+    }
+}
+
+/// Parses the input and solves one part, timing both together, as `solve`
+/// does.
+pub fn report<S: Solution>(solution: &S, day: u32, input: &str, part: usize) -> Report {
+    let start = Instant::now();
+    let answer = crate::solve(solution, input, part).map_err(|e| e.to_string());
+    Report {
+        day,
+        part,
+        answer,
+        elapsed: Some(start.elapsed()),
+        input_hash: Some(input_hash(input)),
+    }
+}
+
+/// As `report`, for input that's already parsed, which took `parsing`.
+/// That time still counts towards the part's.
+pub fn report_parsed<S: Solution>(
+    solution: &S,
+    day: u32,
+    input: &str,
+    parsed: &S::Input,
+    parsing: Duration,
+    part: usize,
+) -> Report {
+    let start = Instant::now();
+    let answer = crate::solve_parsed(solution, parsed, part).map_err(|e| e.to_string());
+    Report {
+        day,
+        part,
+        answer,
+        elapsed: Some(parsing + start.elapsed()),
+        input_hash: Some(input_hash(input)),
+    }
+}
+
+/// A fingerprint of the puzzle input, so that runs on different inputs
+/// can be told apart.  This is 64-bit FNV-1a, which unlike std's hashers
+/// is the same on every platform and Rust release; it isn't meant to be
+/// secure.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{:016x}", hash)
+}
+
+// Whether `s` can be written as a bare JSON integer.
+fn is_integer(s: &str) -> bool {
+    let digits = s.strip_prefix('-').unwrap_or(s);
+    !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            ch if ch.is_control() => write!(json, "\\u{:04x}", ch as u32).unwrap(),
+            ch => json.push(ch),
+        }
+    }
+    json.push('"');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answer(answer: Result<&str, &str>) -> String {
+        Report {
+            day: 10,
+            part: 2,
+            answer: answer.map(String::from).map_err(String::from),
+            elapsed: Some(Duration::from_micros(3)),
+            input_hash: Some("00ff".into()),
+        }
+        .to_json()
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(
+            answer(Ok("13140")),
+            r#"{"day":10,"part":2,"answer":13140,"elapsed_ns":3000,"input_hash":"00ff"}"#
+        );
+        assert_eq!(
+            answer(Ok("##..\n\"#\"\n")),
+            r###"{"day":10,"part":2,"answer":"##..\n\"#\"\n","elapsed_ns":3000,"input_hash":"00ff"}"###
+        );
+        assert_eq!(
+            answer(Err("line 1, column 2: bad\tinput")),
+            r#"{"day":10,"part":2,"error":"line 1, column 2: bad\tinput","elapsed_ns":3000,"input_hash":"00ff"}"#
+        );
+
+        let report = Report {
+            day: 22,
+            part: 2,
+            answer: Err("not implemented".into()),
+            elapsed: None,
+            input_hash: None,
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":22,"part":2,"error":"not implemented","elapsed_ns":null,"input_hash":null}"#
+        );
+    }
+
+    #[test]
+    fn test_is_integer() {
+        assert!(is_integer("0"));
+        assert!(is_integer("-12"));
+        assert!(is_integer("2713310158"));
+        assert!(!is_integer(""));
+        assert!(!is_integer("-"));
+        assert!(!is_integer("007"));
+        assert!(!is_integer("67.35"));
+        assert!(!is_integer("Some(7)"));
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(input_hash("1\n2\n"), input_hash("1\n3\n"));
+    }
+}
@@ -0,0 +1,434 @@
+// Submitting answers to $AOC_BASE_URL/2022/day/N/answer, with the same
+// settings as fetch.  Every attempt is logged next to the day's input,
+//
+//     $AOC_INPUT_DIR/dayN/attempts.txt
+//
+// one per line as "TIME part N VERDICT ANSWER", with TIME in seconds since
+// the Unix epoch:
+//
+//     1670130000 part 1 too-high 5000
+//     1670130042 part 1 wait-18s 4000
+//
+// Before anything is sent, the log is checked so that the same answer is
+// never sent twice, answers outside the too-low/too-high bracket found so
+// far are refused, and a request to wait is respected.
+
+use crate::fetch::{self, Fetcher};
+use crate::{Error, ParseError};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::{Duration, UNIX_EPOCH};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    // Sent too soon after the previous attempt; the answer wasn't judged.
+    Wait(Duration),
+    // The part was already solved, or part 1 hasn't been yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    // Whether the server passed judgement on the answer itself.
+    fn judged(self) -> bool {
+        matches!(
+            self,
+            Verdict::Right | Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Right => write!(f, "right"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wait(wait) => write!(f, "wait-{}s", wait.as_secs()),
+            Verdict::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "right" => Ok(Verdict::Right),
+            "wrong" => Ok(Verdict::Wrong),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            _ => s
+                .strip_prefix("wait-")
+                .and_then(|wait| wait.strip_suffix('s'))
+                .and_then(|secs| secs.parse().ok())
+                .map(|secs| Verdict::Wait(Duration::from_secs(secs)))
+                .ok_or_else(|| format!("unknown verdict {:?}", s)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attempt {
+    // Seconds since the Unix epoch.
+    pub time: u64,
+    pub part: usize,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+impl fmt::Display for Attempt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} part {} {} {}",
+            self.time, self.part, self.verdict, self.answer
+        )
+    }
+}
+
+pub fn parse_attempts(s: &str) -> Result<Vec<Attempt>, ParseError> {
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| parse_attempt(line).map_err(|msg| ParseError::at(s, line, msg)))
+        .collect()
+}
+
+fn parse_attempt(line: &str) -> Result<Attempt, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [time, "part", part @ ("1" | "2"), verdict, answer] = fields[..] else {
+        return Err(String::from("expected \"TIME part N VERDICT ANSWER\""));
+    };
+    Ok(Attempt {
+        time: time.parse().map_err(|_| format!("bad time {:?}", time))?,
+        part: part.parse().unwrap(),
+        verdict: verdict.parse()?,
+        answer: answer.to_string(),
+    })
+}
+
+/// Why `answer` to `part` shouldn't be sent at time `now`, judging by the
+/// earlier attempts, if there's any reason.
+pub fn check(attempts: &[Attempt], part: usize, answer: &str, now: u64) -> Result<(), String> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!("{:?} isn't a one-word answer", answer));
+    }
+
+    if let Some(waited) = attempts.iter().rev().find_map(|a| match a.verdict {
+        Verdict::Wait(wait) => Some(a.time + wait.as_secs()),
+        _ => None,
+    }) {
+        if now < waited {
+            return Err(format!("wait another {}s before submitting", waited - now));
+        }
+    }
+
+    let attempts = attempts
+        .iter()
+        .filter(|a| a.part == part && a.verdict.judged());
+    let mut too_low = None;
+    let mut too_high = None;
+    for attempt in attempts {
+        if attempt.verdict == Verdict::Right {
+            return Err(format!(
+                "part {} is already solved: {}",
+                part, attempt.answer
+            ));
+        }
+        if attempt.answer == answer {
+            return Err(format!("{} was already tried: {}", answer, attempt.verdict));
+        }
+        match (attempt.verdict, attempt.answer.parse::<i64>()) {
+            (Verdict::TooLow, Ok(n)) => too_low = too_low.max(Some(n)),
+            (Verdict::TooHigh, Ok(n)) => too_high = Some(too_high.map_or(n, |h: i64| h.min(n))),
+            _ => {}
+        }
+    }
+
+    if let Ok(n) = answer.parse::<i64>() {
+        if let Some(low) = too_low.filter(|&low| n <= low) {
+            return Err(format!("{} is too low: {} already was", n, low));
+        }
+        if let Some(high) = too_high.filter(|&high| n >= high) {
+            return Err(format!("{} is too high: {} already was", n, high));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the verdict out of the page the site answers a submission with.
+pub fn parse_response(html: &str) -> Option<Verdict> {
+    if html.contains("That's the right answer") {
+        Some(Verdict::Right)
+    } else if html.contains("That's not the right answer") {
+        if html.contains("answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if html.contains("answer is too low") {
+            Some(Verdict::TooLow)
+        } else {
+            Some(Verdict::Wrong)
+        }
+    } else if html.contains("You gave an answer too recently") {
+        // "You have 1m 23s left to wait."  If the time can't be made out,
+        // a minute is a safe guess.
+        let wait = html
+            .split_once("You have ")
+            .and_then(|(_, rest)| rest.split_once(" left to wait"))
+            .and_then(|(wait, _)| parse_wait(wait))
+            .unwrap_or(Duration::from_secs(60));
+        Some(Verdict::Wait(wait))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Some(Verdict::WrongLevel)
+    } else {
+        None
+    }
+}
+
+// Parses a wait such as "1m 23s" or "45s".
+fn parse_wait(s: &str) -> Option<Duration> {
+    s.split_whitespace()
+        .map(|word| {
+            if let Some(minutes) = word.strip_suffix('m') {
+                Some(minutes.parse::<u64>().ok()? * 60)
+            } else {
+                word.strip_suffix('s')?.parse::<u64>().ok()
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+/// Where the attempts at the day's puzzle are logged.
+pub fn attempts_path(fetcher: &Fetcher, day: u32) -> PathBuf {
+    fetcher.input_path(day).with_file_name("attempts.txt")
+}
+
+/// The attempts logged for the day, or none if there's no log yet.
+pub fn load_attempts(fetcher: &Fetcher, day: u32) -> Result<Vec<Attempt>, Error> {
+    let path = attempts_path(fetcher, day);
+    match fs::read_to_string(&path) {
+        Ok(s) => Ok(parse_attempts(&s).map_err(|e| format!("{}: {}", path.display(), e))?),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e).into()),
+    }
+}
+
+/// Sends `answer` to one part of the day's puzzle, unless the log says it
+/// shouldn't be, and logs the attempt.
+pub fn submit(fetcher: &Fetcher, day: u32, part: usize, answer: &str) -> Result<Verdict, Error> {
+    fetch::check_unlocked(day, fetcher.now)?;
+    if !(1..=2).contains(&part) {
+        return Err(format!("no such part: {}", part).into());
+    }
+    let now = fetcher.now.duration_since(UNIX_EPOCH)?.as_secs();
+    check(&load_attempts(fetcher, day)?, part, answer, now)?;
+
+    let session = fetcher
+        .session
+        .as_deref()
+        .ok_or("set AOC_SESSION to submit answers")?;
+    let url = format!(
+        "{}/{}/day/{}/answer",
+        fetcher.base_url.trim_end_matches('/'),
+        fetch::YEAR,
+        day
+    );
+    let html = ureq::post(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", fetch::USER_AGENT)
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(|e| format!("submitting day {} part {}: {}", day, part, e))?
+        .into_string()?;
+    let verdict = parse_response(&html).ok_or("couldn't make out the site's response")?;
+
+    let attempt = Attempt {
+        time: now,
+        part,
+        verdict,
+        answer: answer.to_string(),
+    };
+    let path = attempts_path(fetcher, day);
+    fs::create_dir_all(path.parent().expect("attempts path has a directory"))?;
+    let mut log = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(log, "{}", attempt)?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::serve_once;
+    use std::env;
+
+    fn attempt(time: u64, part: usize, verdict: Verdict, answer: &str) -> Attempt {
+        Attempt {
+            time,
+            part,
+            verdict,
+            answer: answer.into(),
+        }
+    }
+
+    #[test]
+    fn test_parse_attempts() {
+        let log = "1000 part 1 too-high 5000\n\n1042 part 2 wait-18s abc\n";
+        let attempts = vec![
+            attempt(1000, 1, Verdict::TooHigh, "5000"),
+            attempt(1042, 2, Verdict::Wait(Duration::from_secs(18)), "abc"),
+        ];
+        assert_eq!(parse_attempts(log), Ok(attempts.clone()));
+        let written: String = attempts.iter().map(|a| format!("{}\n", a)).collect();
+        assert_eq!(
+            written,
+            "1000 part 1 too-high 5000\n1042 part 2 wait-18s abc\n"
+        );
+
+        assert_eq!(
+            parse_attempts("1 part 1 right 5\n1 part 3 right 5\n"),
+            Err(ParseError::new(
+                2,
+                1,
+                "1 part 3 right 5",
+                "expected \"TIME part N VERDICT ANSWER\""
+            ))
+        );
+        assert!(parse_attempts("1 part 1 close 5\n").is_err());
+        assert!(parse_attempts("soon part 1 right 5\n").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let attempts = [
+            attempt(100, 1, Verdict::TooLow, "10"),
+            attempt(200, 1, Verdict::TooHigh, "50"),
+            attempt(300, 1, Verdict::TooHigh, "40"),
+            attempt(400, 1, Verdict::Wrong, "abc"),
+            attempt(500, 2, Verdict::Right, "7"),
+        ];
+        assert_eq!(check(&attempts, 1, "25", 1000), Ok(()));
+        assert_eq!(check(&attempts, 1, "xyz", 1000), Ok(()));
+        assert!(check(&attempts, 1, "10", 1000).is_err());
+        assert!(check(&attempts, 1, "9", 1000).is_err());
+        assert!(check(&attempts, 1, "45", 1000).is_err());
+        assert!(check(&attempts, 1, "abc", 1000).is_err());
+        assert!(check(&attempts, 1, "", 1000).is_err());
+        assert!(check(&attempts, 1, "2 5", 1000).is_err());
+        assert_eq!(
+            check(&attempts, 2, "8", 1000),
+            Err(String::from("part 2 is already solved: 7"))
+        );
+    }
+
+    #[test]
+    fn test_check_wait() {
+        let attempts = [
+            attempt(100, 1, Verdict::TooLow, "10"),
+            attempt(130, 1, Verdict::Wait(Duration::from_secs(30)), "20"),
+        ];
+        assert_eq!(
+            check(&attempts, 1, "20", 150),
+            Err(String::from("wait another 10s before submitting"))
+        );
+        // The answer sent while waiting was never judged.
+        assert_eq!(check(&attempts, 1, "20", 160), Ok(()));
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are one gold star closer."
+            )),
+            Some(Verdict::Right)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high."
+            )),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.  If you're stuck, ...")),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently.  You have 1m 23s left to wait."
+            )),
+            Some(Verdict::Wait(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            parse_response(&page("You don't seem to be solving the right level.")),
+            Some(Verdict::WrongLevel)
+        );
+        assert_eq!(parse_response(&page("Puzzle inputs differ by user.")), None);
+    }
+
+    fn fetcher(name: &str, base_url: String) -> Fetcher {
+        let input_dir = env::temp_dir().join(format!("aoc-submit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&input_dir);
+        Fetcher {
+            base_url,
+            session: Some("s3cr3t".into()),
+            input_dir,
+            now: fetch::unlocks_at(25),
+        }
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = serve_once(
+            "200 OK",
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        );
+        let fetcher = fetcher("submit", url);
+
+        assert_eq!(submit(&fetcher, 3, 1, "500").unwrap(), Verdict::TooHigh);
+        let request = server.join().unwrap();
+        assert!(request
+            .to_lowercase()
+            .starts_with("post /2022/day/3/answer "));
+        assert!(request.contains("session=s3cr3t"));
+        assert!(request.ends_with("\r\n\r\nlevel=1&answer=500"));
+
+        // The server is gone, so these are refused before anything is sent.
+        let e = submit(&fetcher, 3, 1, "500").unwrap_err();
+        assert_eq!(e.to_string(), "500 was already tried: too-high");
+        let e = submit(&fetcher, 3, 1, "600").unwrap_err();
+        assert_eq!(e.to_string(), "600 is too high: 500 already was");
+
+        let attempts = load_attempts(&fetcher, 3).unwrap();
+        assert_eq!(attempts.len(), 1);
+        assert_eq!(
+            (attempts[0].part, attempts[0].verdict),
+            (1, Verdict::TooHigh)
+        );
+        fs::remove_dir_all(&fetcher.input_dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit_unrecognized_response() {
+        let (url, server) = serve_once("200 OK", "<p>Something else entirely</p>");
+        let fetcher = fetcher("unrecognized", url);
+        assert!(submit(&fetcher, 3, 2, "42").is_err());
+        server.join().unwrap();
+        assert_eq!(load_attempts(&fetcher, 3).unwrap(), vec![]);
+        let _ = fs::remove_dir_all(&fetcher.input_dir);
+    }
+}
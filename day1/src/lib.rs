@@ -0,0 +1,479 @@
+/// Solution for https://adventofcode.com/2022/day/1
+use common::{ParseError, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::io::{BufRead, BufReader, Lines, Read};
+
+/// Returns largest value.
+pub fn read_max_sum(to_read: impl Read) -> Result<u32, Box<dyn Error>> {
+    Ok(read_top_k(to_read, 1)?.first().copied().unwrap_or(0))
+}
+
+/// Returns the sum of the largest three values.
+pub fn read_max_three_sum(to_read: impl Read) -> Result<u64, Box<dyn Error>> {
+    let top_three = read_top_k(to_read, 3)?;
+    if top_three.len() < 3 {
+        return Err(format!("need three elves, found {}", top_three.len()).into());
+    }
+    Ok(top_three.iter().map(|&total| u64::from(total)).sum())
+}
+
+/// Returns the `k` largest summed values, largest first, in one pass that
+/// keeps no more than `k` of them, however long the input.
+pub fn read_top_k(to_read: impl Read, k: usize) -> Result<Vec<u32>, Box<dyn Error>> {
+    let mut top = TopK::new(k);
+    for entry in entries(to_read) {
+        if let Entry::Total(total) = entry? {
+            top.push(total);
+        }
+    }
+    Ok(top.into_vec())
+}
+
+/// Returns all summed values.
+pub fn read_sums(to_read: impl Read) -> Result<Vec<u32>, Box<dyn Error>> {
+    let mut sums = Vec::new();
+    for entry in entries(to_read) {
+        if let Entry::Total(total) = entry? {
+            sums.push(total);
+        }
+    }
+    Ok(sums)
+}
+
+/// Returns all summed values, skipping any lines that aren't calories,
+/// along with the errors for the lines skipped.  This is for inventories
+/// edited by hand.
+pub fn read_sums_lenient(
+    to_read: impl Read,
+) -> Result<(Vec<u32>, Vec<ParseError>), Box<dyn Error>> {
+    let mut sums = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in entries(to_read).lenient() {
+        match entry? {
+            Entry::Item(_) => {}
+            Entry::Total(total) => sums.push(total),
+            Entry::Skipped(e) => skipped.push(e),
+        }
+    }
+    Ok((sums, skipped))
+}
+
+/// Returns every elf's items, as listed.  When lenient, bad lines are
+/// skipped, and kept in the inventory, rather than failing.
+pub fn read_inventory(to_read: impl Read, lenient: bool) -> Result<Inventory, Box<dyn Error>> {
+    let mut entries = entries(to_read);
+    if lenient {
+        entries = entries.lenient();
+    }
+    let mut items = Vec::new();
+    let mut elves = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in entries {
+        match entry? {
+            Entry::Item(calories) => items.push(calories),
+            Entry::Total(_) => elves.push(std::mem::take(&mut items)),
+            Entry::Skipped(e) => skipped.push(e),
+        }
+    }
+    let mut inventory = Inventory::new(elves);
+    inventory.skipped = skipped;
+    Ok(inventory)
+}
+
+/// A line of the input, as read: an item's calories, or the total of an
+/// elf's items once their group is over.  When lenient, a bad line is
+/// skipped, with the error it would have been.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    Item(u32),
+    Total(u32),
+    Skipped(ParseError),
+}
+
+/// Reads the input a line at a time.  Groups are separated by one or more
+/// blank lines, and lines may end in CRLF.  A total too large for a u32 is
+/// an error rather than wrapping around.
+///
+/// A bad line is an error, giving its line number and text, unless the
+/// entries are `lenient`.
+pub fn entries<R: Read>(to_read: R) -> Entries<R> {
+    Entries {
+        lines: BufReader::new(to_read).lines(),
+        lineindex: 0,
+        total: None,
+        lenient: false,
+    }
+}
+
+pub struct Entries<R> {
+    lines: Lines<BufReader<R>>,
+    lineindex: usize,
+    // The total so far of the group being read, if it has any items yet.
+    total: Option<u32>,
+    lenient: bool,
+}
+
+impl<R> Entries<R> {
+    /// Skips bad lines instead of failing on them.
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+}
+
+impl<R: Read> Iterator for Entries<R> {
+    type Item = Result<Entry, Box<dyn Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => return Some(Err(e.into())),
+                None => return self.total.take().map(|total| Ok(Entry::Total(total))),
+            };
+            self.lineindex += 1;
+            if line.is_empty() {
+                match self.total.take() {
+                    Some(total) => return Some(Ok(Entry::Total(total))),
+                    None => continue,
+                }
+            }
+            let calories = line
+                .parse::<u32>()
+                .map_err(|e| format!("bad calories: {}", e))
+                .and_then(|calories| {
+                    let total = self.total.unwrap_or(0).checked_add(calories);
+                    total
+                        .map(|total| (calories, total))
+                        .ok_or_else(|| String::from("calorie total overflows a u32"))
+                });
+            return Some(match calories {
+                Ok((calories, total)) => {
+                    self.total = Some(total);
+                    Ok(Entry::Item(calories))
+                }
+                Err(message) => {
+                    let e = ParseError::new(self.lineindex, 1, line, message);
+                    if self.lenient {
+                        Ok(Entry::Skipped(e))
+                    } else {
+                        Err(e.into())
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Keeps the `k` largest of the values pushed into it, in a heap of at
+/// most `k`.
+#[derive(Debug, Clone)]
+pub struct TopK {
+    k: usize,
+    heap: BinaryHeap<Reverse<u32>>,
+}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        TopK {
+            k,
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    pub fn push(&mut self, value: u32) {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(value));
+        } else if let Some(mut smallest) = self.heap.peek_mut() {
+            if value > smallest.0 {
+                *smallest = Reverse(value);
+            }
+        }
+    }
+
+    /// The values kept, largest first.
+    pub fn into_vec(self) -> Vec<u32> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(value)| value)
+            .collect()
+    }
+}
+
+/// The items each elf carries, and their calorie totals.  Elves are
+/// numbered from 0, in the order they're listed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inventory {
+    items: Vec<Vec<u32>>,
+    totals: Vec<u32>,
+    // The lines skipped when read leniently.
+    skipped: Vec<ParseError>,
+}
+
+impl Inventory {
+    // The totals must fit in a u32, as they do when read.
+    fn new(items: Vec<Vec<u32>>) -> Self {
+        let totals = items.iter().map(|items| items.iter().sum()).collect();
+        Inventory {
+            items,
+            totals,
+            skipped: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    pub fn totals(&self) -> &[u32] {
+        &self.totals
+    }
+
+    /// The errors for the lines that weren't read, if read leniently.
+    pub fn skipped(&self) -> &[ParseError] {
+        &self.skipped
+    }
+
+    /// The calories of each item the elf carries.
+    pub fn items(&self, elf: usize) -> Option<&[u32]> {
+        self.items.get(elf).map(Vec::as_slice)
+    }
+
+    /// The `k` largest totals, largest first.  Only `k` are kept at a time,
+    /// so this is cheaper than sorting every total.
+    pub fn top_k(&self, k: usize) -> Vec<u32> {
+        let mut top = TopK::new(k);
+        for &total in &self.totals {
+            top.push(total);
+        }
+        top.into_vec()
+    }
+
+    /// The sum of the `k` largest totals, if there are as many elves.
+    pub fn sum_top_k(&self, k: usize) -> Option<u64> {
+        (k <= self.len()).then(|| self.top_k(k).iter().map(|&total| u64::from(total)).sum())
+    }
+
+    /// Where the elf's total places, from 1 for the most calories.  Elves
+    /// with equal totals share a rank.
+    pub fn rank(&self, elf: usize) -> Option<usize> {
+        let total = *self.totals.get(elf)?;
+        Some(1 + self.totals.iter().filter(|&&other| other > total).count())
+    }
+
+    /// The smallest total that at least `p` percent of the elves carry no
+    /// more than, for `p` from 0 to 100.  The 50th percentile is the median.
+    pub fn percentile(&self, p: f64) -> Option<u32> {
+        if self.is_empty() || !(0.0..=100.0).contains(&p) {
+            return None;
+        }
+        let mut totals = self.totals.clone();
+        totals.sort_unstable();
+        let rank = (p / 100.0 * totals.len() as f64).ceil() as usize;
+        Some(totals[rank.max(1) - 1])
+    }
+}
+
+// Bad lines fail parsing unless the day is lenient, when they're skipped
+// instead.
+#[derive(Debug, Default)]
+pub struct Day1 {
+    pub lenient: bool,
+}
+
+impl Solution for Day1 {
+    type Input = Inventory;
+    type Part1 = u32;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        read_inventory(input.as_bytes(), false)
+    }
+
+    fn parse_input(&self, input: &str) -> Result<Self::Input, common::Error> {
+        read_inventory(input.as_bytes(), self.lenient)
+    }
+
+    fn part1(&self, inventory: &Self::Input) -> Result<u32, common::Error> {
+        Ok(inventory.top_k(1).first().copied().unwrap_or(0))
+    }
+
+    fn part2(&self, inventory: &Self::Input) -> Result<u64, common::Error> {
+        Ok(inventory
+            .sum_top_k(3)
+            .ok_or_else(|| format!("need three elves, found {}", inventory.len()))?)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        match name {
+            "lenient" => self.lenient = value.parse()?,
+            _ => return Err(common::unknown_param(name, &["lenient"])),
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_read_data_single() -> Result<(), Box<dyn Error>> {
+    assert_eq!(read_max_sum(r"1".as_bytes(),)?, 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_data_empty() -> Result<(), Box<dyn Error>> {
+    assert_eq!(read_max_sum("".as_bytes(),)?, 0);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_data_sums() -> Result<(), Box<dyn Error>> {
+    assert_eq!(read_max_sum("1\n2\n3".as_bytes(),)?, 6);
+
+    Ok(())
+}
+
+#[test]
+fn test_read_data_main_example() -> Result<(), Box<dyn Error>> {
+    assert_eq!(
+        read_max_sum(
+            r#"
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000"#
+                .as_bytes(),
+        )?,
+        24000
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_read_data_bad_line() {
+    let e = read_sums("1\n2x\n".as_bytes()).unwrap_err();
+    let e = e.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "2x"));
+}
+
+#[cfg(test)]
+const EXAMPLE: &str = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+
+#[test]
+fn test_inventory_top_k() -> Result<(), Box<dyn Error>> {
+    let inventory = read_inventory(EXAMPLE.as_bytes(), false)?;
+    assert_eq!(inventory.totals(), [6000, 4000, 11000, 24000, 10000]);
+    assert_eq!(inventory.top_k(3), [24000, 11000, 10000]);
+    assert_eq!(inventory.top_k(0), []);
+    assert_eq!(inventory.top_k(9), [24000, 11000, 10000, 6000, 4000]);
+    assert_eq!(inventory.sum_top_k(3), Some(45000));
+    assert_eq!(inventory.sum_top_k(5), Some(55000));
+    assert_eq!(inventory.sum_top_k(6), None);
+    assert_eq!(read_max_three_sum(EXAMPLE.as_bytes())?, 45000);
+    assert!(read_max_three_sum("1\n\n2".as_bytes()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_inventory_rank_and_items() -> Result<(), Box<dyn Error>> {
+    let inventory = read_inventory("5\n\n7\n\n2\n3\n\n1".as_bytes(), false)?;
+    assert_eq!(inventory.rank(1), Some(1));
+    assert_eq!(inventory.rank(0), Some(2));
+    // Ties share a rank.
+    assert_eq!(inventory.rank(2), Some(2));
+    assert_eq!(inventory.rank(3), Some(4));
+    assert_eq!(inventory.rank(4), None);
+    assert_eq!(inventory.items(2), Some(&[2, 3][..]));
+    assert_eq!(inventory.items(4), None);
+    Ok(())
+}
+
+#[test]
+fn test_inventory_percentile() -> Result<(), Box<dyn Error>> {
+    let inventory = read_inventory(EXAMPLE.as_bytes(), false)?;
+    assert_eq!(inventory.percentile(0.0), Some(4000));
+    assert_eq!(inventory.percentile(50.0), Some(10000));
+    assert_eq!(inventory.percentile(80.0), Some(11000));
+    assert_eq!(inventory.percentile(100.0), Some(24000));
+    assert_eq!(inventory.percentile(101.0), None);
+    assert_eq!(Inventory::new(vec![]).percentile(50.0), None);
+    Ok(())
+}
+
+#[test]
+fn test_read_top_k() -> Result<(), Box<dyn Error>> {
+    assert_eq!(read_top_k(EXAMPLE.as_bytes(), 3)?, [24000, 11000, 10000]);
+    assert_eq!(read_top_k(EXAMPLE.as_bytes(), 0)?, []);
+    assert_eq!(read_top_k("".as_bytes(), 3)?, []);
+    Ok(())
+}
+
+#[test]
+fn test_read_crlf_and_blank_runs() -> Result<(), Box<dyn Error>> {
+    let text = "\r\n1\r\n2\r\n\r\n\r\n\r\n4\r\n\r\n";
+    assert_eq!(read_sums(text.as_bytes())?, [3, 4]);
+    assert_eq!(
+        read_inventory(text.as_bytes(), false)?.items(1),
+        Some(&[4][..])
+    );
+    Ok(())
+}
+
+#[test]
+fn test_read_overflow() {
+    let e = read_sums("4294967295\n\n4294967295\n1\n".as_bytes()).unwrap_err();
+    let e = e.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((e.line, e.text.as_str()), (4, "1"));
+    assert!(read_top_k("4294967295\n1\n".as_bytes(), 1).is_err());
+}
+
+#[test]
+fn test_read_lenient() -> Result<(), Box<dyn Error>> {
+    let text = "1\n2x\n3\n\nfour\n\n5\n";
+    let (sums, skipped) = read_sums_lenient(text.as_bytes())?;
+    assert_eq!(sums, [4, 5]);
+    let lines: Vec<(usize, &str)> = skipped.iter().map(|e| (e.line, e.text.as_str())).collect();
+    assert_eq!(lines, [(2, "2x"), (5, "four")]);
+    assert!(read_sums(text.as_bytes()).is_err());
+
+    let inventory = read_inventory(text.as_bytes(), true)?;
+    assert_eq!(inventory.totals(), [4, 5]);
+    assert_eq!(inventory.skipped(), skipped);
+    Ok(())
+}
+
+#[test]
+fn test_day1_lenient() -> Result<(), Box<dyn Error>> {
+    let input = "1\n\n2x\n\n3\n\n4\n";
+    let e = Day1::parse(input).unwrap_err();
+    assert_eq!(e.downcast_ref::<ParseError>().map(|e| e.line), Some(3));
+
+    let mut day = Day1::default();
+    assert!(day.parse_input(input).is_err());
+    day.set_param("lenient", "true")?;
+    let inventory = day.parse_input(input)?;
+    assert_eq!(inventory.skipped().len(), 1);
+    assert_eq!(day.part1(&inventory)?, 4);
+    assert_eq!(day.part2(&inventory)?, 8);
+    assert!(day.set_param("lenient", "maybe").is_err());
+    Ok(())
+}
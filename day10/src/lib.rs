@@ -1,4 +1,9 @@
-#[derive(Debug, PartialEq)]
+use common::observe::Observer;
+use common::{ParseError, Solution};
+use grid::image::Palette;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Instruction {
     NoOp,
     AddX(i32),
@@ -75,6 +80,16 @@ impl Computer {
     }
 }
 
+impl fmt::Display for Computer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "X={} pc={}", self.x, self.program_counter)?;
+        match self.in_flight {
+            Some(RunningInstruction::AddX { delta, .. }) => write!(f, " adding {}", delta),
+            Some(RunningInstruction::NoOp) | None => Ok(()),
+        }
+    }
+}
+
 pub struct SignalStrengths {
     cycles_executed: usize,
     computer: Computer,
@@ -108,37 +123,56 @@ impl Iterator for SignalStrengths {
     }
 }
 
-pub fn parse_instructions(s: &str) -> Vec<Instruction> {
+pub fn parse_instructions(s: &str) -> Result<Vec<Instruction>, ParseError> {
     s.lines()
-        .filter_map(
+        .filter(|line| !line.trim().is_empty())
+        .map(
             |line| match line.split_whitespace().collect::<Vec<&str>>()[..] {
                 ["addx", n] => n
                     .parse::<i32>()
-                    .map(|v| Some(Instruction::AddX(v)))
-                    .unwrap_or(None),
-                ["noop"] => Some(Instruction::NoOp),
-                _ => None,
+                    .map(Instruction::AddX)
+                    .map_err(|_| ParseError::at(s, n, "expected a number")),
+                ["noop"] => Ok(Instruction::NoOp),
+                _ => Err(ParseError::at(s, line, "expected addx or noop")),
             },
         )
         .collect()
 }
 
 // Computing signal strength sums.
-pub fn part_1(s: &str) -> i32 {
-    let computer = Computer::new(parse_instructions(s));
+pub fn part_1(s: &str) -> Result<i32, ParseError> {
+    Ok(sum_signal_strengths(&parse_instructions(s)?))
+}
+
+// Cycles after the program has finished count for nothing.
+pub fn sum_signal_strengths(program: &[Instruction]) -> i32 {
+    let computer = Computer::new(program.to_vec());
     let signal_strengths: Vec<i32> = SignalStrengths::new(computer).collect();
-    signal_strengths[19]
-        + signal_strengths[59]
-        + signal_strengths[99]
-        + signal_strengths[139]
-        + signal_strengths[179]
-        + signal_strengths[219]
+    [20, 60, 100, 140, 180, 220]
+        .iter()
+        .filter_map(|cycle| signal_strengths.get(cycle - 1))
+        .sum()
 }
 
 // Simulating CRT.
-pub fn part_2(s: &str) -> String {
+pub fn part_2(s: &str) -> Result<String, ParseError> {
+    Ok(render_crt(&parse_instructions(s)?))
+}
+
+// Lit pixels glow green on a dark screen.
+pub fn palette() -> Palette {
+    Palette::new([10, 20, 10]).with('#', [80, 255, 80])
+}
+
+pub fn render_crt(program: &[Instruction]) -> String {
+    render_crt_with(program, &mut ())
+}
+
+// As render_crt, showing the observer the computer after each cycle.
+pub fn render_crt_with(program: &[Instruction], observer: &mut impl Observer<Computer>) -> String {
     let mut result = String::new();
-    let mut computer = Computer::new(parse_instructions(s));
+    let mut computer = Computer::new(program.to_vec());
+    let mut cycle = 0;
     for _row in 0..6 {
         for col in 0..40 {
             if computer.x.abs_diff(col) <= 1 {
@@ -147,8 +181,31 @@ pub fn part_2(s: &str) -> String {
                 result.push('.');
             }
             computer.tick();
+            cycle += 1;
+            observer.observe(cycle, &computer);
         }
         result.push('\n');
     }
     result
 }
+
+#[derive(Debug, Default)]
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Part1 = i32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_instructions(input)?)
+    }
+
+    fn part1(&self, program: &Self::Input) -> Result<i32, common::Error> {
+        Ok(sum_signal_strengths(program))
+    }
+
+    fn part2(&self, program: &Self::Input) -> Result<String, common::Error> {
+        Ok(render_crt(program))
+    }
+}
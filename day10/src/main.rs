@@ -1,11 +1,6 @@
-use day10::*;
+use day10::Day10;
+use std::process::ExitCode;
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let input = std::fs::read_to_string("adventofcode.com_2022_day_10_input.txt")?;
-
-    println!("part 1: {}", part_1(&input));
-    println!();
-    println!("part 2:\n{}", part_2(&input));
-
-    Ok(())
+fn main() -> ExitCode {
+    common::cli::exit(common::cli::run(Day10, 10))
 }
@@ -8,7 +8,7 @@ addx 3
 addx -5
 ";
     assert_eq!(
-        parse_instructions(input),
+        parse_instructions(input).unwrap(),
         vec![
             Instruction::NoOp,
             Instruction::AddX(3),
@@ -202,7 +202,7 @@ noop
 #[test]
 fn test_signal_strengths_larger_example() {
     let input = LARGE_EXAMPLE;
-    let computer = Computer::new(parse_instructions(input));
+    let computer = Computer::new(parse_instructions(input).unwrap());
     let signal_strengths: Vec<i32> = SignalStrengths::new(computer).collect();
     assert_eq!(signal_strengths[19], 420);
     assert_eq!(signal_strengths[59], 1140);
@@ -212,10 +212,19 @@ fn test_signal_strengths_larger_example() {
     assert_eq!(signal_strengths[219], 3960);
 }
 
+#[test]
+fn test_parse_errors() {
+    let e = parse_instructions("noop\naddx 3\naddx three\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (3, 6, "three"));
+
+    let e = parse_instructions("noop\njmp 3\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "jmp 3"));
+}
+
 #[test]
 fn test_part_2() {
     assert_eq!(
-        part_2(LARGE_EXAMPLE),
+        part_2(LARGE_EXAMPLE).unwrap(),
         "
 ##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
@@ -227,3 +236,25 @@ fn test_part_2() {
         .trim_start()
     );
 }
+
+#[test]
+fn test_render_crt_observed() {
+    use common::observe::{Counter, Invariant};
+
+    let program = parse_instructions(LARGE_EXAMPLE).unwrap();
+    let mut counter = Counter::default();
+    let mut on_screen = Invariant::new("X on the screen", |computer: &Computer| {
+        (-1..=40).contains(&computer.x)
+    });
+    render_crt_with(&program, &mut (&mut counter, &mut on_screen));
+    assert_eq!(counter.steps, 240);
+}
+
+#[test]
+fn test_display_computer() {
+    let mut computer = Computer::new(vec![Instruction::AddX(3)]);
+    computer.tick();
+    assert_eq!(computer.to_string(), "X=1 pc=1 adding 3");
+    computer.tick();
+    assert_eq!(computer.to_string(), "X=4 pc=1");
+}
@@ -0,0 +1,531 @@
+use common::observe::Observer;
+use common::{ParseError, Solution};
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Clone)]
+enum Operation {
+    Multiply(Operand),
+    Add(Operand),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+enum Operand {
+    Const(u64),
+    Old,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Monkey {
+    id: usize,
+    starting_items: Vec<u64>,
+    operation: Operation,
+    divisible_by_test: u64,
+    true_throw_to: usize,
+    false_throw_to: usize,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct MonkeyDynamics {
+    items: Vec<u64>,
+    count_inspected: usize,
+}
+
+impl Monkey {
+    fn get_dynamics(&self) -> MonkeyDynamics {
+        MonkeyDynamics {
+            items: self.starting_items.clone(),
+            count_inspected: 0,
+        }
+    }
+}
+
+impl FromStr for Monkey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines();
+        let mut next_line = |what: &str| {
+            lines
+                .next()
+                .ok_or_else(|| ParseError::at(s, &s[s.len()..], format!("expected {}", what)))
+        };
+        fn number<T: FromStr>(s: &str, v: &str) -> Result<T, ParseError> {
+            v.parse()
+                .map_err(|_| ParseError::at(s, v, "expected a number"))
+        }
+
+        let line = next_line("the monkey's id")?;
+        let id: usize = match line.split(&[' ', ':']).collect::<Vec<_>>()[..] {
+            ["Monkey", n, ""] => number(s, n)?,
+            _ => {
+                return Err(ParseError::at(s, line, "expected \"Monkey N:\""));
+            }
+        };
+
+        let line = next_line("starting items")?;
+        let starting_items: Vec<u64> = match line
+            .trim_start()
+            .split(&[' ', ','])
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<_>>()[..]
+        {
+            ["Starting", "items:", ref items @ ..] => items
+                .iter()
+                .map(|item| number(s, item))
+                .collect::<Result<Vec<_>, _>>()?,
+            _ => {
+                return Err(ParseError::at(s, line, "expected starting items"));
+            }
+        };
+
+        let line = next_line("an operation")?;
+        let operation: Operation = match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["Operation:", "new", "=", "old", op, v] => {
+                let v = if v == "old" {
+                    Operand::Old
+                } else {
+                    Operand::Const(number(s, v)?)
+                };
+                if op == "*" {
+                    Operation::Multiply(v)
+                } else if op == "+" {
+                    Operation::Add(v)
+                } else {
+                    return Err(ParseError::at(s, op, "expected * or +"));
+                }
+            }
+            _ => {
+                return Err(ParseError::at(s, line, "expected an operation"));
+            }
+        };
+
+        let line = next_line("a divisibility test")?;
+        let divisible_by_test: u64 = match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["Test:", "divisible", "by", v] => match number(s, v)? {
+                0 => return Err(ParseError::at(s, v, "can't divide by zero")),
+                v => v,
+            },
+            _ => {
+                return Err(ParseError::at(s, line, "expected a divisibility test"));
+            }
+        };
+
+        let line = next_line("where to throw if true")?;
+        let true_throw_to: usize = match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["If", "true:", "throw", "to", "monkey", v] => number(s, v)?,
+            _ => {
+                return Err(ParseError::at(s, line, "expected where to throw if true"));
+            }
+        };
+
+        let line = next_line("where to throw if false")?;
+        let false_throw_to: usize = match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["If", "false:", "throw", "to", "monkey", v] => number(s, v)?,
+            _ => {
+                return Err(ParseError::at(s, line, "expected where to throw if false"));
+            }
+        };
+
+        Ok(Monkey {
+            id,
+            starting_items,
+            operation,
+            divisible_by_test,
+            true_throw_to,
+            false_throw_to,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Zoo {
+    monkeys: Box<[Monkey]>,
+    dynamics: Box<[MonkeyDynamics]>,
+}
+
+impl Zoo {
+    fn new(monkeys: Vec<Monkey>) -> Self {
+        let dynamics = monkeys.iter().map(Monkey::get_dynamics).collect::<Vec<_>>();
+        Self {
+            monkeys: monkeys.into(),
+            dynamics: dynamics.into(),
+        }
+    }
+    pub fn len(&self) -> usize {
+        self.monkeys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.monkeys.is_empty()
+    }
+
+    // The worry levels of the items a monkey holds.
+    pub fn items(&self, monkey: usize) -> &[u64] {
+        &self.dynamics[monkey].items
+    }
+
+    // How many items a monkey has inspected so far.
+    pub fn inspected(&self, monkey: usize) -> usize {
+        self.dynamics[monkey].count_inspected
+    }
+}
+
+// Each monkey's items, as the puzzle shows them between rounds.
+impl fmt::Display for Zoo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (monkey, dynamics) in self.monkeys.iter().zip(self.dynamics.iter()) {
+            let items: Vec<String> = dynamics.items.iter().map(u64::to_string).collect();
+            write!(f, "Monkey {}:", monkey.id)?;
+            if !items.is_empty() {
+                write!(f, " {}", items.join(", "))?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+fn do_round(zoo: &mut Zoo, relief_fn: &dyn Fn(u64) -> u64) {
+    for i in 0..zoo.len() {
+        do_monkey_turn(i, zoo, relief_fn);
+    }
+}
+
+fn do_monkey_turn(index: usize, zoo: &mut Zoo, relief_fn: &dyn Fn(u64) -> u64) {
+    let monkey: &Monkey = &zoo.monkeys[index];
+
+    // Take the monkey's items off their hands: we'll be distributing
+    // to other monkeys.
+    let items = std::mem::take(&mut zoo.dynamics[index].items);
+
+    zoo.dynamics[index].count_inspected += items.len();
+    for item in items {
+        // Inspects an item, changing worry.
+        let item = apply_operation(item, &monkey.operation);
+
+        // Compute relief.
+        let item = relief_fn(item);
+
+        // Compute target to throw
+        let target: usize = if item.is_multiple_of(monkey.divisible_by_test) {
+            monkey.true_throw_to
+        } else {
+            monkey.false_throw_to
+        };
+
+        // In-place throw to the next monkey.
+        zoo.dynamics[target].items.push(item);
+    }
+}
+
+fn apply_operation(item: u64, operation: &Operation) -> u64 {
+    match operation {
+        Operation::Add(operand) => item + apply_operand(item, operand),
+        Operation::Multiply(operand) => item * apply_operand(item, operand),
+    }
+}
+
+fn apply_operand(old: u64, operand: &Operand) -> u64 {
+    match operand {
+        Operand::Const(v) => *v,
+        Operand::Old => old,
+    }
+}
+
+pub fn parse_zoo(s: &str) -> Result<Zoo, ParseError> {
+    let chunks: Vec<&str> = s
+        .split("\n\n")
+        .filter(|chunk| !chunk.trim().is_empty())
+        .collect();
+    let monkeys: Vec<Monkey> = chunks
+        .iter()
+        .map(|chunk| chunk.parse().map_err(|e: ParseError| e.within(s, chunk)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Monkeys can only throw to each other.
+    for (chunk, monkey) in chunks.iter().zip(&monkeys) {
+        for target in [monkey.true_throw_to, monkey.false_throw_to] {
+            if target < monkeys.len() {
+                continue;
+            }
+            // The number at the end of the "If ...: throw to monkey N" line.
+            let number = chunk
+                .lines()
+                .filter(|line| line.trim_start().starts_with("If "))
+                .filter_map(|line| line.split_whitespace().last())
+                .find(|number| number.parse() == Ok(target))
+                .unwrap_or(chunk);
+            return Err(ParseError::at(s, number, "throws to a missing monkey"));
+        }
+    }
+    Ok(Zoo::new(monkeys))
+}
+
+pub fn part_1(zoo: &Zoo, rounds: usize) -> Result<usize, Box<dyn Error>> {
+    part_1_with(zoo, rounds, &mut ())
+}
+
+// As part_1, showing the observer the monkeys after each round.
+pub fn part_1_with(
+    zoo: &Zoo,
+    rounds: usize,
+    observer: &mut impl Observer<Zoo>,
+) -> Result<usize, Box<dyn Error>> {
+    let mut zoo = zoo.clone();
+    for round in 1..=rounds {
+        do_round(&mut zoo, &|x| x / 3);
+        observer.observe(round, &zoo);
+    }
+    monkey_business(&zoo)
+}
+
+pub fn part_2(zoo: &Zoo, rounds: usize) -> Result<usize, Box<dyn Error>> {
+    part_2_with(zoo, rounds, &mut ())
+}
+
+// As part_2, showing the observer the monkeys after each round.
+pub fn part_2_with(
+    zoo: &Zoo,
+    rounds: usize,
+    observer: &mut impl Observer<Zoo>,
+) -> Result<usize, Box<dyn Error>> {
+    let mut zoo = zoo.clone();
+
+    // Keep the numbers down by doing modulo the LCM of all divisibles.
+    // https://jactl.io/blog/2023/04/17/advent-of-code-2022-day11.html
+    let common_multiple: u64 = zoo
+        .monkeys
+        .iter()
+        .map(|monkey| monkey.divisible_by_test)
+        .fold(1, least_common_multiple);
+
+    for round in 1..=rounds {
+        do_round(&mut zoo, &|x| x % common_multiple);
+        observer.observe(round, &zoo);
+    }
+    monkey_business(&zoo)
+}
+
+// The product of the two largest inspection counts.
+fn monkey_business(zoo: &Zoo) -> Result<usize, Box<dyn Error>> {
+    let mut inspections: Vec<usize> = zoo
+        .dynamics
+        .iter()
+        .map(|monkey| monkey.count_inspected)
+        .collect();
+    inspections.sort();
+
+    match inspections[..] {
+        [.., second, first] => Ok(second * first),
+        _ => Err("need at least two monkeys".into()),
+    }
+}
+
+#[derive(Debug)]
+pub struct Day11 {
+    pub part1_rounds: usize,
+    pub part2_rounds: usize,
+}
+
+impl Default for Day11 {
+    fn default() -> Self {
+        Day11 {
+            part1_rounds: 20,
+            part2_rounds: 10000,
+        }
+    }
+}
+
+impl Solution for Day11 {
+    type Input = Zoo;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_zoo(input)?)
+    }
+
+    fn part1(&self, zoo: &Self::Input) -> Result<usize, common::Error> {
+        part_1(zoo, self.part1_rounds)
+    }
+
+    fn part2(&self, zoo: &Self::Input) -> Result<usize, common::Error> {
+        part_2(zoo, self.part2_rounds)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        match name {
+            "part1-rounds" => self.part1_rounds = value.parse()?,
+            "part2-rounds" => self.part2_rounds = value.parse()?,
+            _ => {
+                return Err(common::unknown_param(
+                    name,
+                    &["part1-rounds", "part2-rounds"],
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+fn greatest_common_divisor(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn least_common_multiple(a: u64, b: u64) -> u64 {
+    a * b / greatest_common_divisor(a, b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str() -> Result<(), ParseError> {
+        let input = "
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+"
+        .trim_start();
+        let monkey: Monkey = input.parse()?;
+
+        assert_eq!(monkey.id, 0);
+        assert_eq!(monkey.starting_items, vec![79, 98]);
+        assert_eq!(monkey.operation, Operation::Multiply(Operand::Const(19)));
+        assert_eq!(monkey.divisible_by_test, 23);
+        assert_eq!(monkey.true_throw_to, 2);
+        assert_eq!(monkey.false_throw_to, 3);
+
+        Ok(())
+    }
+
+    const EXAMPLE: &str = "\
+Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn test_parse_zoo() -> Result<(), Box<dyn Error>> {
+        let zoo = parse_zoo(EXAMPLE)?;
+        assert_eq!(zoo.len(), 4);
+
+        assert_eq!(
+            zoo.dynamics
+                .iter()
+                .map(|m| m.items.clone())
+                .collect::<Vec<Vec<_>>>(),
+            vec![
+                vec![79, 98],
+                vec![54, 65, 75, 74],
+                vec![79, 60, 97],
+                vec![74],
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_zoo_errors() {
+        let bad_item = EXAMPLE.replace("54, 65", "54, x5");
+        let e = parse_zoo(&bad_item).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (9, 23, "x5"));
+
+        let bad_operator = EXAMPLE.replace("old + 6", "old - 6");
+        let e = parse_zoo(&bad_operator).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (10, 24, "-"));
+
+        let truncated = &EXAMPLE[..EXAMPLE.find("    If false: throw to monkey 3").unwrap()];
+        let e = parse_zoo(truncated).unwrap_err();
+        assert_eq!((e.line, e.column), (6, 1));
+
+        let missing_monkey = EXAMPLE.replace("throw to monkey 0", "throw to monkey 4");
+        let e = parse_zoo(&missing_monkey).unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (13, 31, "4"));
+    }
+
+    #[test]
+    fn test_do_round() -> Result<(), Box<dyn Error>> {
+        let mut zoo = parse_zoo(EXAMPLE)?;
+        do_round(&mut zoo, &|x| x / 3);
+
+        assert_eq!(
+            zoo.dynamics
+                .iter()
+                .map(|m| m.items.clone())
+                .collect::<Vec<Vec<_>>>(),
+            vec![
+                vec![20, 23, 27, 26],
+                vec![2080, 25, 167, 207, 401, 1046],
+                vec![],
+                vec![],
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_items_are_kept() -> Result<(), Box<dyn Error>> {
+        use common::observe::{Counter, Invariant};
+
+        let zoo = parse_zoo(EXAMPLE)?;
+        let total = |zoo: &Zoo| (0..zoo.len()).map(|i| zoo.items(i).len()).sum::<usize>();
+        let mut kept = Invariant::new("ten items between them", |zoo: &Zoo| total(zoo) == 10);
+        let mut counter = Counter::default();
+        assert_eq!(
+            part_1_with(&zoo, 20, &mut (&mut kept, &mut counter))?,
+            10605
+        );
+        assert_eq!(counter.last, Some(20));
+        Ok(())
+    }
+
+    #[test]
+    fn test_display_zoo() -> Result<(), Box<dyn Error>> {
+        let mut zoo = parse_zoo(EXAMPLE)?;
+        do_round(&mut zoo, &|x| x / 3);
+        assert_eq!(
+            zoo.to_string(),
+            "Monkey 0: 20, 23, 27, 26\n\
+             Monkey 1: 2080, 25, 167, 207, 401, 1046\n\
+             Monkey 2:\n\
+             Monkey 3:\n"
+        );
+        assert_eq!(zoo.inspected(0), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,257 @@
+use common::{ParseError, Solution};
+use grid::image::Palette;
+use grid::{Grid, Pos};
+use std::collections::{HashMap, VecDeque};
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct HeightMap(Grid<u8>);
+
+impl HeightMap {
+    fn find(&self, pred: fn(u8) -> bool) -> Option<Pos> {
+        self.0.iter().find(|&(_, &ch)| pred(ch)).map(|(p, _)| p)
+    }
+
+    fn find_all(&self, pred: &dyn Fn(u8) -> bool) -> Vec<Pos> {
+        self.0
+            .iter()
+            .filter(|&(_, &ch)| pred(ch))
+            .map(|(p, _)| p)
+            .collect()
+    }
+
+    fn at(&self, p: Pos) -> Option<u8> {
+        self.0.get(p).copied()
+    }
+
+    fn height(&self, p: Pos) -> Option<u8> {
+        let ch = self.at(p)?;
+        match ch {
+            b'S' => Some(0),
+            b'E' => Some(25),
+            b'a'..=b'z' => Some(ch - b'a'),
+            _ => None,
+        }
+    }
+
+    // Returns list of neighbor positions in-bounds of the heightmap
+    fn neighbors(&self, p: Pos) -> Vec<Pos> {
+        self.0.neighbors4(p).collect()
+    }
+}
+
+impl FromStr for HeightMap {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(HeightMap(Grid::parse(s, |ch| {
+            matches!(ch, 'S' | 'E' | 'a'..='z').then_some(ch as u8)
+        })?))
+    }
+}
+
+pub fn part_1(h: &HeightMap) -> Option<u32> {
+    steps(&search(h, h.find(|ch| ch == b'S'))?)
+}
+
+pub fn part_2(h: &HeightMap) -> Option<u32> {
+    steps(&search(h, h.find_all(&|p| p == b'a' || p == b'S'))?)
+}
+
+fn steps(path: &[Pos]) -> Option<u32> {
+    u32::try_from(path.len().checked_sub(1)?).ok()
+}
+
+// Returns the shortest path from any of the starting positions to E, both
+// ends included.
+fn search(h: &HeightMap, starting_positions: impl IntoIterator<Item = Pos>) -> Option<Vec<Pos>> {
+    // Keep a queue of positions to visit, and where each was reached from.
+    let mut queue = VecDeque::<Pos>::new();
+    let mut came_from = HashMap::<Pos, Option<Pos>>::new();
+    for starting in starting_positions {
+        if came_from.insert(starting, None).is_none() {
+            queue.push_back(starting);
+        }
+    }
+
+    while let Some(p) = queue.pop_front() {
+        // Terminate search early if we hit the end, and retrace the steps.
+        if h.at(p) == Some(b'E') {
+            let mut path: Vec<Pos> =
+                std::iter::successors(Some(p), |q| came_from.get(q).copied().flatten()).collect();
+            path.reverse();
+            return Some(path);
+        }
+
+        // Queue up the neighbors that we can visit and haven't reached yet.
+        let p_height = h.height(p)?;
+        for candidate in h.neighbors(p) {
+            if let Some(candidate_height) = h.height(candidate) {
+                // We can either descend, stay at the same height, or
+                // climb up by one.
+                if candidate_height <= (p_height + 1) && !came_from.contains_key(&candidate) {
+                    came_from.insert(candidate, Some(p));
+                    queue.push_back(candidate);
+                }
+            }
+        }
+    }
+
+    None
+}
+
+// The map with the shortest path from S to E drawn over it in #, or None
+// if there isn't one.
+pub fn path_picture(h: &HeightMap) -> Option<String> {
+    let mut map = h.0.clone();
+    let path = search(h, h.find(|ch| ch == b'S'))?;
+    for &p in &path[1..path.len() - 1] {
+        map[p] = b'#';
+    }
+    Some(map.render(|&ch| ch as char))
+}
+
+// Heights shade from dark to light, with the path in red.
+pub fn palette() -> Palette {
+    Palette::new([0, 0, 0])
+        .gradient('a'..='z', [30, 60, 30], [240, 240, 220])
+        .with('S', [60, 120, 255])
+        .with('E', [255, 200, 0])
+        .with('#', [220, 30, 30])
+}
+
+#[derive(Debug, Default)]
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = HeightMap;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, h: &Self::Input) -> Result<u32, common::Error> {
+        Ok(part_1(h).ok_or("no path to the best signal")?)
+    }
+
+    fn part2(&self, h: &Self::Input) -> Result<u32, common::Error> {
+        Ok(part_2(h).ok_or("no path to the best signal")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let e = "Sab\nc1E\n".parse::<HeightMap>().unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "1"));
+
+        let e = "Sabc\ncE\n".parse::<HeightMap>().unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "cE"));
+    }
+
+    const SMALL_MAP: &str = "
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+    #[test]
+    fn test_from_str() {
+        let h: HeightMap = SMALL_MAP.parse().expect("Oops");
+        assert_eq!(h.0.height(), 5);
+        assert_eq!(h.0.width(), 8);
+    }
+
+    #[test]
+    fn test_at() {
+        let h: HeightMap = SMALL_MAP.parse().expect("Oops");
+        assert_eq!(h.at(Pos::new(0, 0)), Some(b'S'));
+        assert_eq!(h.at(Pos::new(1, 1)), Some(b'b'));
+        assert_eq!(h.at(Pos::new(2, 2)), Some(b'c'));
+        assert_eq!(h.at(Pos::new(3, 3)), Some(b't'));
+        assert_eq!(h.at(Pos::new(4, 4)), Some(b'f'));
+        assert_eq!(h.at(Pos::new(5, 5)), None);
+    }
+
+    #[test]
+    fn test_height() {
+        let h: HeightMap = SMALL_MAP.parse().expect("Oops");
+        assert_eq!(h.height(Pos::new(0, 0)), Some(0));
+        assert_eq!(h.height(Pos::new(1, 0)), Some(0));
+        assert_eq!(h.height(Pos::new(2, 0)), Some(1));
+        assert_eq!(h.height(Pos::new(3, 0)), Some(16));
+    }
+
+    #[test]
+    fn test_find() {
+        let h: HeightMap = SMALL_MAP.parse().expect("Oops");
+        assert_eq!(h.find(|ch| ch == b'S'), Some(Pos::new(0, 0)));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let h: HeightMap = SMALL_MAP.parse().expect("Oops");
+        // Upper left corner
+        assert_eq!(
+            h.neighbors(Pos::new(0, 0)),
+            vec![Pos::new(1, 0), Pos::new(0, 1)]
+        );
+
+        assert_eq!(
+            h.neighbors(Pos::new(1, 1)),
+            vec![
+                Pos::new(0, 1),
+                Pos::new(2, 1),
+                Pos::new(1, 0),
+                Pos::new(1, 2)
+            ]
+        );
+
+        // Bottom right corner
+        assert_eq!(
+            h.neighbors(Pos::new(7, 4)),
+            vec![Pos::new(6, 4), Pos::new(7, 3)]
+        );
+    }
+
+    #[test]
+    fn test_part_1() {
+        let h: HeightMap = SMALL_MAP.parse().expect("Oops");
+        assert_eq!(part_1(&h), Some(31));
+    }
+
+    #[test]
+    fn test_path_picture() {
+        let h: HeightMap = "Sbcdefghijklm\nzyxwvutsrqpon\nEaaaaaaaaaaaa\n"
+            .parse()
+            .expect("Oops");
+        assert_eq!(
+            path_picture(&h).as_deref(),
+            Some("S############\n#############\nEaaaaaaaaaaaa\n")
+        );
+        let h: HeightMap = "Sz\nzE\n".parse().expect("Oops");
+        assert_eq!(path_picture(&h), None);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let h: HeightMap = SMALL_MAP.parse().expect("Oops");
+        assert_eq!(
+            h.find_all(&|p| p == b'a' || p == b'S'),
+            vec![
+                Pos::new(0, 0),
+                Pos::new(1, 0),
+                Pos::new(0, 1),
+                Pos::new(0, 2),
+                Pos::new(0, 3),
+                Pos::new(0, 4)
+            ]
+        );
+    }
+}
@@ -0,0 +1,499 @@
+use common::{ParseError, Solution};
+use logos::{Lexer, Logos};
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Data {
+    Num(u32),
+    List(Vec<Data>),
+}
+
+// Packets are written the way the puzzle writes them, e.g. [1,[2,3]].
+impl fmt::Display for Data {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Data::Num(n) => write!(f, "{}", n),
+            Data::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl PartialOrd for Data {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Data {
+    fn cmp(&self, rhs: &Data) -> Ordering {
+        match (self, rhs) {
+            (Data::Num(lhs), Data::Num(rhs)) => lhs.cmp(rhs),
+            (Data::Num(lhs), rhs @ Data::List(_)) => {
+                Data::cmp(&Data::List(vec![Data::Num(*lhs)]), rhs)
+            }
+            (lhs @ Data::List(_), Data::Num(rhs)) => {
+                Data::cmp(lhs, &Data::List(vec![Data::Num(*rhs)]))
+            }
+            (Data::List(lhs_items), Data::List(rhs_items)) => {
+                let mut lhs_iter = lhs_items.iter();
+                let mut rhs_iter = rhs_items.iter();
+
+                loop {
+                    match (lhs_iter.next(), rhs_iter.next()) {
+                        (None, None) => {
+                            return Ordering::Equal;
+                        }
+                        (None, Some(_)) => {
+                            return Ordering::Less;
+                        }
+                        (Some(_), None) => {
+                            return Ordering::Greater;
+                        }
+                        (Some(l), Some(r)) => match Data::cmp(l, r) {
+                            Ordering::Less => {
+                                return Ordering::Less;
+                            }
+                            Ordering::Greater => {
+                                return Ordering::Greater;
+                            }
+                            Ordering::Equal => {}
+                        },
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Logos, Debug, PartialEq)]
+#[logos(skip r"[ \t\n\f]+")] // ignore whitespace
+enum Token {
+    #[regex(r"\d+", |lex| lex.slice().parse().ok())]
+    Num(u32),
+
+    #[token("[")]
+    Lbracket,
+
+    #[token("]")]
+    Rbracket,
+
+    #[token(",")]
+    Comma,
+}
+
+struct Tokenizer<'a> {
+    lexer: Lexer<'a, Token>,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(s: &'a str) -> Self {
+        Tokenizer {
+            lexer: Token::lexer(s),
+        }
+    }
+
+    fn input(&self) -> &'a str {
+        self.lexer.source()
+    }
+
+    // The text of the token most recently returned by next().
+    fn slice(&self) -> &'a str {
+        self.lexer.slice()
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Token, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.lexer.next()? {
+            Ok(token) => Some(Ok(token)),
+            Err(()) => Some(Err(ParseError::at(
+                self.input(),
+                self.slice(),
+                "invalid token",
+            ))),
+        }
+    }
+}
+
+// Real packets nest a handful of lists deep.  The limit keeps a malicious
+// input from overflowing the stack, both here and in Data's recursive
+// comparison.
+const MAX_DEPTH: usize = 100;
+
+struct Parser<'a> {
+    tokens: Tokenizer<'a>,
+    lookahead: Option<Token>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: Tokenizer<'a>) -> Self {
+        Self {
+            tokens,
+            lookahead: None,
+        }
+    }
+
+    fn peek(&mut self) -> Result<Option<&Token>, ParseError> {
+        if self.lookahead.is_none() {
+            self.lookahead = self.tokens.next().transpose()?;
+        }
+        Ok(self.lookahead.as_ref())
+    }
+
+    fn advance(&mut self) {
+        self.lookahead = None;
+    }
+
+    // An error about the token we've just peeked at.
+    fn unexpected(&self, message: &str) -> ParseError {
+        let input = self.tokens.input();
+        let text = match self.lookahead {
+            Some(_) => self.tokens.slice(),
+            None => &input[input.len()..],
+        };
+        ParseError::at(input, text, message)
+    }
+
+    fn data(&mut self, depth: usize) -> Result<Data, ParseError> {
+        match self.peek()? {
+            Some(&Token::Num(n)) => {
+                self.advance();
+                Ok(Data::Num(n))
+            }
+
+            Some(Token::Lbracket) => {
+                if depth == MAX_DEPTH {
+                    return Err(self.unexpected("packet nests too deeply"));
+                }
+                self.advance();
+
+                let mut data_items = Vec::new();
+                if let Some(Token::Rbracket) = self.peek()? {
+                    self.advance();
+                    return Ok(Data::List(data_items));
+                }
+                loop {
+                    // Recursive call: pick up items
+                    data_items.push(self.data(depth + 1)?);
+
+                    // Items are separated by commas, and the list ends at ']'.
+                    match self.peek()? {
+                        Some(Token::Comma) => self.advance(),
+                        Some(Token::Rbracket) => {
+                            self.advance();
+                            return Ok(Data::List(data_items));
+                        }
+                        _ => return Err(self.unexpected("expected ',' or ']'")),
+                    }
+                }
+            }
+            _ => Err(self.unexpected("expected a number or '['")),
+        }
+    }
+}
+
+impl<'a> Iterator for Parser<'a> {
+    type Item = Result<Data, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.peek() {
+            Ok(None) => None,
+            Ok(Some(_)) => Some(self.data(0)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+// Reads every packet in the input, ignoring the blank lines between pairs.
+pub fn parse_packets(input: &str) -> Result<Vec<Data>, ParseError> {
+    Parser::new(Tokenizer::new(input)).collect()
+}
+
+pub fn part1(input: &str) -> Result<i32, ParseError> {
+    Ok(sum_ordered_pair_indices(&parse_packets(input)?))
+}
+
+pub fn sum_ordered_pair_indices(packets: &[Data]) -> i32 {
+    let mut sum = 0;
+    for (index, pair) in (1..).zip(packets.chunks_exact(2)) {
+        if Data::cmp(&pair[0], &pair[1]).is_lt() {
+            sum += index;
+        }
+    }
+    sum
+}
+
+pub fn part2(input: &str) -> Result<Option<usize>, ParseError> {
+    Ok(decoder_key(&parse_packets(input)?))
+}
+
+pub fn decoder_key(packets: &[Data]) -> Option<usize> {
+    let mut items: Vec<Data> = packets.to_vec();
+    let divider1 = parse("[[2]]");
+    let divider2 = parse("[[6]]");
+    items.push(divider1.clone());
+    items.push(divider2.clone());
+
+    items.sort();
+
+    let index1 = items.binary_search(&divider1);
+    let index2 = items.binary_search(&divider2);
+    Some(index1.map(|x| x + 1).ok()? * index2.map(|x| x + 1).ok()?)
+}
+
+#[derive(Debug, Default)]
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Data>;
+    type Part1 = i32;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_packets(input)?)
+    }
+
+    fn part1(&self, packets: &Self::Input) -> Result<i32, common::Error> {
+        Ok(sum_ordered_pair_indices(packets))
+    }
+
+    fn part2(&self, packets: &Self::Input) -> Result<usize, common::Error> {
+        Ok(decoder_key(packets).ok_or("missing divider packet")?)
+    }
+}
+
+fn parse(s: &str) -> Data {
+    Parser::new(Tokenizer::new(s))
+        .next()
+        .expect("a data")
+        .expect("a valid packet")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_number() {
+        let input = "42";
+        let mut tokenizer = Tokenizer::new(input);
+        assert_eq!(tokenizer.next(), Some(Ok(Token::Num(42))));
+        assert_eq!(tokenizer.next(), None);
+    }
+
+    #[test]
+    fn test_tokenize_lbracket() {
+        let input = "[";
+        let mut tokenizer = Tokenizer::new(input);
+        assert_eq!(tokenizer.next(), Some(Ok(Token::Lbracket)));
+        assert_eq!(tokenizer.next(), None);
+    }
+
+    #[test]
+    fn test_tokenize_rbracket() {
+        let input = "]";
+        let mut tokenizer = Tokenizer::new(input);
+        assert_eq!(tokenizer.next(), Some(Ok(Token::Rbracket)));
+        assert_eq!(tokenizer.next(), None);
+    }
+
+    #[test]
+    fn test_tokenize_comma() {
+        let input = ",";
+        let mut tokenizer = Tokenizer::new(input);
+        assert_eq!(tokenizer.next(), Some(Ok(Token::Comma)));
+        assert_eq!(tokenizer.next(), None);
+    }
+
+    #[test]
+    fn test_tokenize_list() {
+        let input = "[10,22,[301]]";
+        let tokenizer = Tokenizer::new(input);
+        assert_eq!(
+            tokenizer.collect::<Result<Vec<_>, _>>(),
+            Ok(vec![
+                Token::Lbracket,
+                Token::Num(10),
+                Token::Comma,
+                Token::Num(22),
+                Token::Comma,
+                Token::Lbracket,
+                Token::Num(301),
+                Token::Rbracket,
+                Token::Rbracket,
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let input = "42";
+        let tokenizer = Tokenizer::new(input);
+        let mut parser = Parser::new(tokenizer);
+        assert_eq!(parser.next(), Some(Ok(Data::Num(42))));
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_parse_empty_list() {
+        let input = "[]";
+        let tokenizer = Tokenizer::new(input);
+        let mut parser = Parser::new(tokenizer);
+        assert_eq!(parser.next(), Some(Ok(Data::List(vec![]))));
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_parse_list() {
+        let input = "[1, 2]";
+        let tokenizer = Tokenizer::new(input);
+        let mut parser = Parser::new(tokenizer);
+        assert_eq!(
+            parser.next(),
+            Some(Ok(Data::List(vec![Data::Num(1), Data::Num(2)])))
+        );
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_parse_nested_list() {
+        let input = "[1, [2], 3]";
+        let tokenizer = Tokenizer::new(input);
+        let mut parser = Parser::new(tokenizer);
+        assert_eq!(
+            parser.next(),
+            Some(Ok(Data::List(vec![
+                Data::Num(1),
+                Data::List(vec![Data::Num(2)]),
+                Data::Num(3)
+            ])))
+        );
+        assert_eq!(parser.next(), None);
+    }
+
+    #[test]
+    fn test_cmp_data() {
+        assert_eq!(
+            Data::cmp(&parse("[1,1,3,1,1]"), &parse("[1,1,5,1,1]")),
+            Ordering::Less
+        );
+
+        assert_eq!(
+            Data::cmp(&parse("[[1],[2,3,4]]"), &parse("[[1],4]")),
+            Ordering::Less
+        );
+
+        assert_eq!(
+            Data::cmp(&parse("[9]"), &parse("[[8,7,6]]")),
+            Ordering::Greater
+        );
+
+        assert_eq!(
+            Data::cmp(&parse("[[4,4],4,4]"), &parse("[[4,4],4,4,4]")),
+            Ordering::Less
+        );
+
+        assert_eq!(
+            Data::cmp(&parse("[7,7,7,7]"), &parse("[7,7,7]")),
+            Ordering::Greater
+        );
+
+        assert_eq!(Data::cmp(&parse("[]"), &parse("[3]")), Ordering::Less);
+
+        assert_eq!(
+            Data::cmp(&parse("[[[]]]"), &parse("[[]]")),
+            Ordering::Greater
+        );
+
+        assert_eq!(
+            Data::cmp(
+                &parse("[1,[2,[3,[4,[5,6,7]]]],8,9]"),
+                &parse("[1,[2,[3,[4,[5,6,0]]]],8,9]")
+            ),
+            Ordering::Greater
+        );
+
+        assert_eq!(Data::cmp(&parse("[[2]]"), &parse("[[2]]")), Ordering::Equal);
+        assert_eq!(Data::cmp(&parse("[[6]]"), &parse("[[6]]")), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2(
+                "
+[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]"
+            ),
+            Ok(Some(140))
+        );
+    }
+
+    #[test]
+    fn test_parse_packets_errors() {
+        assert_eq!(
+            parse_packets("[1,2]\n[3,x]"),
+            Err(ParseError::new(2, 4, "x", "invalid token"))
+        );
+        assert_eq!(
+            parse_packets("[1,2]\n[3 4]"),
+            Err(ParseError::new(2, 4, "4", "expected ',' or ']'"))
+        );
+        assert_eq!(
+            parse_packets("[1,[2]\n"),
+            Err(ParseError::new(2, 1, "", "expected ',' or ']'"))
+        );
+        assert_eq!(
+            parse_packets("[1,]"),
+            Err(ParseError::new(1, 4, "]", "expected a number or '['"))
+        );
+
+        let deep = format!("{}{}", "[".repeat(100), "]".repeat(100));
+        assert!(parse_packets(&deep).is_ok());
+        let deeper = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        assert_eq!(
+            parse_packets(&deeper),
+            Err(ParseError::new(1, 101, "[", "packet nests too deeply"))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let packet = "[1,[2,[3,[4,[5,6,7]]]],8,9]";
+        assert_eq!(parse(packet).to_string(), packet);
+        assert_eq!(Data::List(vec![]).to_string(), "[]");
+    }
+}
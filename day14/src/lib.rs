@@ -0,0 +1,526 @@
+use common::observe::Observer;
+use common::{ParseError, Solution};
+use grid::image::Palette;
+use grid::{Pos, SparseGrid};
+use logos::{Lexer, Logos};
+use std::fmt;
+
+const SOURCE: Pos = Pos::new(500, 0);
+
+#[derive(Debug)]
+pub struct Cave {
+    cells: SparseGrid<Cell>,
+    y_boundary: i64,
+}
+
+impl Cave {
+    fn new() -> Self {
+        Self {
+            cells: SparseGrid::new(),
+            // The source at y 0 is as high as rock goes, so even an empty
+            // cave has a floor below it.
+            y_boundary: 2,
+        }
+    }
+
+    fn at(&self, p: Pos) -> Option<Cell> {
+        self.cells.get(p).copied()
+    }
+
+    // How much sand has come to rest.
+    pub fn sand(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|&(_, &cell)| cell == Cell::Sand)
+            .count()
+    }
+
+    fn add_wall(&mut self, p: Pos) {
+        self.cells.insert(p, Cell::Wall);
+        self.y_boundary = self.y_boundary.max(p.y + 2);
+    }
+
+    fn fill_wall_line(&mut self, p1: Pos, p2: Pos) {
+        self.add_wall(p1);
+
+        self.add_wall(p2);
+
+        if p1.x != p2.x && p1.y != p2.y {
+            // Ignore diagonals
+            return;
+        }
+
+        let step = Pos::new((p2.x - p1.x).signum(), (p2.y - p1.y).signum());
+        let mut p = p1;
+        while p != p2 {
+            self.add_wall(p);
+            p += step;
+        }
+    }
+
+    // Returns `true` if sand is at rest, `false` if it falls into the abyss.
+    fn drop_sand_part_1(&mut self, mut p: Pos) -> bool {
+        while p.y != self.y_boundary {
+            let lower_left = p + Pos::new(-1, 1);
+            let down = p + Pos::DOWN;
+            let lower_right = p + Pos::new(1, 1);
+            match (
+                self.at(lower_left).unwrap_or(Cell::Empty),
+                self.at(down).unwrap_or(Cell::Empty),
+                self.at(lower_right).unwrap_or(Cell::Empty),
+            ) {
+                (_, Cell::Empty, _) => {
+                    p = down;
+                }
+                (Cell::Empty, _, _) => {
+                    p = lower_left;
+                }
+                (_, _, Cell::Empty) => {
+                    p = lower_right;
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+
+        if p.y != self.y_boundary {
+            self.cells.insert(p, Cell::Sand);
+            true
+        } else {
+            false
+        }
+    }
+
+    /** Tells where the sand was dropped */
+    fn drop_sand_part_2(&mut self, mut p: Pos) -> Pos {
+        loop {
+            let lower_left = p + Pos::new(-1, 1);
+            let down = p + Pos::DOWN;
+            let lower_right = p + Pos::new(1, 1);
+            let bottom_boundary = if p.y + 1 == self.y_boundary {
+                Cell::Wall
+            } else {
+                Cell::Empty
+            };
+            match (
+                self.at(lower_left).unwrap_or(bottom_boundary),
+                self.at(down).unwrap_or(bottom_boundary),
+                self.at(lower_right).unwrap_or(bottom_boundary),
+            ) {
+                (_, Cell::Empty, _) => {
+                    p = down;
+                }
+                (Cell::Empty, _, _) => {
+                    p = lower_left;
+                }
+                (_, _, Cell::Empty) => {
+                    p = lower_right;
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+
+        self.cells.insert(p, Cell::Sand);
+
+        p
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Cell {
+    Empty,
+    Sand,
+    Wall,
+}
+
+// Draws the cave as in the puzzle, with # for rock, o for sand and + for
+// the source, down to just above the floor.
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min, max) = self.cells.bounds().unwrap_or((SOURCE, SOURCE));
+        for y in min.y.min(SOURCE.y)..self.y_boundary {
+            for x in min.x.min(SOURCE.x)..=max.x.max(SOURCE.x) {
+                let p = Pos::new(x, y);
+                let ch = match self.at(p) {
+                    Some(Cell::Wall) => '#',
+                    Some(Cell::Sand) => 'o',
+                    _ if p == SOURCE => '+',
+                    _ => '.',
+                };
+                write!(f, "{}", ch)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Tokenizer for reading the input, the list of positions that form
+// the walls.
+#[derive(Logos, Debug, PartialEq)]
+#[logos(skip r"[ \t\n\f]+")] // ignore whitespace
+enum Token {
+    #[regex(r"\d+", |lex| lex.slice().parse().ok())]
+    Num(i64),
+
+    #[token(",")]
+    Comma,
+
+    #[token("->")]
+    Arrow,
+}
+
+fn next_token(lexer: &mut Lexer<Token>) -> Result<Option<Token>, ParseError> {
+    match lexer.next() {
+        None => Ok(None),
+        Some(Ok(token)) => Ok(Some(token)),
+        Some(Err(())) => Err(ParseError::at(
+            lexer.source(),
+            lexer.slice(),
+            "invalid token",
+        )),
+    }
+}
+
+// An error about `token`, the one the lexer has just read.
+fn unexpected(lexer: &Lexer<Token>, token: Option<Token>, message: &str) -> ParseError {
+    let s = lexer.source();
+    let text = match token {
+        Some(_) => lexer.slice(),
+        None => &s[s.len()..],
+    };
+    ParseError::at(s, text, message)
+}
+
+fn parse_number(lexer: &mut Lexer<Token>) -> Result<i64, ParseError> {
+    match next_token(lexer)? {
+        Some(Token::Num(n)) => Ok(n),
+        other => Err(unexpected(lexer, other, "expected a number")),
+    }
+}
+
+fn parse_line(s: &str) -> Result<Vec<Pos>, ParseError> {
+    let mut result: Vec<Pos> = Vec::new();
+    let mut lexer = Token::lexer(s);
+
+    loop {
+        let x = parse_number(&mut lexer)?;
+        let start = lexer.span().start;
+        match next_token(&mut lexer)? {
+            Some(Token::Comma) => {}
+            other => return Err(unexpected(&lexer, other, "expected ','")),
+        }
+        let y = parse_number(&mut lexer)?;
+
+        // Walls only run horizontally or vertically.
+        if let Some(&last) = result.last() {
+            if last.x != x && last.y != y {
+                let text = &s[start..lexer.span().end];
+                return Err(ParseError::at(s, text, "diagonal wall"));
+            }
+        }
+        result.push(Pos::new(x, y));
+
+        // Eat the arrow
+        match next_token(&mut lexer)? {
+            None => return Ok(result),
+            Some(Token::Arrow) => {}
+            other => return Err(unexpected(&lexer, other, "expected '->'")),
+        }
+    }
+}
+
+// Reads the rock paths, one per line.
+pub fn parse_paths(input: &str) -> Result<Vec<Vec<Pos>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(lineindex, line)| parse_line(line).map_err(|e| e.on_line(lineindex + 1)))
+        .collect()
+}
+
+fn build_cave(paths: &[Vec<Pos>]) -> Cave {
+    let mut cave = Cave::new();
+
+    // Fill in the walls
+    for positions in paths {
+        for pair in positions.windows(2) {
+            cave.fill_wall_line(pair[0], pair[1]);
+        }
+    }
+    cave
+}
+
+pub fn part_1(input: &str) -> Result<usize, ParseError> {
+    Ok(sand_at_rest(&parse_paths(input)?))
+}
+
+// Counts the sand that comes to rest before sand falls into the abyss, or
+// before the source is blocked, if the rock holds all the sand.
+pub fn sand_at_rest(paths: &[Vec<Pos>]) -> usize {
+    sand_at_rest_with(paths, &mut ())
+}
+
+// As sand_at_rest, showing the observer the cave after each unit of sand
+// comes to rest.
+pub fn sand_at_rest_with(paths: &[Vec<Pos>], observer: &mut impl Observer<Cave>) -> usize {
+    let mut cave = build_cave(paths);
+    let mut i = 0;
+    loop {
+        if cave.at(SOURCE).is_some() || !cave.drop_sand_part_1(SOURCE) {
+            return i;
+        }
+        i += 1;
+        observer.observe(i, &cave);
+    }
+}
+
+pub fn part_2(input: &str) -> Result<usize, ParseError> {
+    Ok(sand_until_blocked(&parse_paths(input)?))
+}
+
+// Counts the sand that comes to rest on the floor until the source is blocked.
+pub fn sand_until_blocked(paths: &[Vec<Pos>]) -> usize {
+    sand_until_blocked_with(paths, &mut ())
+}
+
+// As sand_until_blocked, showing the observer the cave after each unit of
+// sand comes to rest.
+pub fn sand_until_blocked_with(paths: &[Vec<Pos>], observer: &mut impl Observer<Cave>) -> usize {
+    fill_until_blocked(paths, observer).0
+}
+
+// The cave once sand has blocked the source, showing the observer the cave
+// after each unit of sand comes to rest.
+pub fn blocked_cave_with(paths: &[Vec<Pos>], observer: &mut impl Observer<Cave>) -> Cave {
+    fill_until_blocked(paths, observer).1
+}
+
+fn fill_until_blocked(paths: &[Vec<Pos>], observer: &mut impl Observer<Cave>) -> (usize, Cave) {
+    let mut cave = build_cave(paths);
+    let mut i = 1;
+    loop {
+        let rested = cave.drop_sand_part_2(SOURCE);
+        observer.observe(i, &cave);
+        if rested == SOURCE {
+            return (i, cave);
+        }
+        i += 1;
+    }
+}
+
+// Gray rock and yellow sand in a dark cave.
+pub fn palette() -> Palette {
+    Palette::new([20, 20, 30])
+        .with('#', [120, 120, 120])
+        .with('o', [230, 200, 120])
+        .with('+', [255, 80, 80])
+}
+
+#[derive(Debug, Default)]
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Vec<Pos>>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_paths(input)?)
+    }
+
+    fn part1(&self, paths: &Self::Input) -> Result<usize, common::Error> {
+        Ok(sand_at_rest(paths))
+    }
+
+    fn part2(&self, paths: &Self::Input) -> Result<usize, common::Error> {
+        Ok(sand_until_blocked(paths))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            parse_line("484,41 -> 484,42 -> 495,42 -> 495,41"),
+            Ok(vec![
+                Pos::new(484, 41),
+                Pos::new(484, 42),
+                Pos::new(495, 42),
+                Pos::new(495, 41)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_paths_errors() {
+        assert_eq!(
+            parse_paths("498,4 -> 498,6\n503,4 -> 502;4"),
+            Err(ParseError::new(2, 13, ";", "invalid token"))
+        );
+        assert_eq!(
+            parse_paths("498,4 -> 498,6 ->"),
+            Err(ParseError::new(1, 18, "", "expected a number"))
+        );
+        assert_eq!(
+            parse_paths("498,4 498,6"),
+            Err(ParseError::new(1, 7, "498", "expected '->'"))
+        );
+        assert_eq!(
+            parse_paths("498,4 -> 500,6"),
+            Err(ParseError::new(1, 10, "500,6", "diagonal wall"))
+        );
+    }
+
+    #[test]
+    fn test_fill_wall_down() {
+        let mut cave = Cave::new();
+        cave.fill_wall_line(Pos::new(0, 0), Pos::new(0, 3));
+        assert_eq!(
+            cave.cells,
+            SparseGrid::from_iter([
+                (Pos::new(0, 0), Cell::Wall),
+                (Pos::new(0, 1), Cell::Wall),
+                (Pos::new(0, 2), Cell::Wall),
+                (Pos::new(0, 3), Cell::Wall),
+            ])
+        );
+        assert_eq!(cave.y_boundary, 5);
+    }
+
+    #[test]
+    fn test_fill_wall_up() {
+        let mut cave = Cave::new();
+        cave.fill_wall_line(Pos::new(1, 3), Pos::new(1, 0));
+        assert_eq!(
+            cave.cells,
+            SparseGrid::from_iter([
+                (Pos::new(1, 3), Cell::Wall),
+                (Pos::new(1, 2), Cell::Wall),
+                (Pos::new(1, 1), Cell::Wall),
+                (Pos::new(1, 0), Cell::Wall),
+            ])
+        );
+        assert_eq!(cave.y_boundary, 5);
+    }
+
+    #[test]
+    fn test_fill_wall_left() {
+        let mut cave = Cave::new();
+        cave.fill_wall_line(Pos::new(2, 3), Pos::new(0, 3));
+        assert_eq!(
+            cave.cells,
+            SparseGrid::from_iter([
+                (Pos::new(2, 3), Cell::Wall),
+                (Pos::new(1, 3), Cell::Wall),
+                (Pos::new(0, 3), Cell::Wall),
+            ])
+        );
+        assert_eq!(cave.y_boundary, 5);
+    }
+
+    #[test]
+    fn test_fill_wall_right() {
+        let mut cave = Cave::new();
+        cave.fill_wall_line(Pos::new(2, 3), Pos::new(0, 3));
+        assert_eq!(
+            cave.cells,
+            SparseGrid::from_iter([
+                (Pos::new(2, 3), Cell::Wall),
+                (Pos::new(1, 3), Cell::Wall),
+                (Pos::new(0, 3), Cell::Wall),
+            ])
+        );
+        assert_eq!(cave.y_boundary, 5);
+    }
+
+    #[test]
+    fn test_fill_wall_same() {
+        let mut cave = Cave::new();
+        cave.fill_wall_line(Pos::new(2, 3), Pos::new(2, 3));
+        assert_eq!(
+            cave.cells,
+            SparseGrid::from_iter([(Pos::new(2, 3), Cell::Wall),])
+        );
+        assert_eq!(cave.y_boundary, 5);
+    }
+
+    #[test]
+    fn test_at() {
+        let mut cave = Cave::new();
+        cave.fill_wall_line(Pos::new(2, 3), Pos::new(2, 3));
+        assert_eq!(cave.at(Pos::new(2, 3)), Some(Cell::Wall));
+        assert_eq!(cave.at(Pos::new(2, 4)), None);
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+        assert_eq!(part_1(input), Ok(24));
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
+        assert_eq!(part_2(input), Ok(93));
+    }
+
+    #[test]
+    fn test_rock_holds_all_the_sand() {
+        let paths = parse_paths("498,0 -> 498,2 -> 502,2 -> 502,0").unwrap();
+        assert_eq!(sand_at_rest(&paths), 4);
+    }
+
+    #[test]
+    fn test_observed_sand() {
+        use common::observe::{Counter, Invariant};
+
+        let paths =
+            parse_paths("498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9").unwrap();
+        let mut step = 0;
+        let mut one_at_a_time = Invariant::new("one more unit of sand each step", |cave: &Cave| {
+            step += 1;
+            cave.sand() == step
+        });
+        let mut counter = Counter::default();
+        assert_eq!(
+            sand_until_blocked_with(&paths, &mut (&mut one_at_a_time, &mut counter)),
+            93
+        );
+        assert_eq!(counter.steps, 93);
+    }
+
+    #[test]
+    fn test_display_cave() {
+        use common::observe::Inspect;
+
+        let paths = parse_paths("498,0 -> 498,2 -> 502,2 -> 502,0").unwrap();
+        let mut frames = Vec::new();
+        let mut record = Inspect(|_, cave: &Cave| frames.push(cave.to_string()));
+        assert_eq!(sand_at_rest_with(&paths, &mut record), 4);
+        assert_eq!(frames[0], "#.+.#\n#.o.#\n#####\n.....\n");
+        assert_eq!(frames[3], "#.o.#\n#ooo#\n#####\n.....\n");
+    }
+
+    #[test]
+    fn test_blocked_cave() {
+        let cave = blocked_cave_with(&[], &mut ());
+        assert_eq!(cave.sand(), 4);
+        assert_eq!(cave.to_string(), ".o.\nooo\n");
+    }
+
+    #[test]
+    fn test_empty_cave() {
+        assert_eq!(sand_at_rest(&[]), 0);
+        assert_eq!(sand_until_blocked(&[]), 4);
+    }
+}
@@ -0,0 +1,284 @@
+// https://adventofcode.com/2022/day/15
+
+use common::{parallel, ParseError, Solution};
+use range_set_blaze::RangeSetBlaze;
+use regex::Regex;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct Pos(i32, i32);
+
+impl Pos {
+    // Returns Manhattan distance between two Pos, which can be more than
+    // fits in a u32.
+    fn dist(self, other: Self) -> u64 {
+        self.0.abs_diff(other.0) as u64 + self.1.abs_diff(other.1) as u64
+    }
+
+    fn signal_strength(self) -> u64 {
+        self.0 as u64 * 4000000 + self.1 as u64
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SensorData {
+    sensor_at: Pos,
+    beacon_at: Pos,
+}
+
+impl SensorData {
+    fn beacon_radius(&self) -> u64 {
+        self.sensor_at.dist(self.beacon_at)
+    }
+
+    // Returns a bound of positions bounded by the marker and beacon on line y,
+    // cut short where it would run past what an i32 holds.
+    fn get_boundary(&self, y: i32) -> Option<RangeInclusive<i32>> {
+        let beacon_radius = self.beacon_radius();
+        let y_distance_from_sensor = self.sensor_at.1.abs_diff(y) as u64;
+        if y_distance_from_sensor <= beacon_radius {
+            let delta = (beacon_radius - y_distance_from_sensor) as i64;
+            let x = self.sensor_at.0 as i64;
+            let left = (x - delta).max(i32::MIN as i64) as i32;
+            let right = (x + delta).min(i32::MAX as i64) as i32;
+            Some(left..=right)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for SensorData {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        let pattern = PATTERN.get_or_init(|| {
+            Regex::new(r"Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)")
+                .unwrap()
+        });
+        let caps = pattern.captures(s).ok_or_else(|| {
+            ParseError::new(
+                1,
+                1,
+                s,
+                "expected \"Sensor at x=X, y=Y: closest beacon is at x=X, y=Y\"",
+            )
+        })?;
+        let number = |index, loc| {
+            let m = caps.get(index).unwrap();
+            m.as_str()
+                .parse::<i32>()
+                .map_err(|e| ParseError::at(s, m.as_str(), format!("bad {}: {}", loc, e)))
+        };
+        let [n1, n2, n3, n4] = [
+            number(1, "sensor x")?,
+            number(2, "sensor y")?,
+            number(3, "beacon x")?,
+            number(4, "beacon y")?,
+        ];
+        Ok(SensorData {
+            sensor_at: Pos(n1, n2),
+            beacon_at: Pos(n3, n4),
+        })
+    }
+}
+
+pub fn parse_sensors(input: &str) -> Result<Vec<SensorData>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(lineindex, line)| {
+            line.parse()
+                .map_err(|e: ParseError| e.on_line(lineindex + 1))
+        })
+        .collect()
+}
+
+pub fn part_1(input: &str, y: i32) -> Result<usize, ParseError> {
+    Ok(count_beaconless_positions(&parse_sensors(input)?, y))
+}
+
+// Counts the positions on row y where a beacon cannot be.
+pub fn count_beaconless_positions(all_sensor_data: &[SensorData], y: i32) -> usize {
+    let mut positions = RangeSetBlaze::new();
+    for data in all_sensor_data {
+        positions.extend(data.get_boundary(y));
+    }
+
+    for data in all_sensor_data {
+        if data.beacon_at.1 == y {
+            positions.remove(data.beacon_at.0);
+        }
+    }
+
+    positions.len()
+}
+
+fn find_distress_beacon(sensor_data: &[SensorData], x_bounds: i32, y_bounds: i32) -> Option<Pos> {
+    let x_range = RangeSetBlaze::from_iter([0..=x_bounds]);
+
+    // Rows are independent, so they can be scanned in parallel; the first
+    // row with a gap wins either way.
+    parallel::find_map_first(0..=y_bounds as i64, |y| {
+        let y = y as i32;
+        let mut positions = RangeSetBlaze::new();
+        for data in sensor_data {
+            positions.extend(data.get_boundary(y));
+        }
+
+        if x_range.is_subset(&positions) {
+            None
+        } else {
+            (&x_range - &positions).first().map(|x| Pos(x, y))
+        }
+    })
+}
+
+pub fn part_2(input: &str, x_bounds: i32, y_bounds: i32) -> Result<Option<u64>, ParseError> {
+    Ok(tuning_frequency(&parse_sensors(input)?, x_bounds, y_bounds))
+}
+
+// The tuning frequency of the only position within bounds that no sensor covers.
+pub fn tuning_frequency(
+    all_sensor_data: &[SensorData],
+    x_bounds: i32,
+    y_bounds: i32,
+) -> Option<u64> {
+    find_distress_beacon(all_sensor_data, x_bounds, y_bounds).map(Pos::signal_strength)
+}
+
+// The row to scan in part 1, and the bound on the distress beacon's
+// coordinates in part 2, differ between the example and the real input.
+#[derive(Debug)]
+pub struct Day15 {
+    pub row: i32,
+    pub bound: i32,
+}
+
+impl Default for Day15 {
+    fn default() -> Self {
+        Day15 {
+            row: 2000000,
+            bound: 4000000,
+        }
+    }
+}
+
+impl Solution for Day15 {
+    type Input = Vec<SensorData>;
+    type Part1 = usize;
+    type Part2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_sensors(input)?)
+    }
+
+    fn part1(&self, sensors: &Self::Input) -> Result<usize, common::Error> {
+        Ok(count_beaconless_positions(sensors, self.row))
+    }
+
+    fn part2(&self, sensors: &Self::Input) -> Result<u64, common::Error> {
+        Ok(tuning_frequency(sensors, self.bound, self.bound).ok_or("no distress beacon")?)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        match name {
+            "row" => self.row = value.parse()?,
+            "bound" => self.bound = value.parse()?,
+            _ => return Err(common::unknown_param(name, &["row", "bound"])),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line() {
+        assert_eq!(
+            "Sensor at x=2, y=18: closest beacon is at x=-2, y=15"
+                .parse::<SensorData>()
+                .unwrap(),
+            SensorData {
+                sensor_at: Pos(2, 18),
+                beacon_at: Pos(-2, 15)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_sensors_errors() {
+        assert_eq!(
+            parse_sensors("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9"),
+            Err(ParseError::new(
+                2,
+                1,
+                "Sensor at x=9",
+                "expected \"Sensor at x=X, y=Y: closest beacon is at x=X, y=Y\""
+            ))
+        );
+        assert_eq!(
+            parse_sensors("Sensor at x=2, y=18: closest beacon is at x=-2, y=99999999999"),
+            Err(ParseError::new(
+                1,
+                51,
+                "99999999999",
+                "bad beacon y: number too large to fit in target type"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_no_beacon_positions() {
+        let s = SensorData {
+            sensor_at: Pos(8, 7),
+            beacon_at: Pos(2, 10),
+        };
+        assert_eq!(s.get_boundary(-3), None);
+        assert_eq!(s.get_boundary(-2), Some(8..=8));
+        assert_eq!(s.get_boundary(-1), Some(7..=9));
+    }
+
+    const TEST_INPUT: &str = "\
+Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+    #[test]
+    fn test_part_1() {
+        assert_eq!(part_1(TEST_INPUT, 10), Ok(26));
+
+        // The beacon is further away than a u32 reaches, so every x is covered.
+        let far = "Sensor at x=2147483647, y=0: closest beacon is at x=-2147483648, y=5";
+        assert_eq!(part_1(far, 3), Ok(1 << 32));
+    }
+
+    #[test]
+    fn test_find_distress_beacon() {
+        let sensor_data: Vec<SensorData> = TEST_INPUT
+            .lines()
+            .map(SensorData::from_str)
+            .collect::<Result<_, _>>()
+            .expect("could not parse clean sensor data");
+        assert_eq!(
+            find_distress_beacon(&sensor_data, 20, 20),
+            Some(Pos(14, 11))
+        );
+    }
+}
@@ -1,11 +1,11 @@
 use bit_set::BitSet;
+use common::{ParseError, Solution};
 use regex::Regex;
-use std::collections::HashMap;
-use std::str::FromStr;
+use std::collections::{HashMap, HashSet};
 use std::sync::OnceLock;
 
-mod dynamic_programming;
-mod search;
+pub mod dynamic_programming;
+pub mod search;
 
 #[derive(Debug, PartialEq)]
 pub struct Valve {
@@ -15,7 +15,7 @@ pub struct Valve {
 }
 
 impl std::str::FromStr for Valve {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         static RE: OnceLock<Regex> = OnceLock::new();
@@ -26,17 +26,20 @@ impl std::str::FromStr for Valve {
             Regex::new(&pattern).unwrap()
         });
 
-        let captures = re
-            .captures(s)
-            .ok_or(format!("Could not parse {:?} as Valve", s))?;
+        let captures = re.captures(s).ok_or_else(|| {
+            ParseError::new(
+                1,
+                1,
+                s,
+                "expected \"Valve ID has flow rate=N; tunnels lead to valves ID, ...\"",
+            )
+        })?;
 
         let id = captures.get(1).unwrap().as_str().to_owned();
-        let flow_rate: u32 = captures
-            .get(2)
-            .unwrap()
-            .as_str()
+        let flow_rate = captures.get(2).unwrap().as_str();
+        let flow_rate: u32 = flow_rate
             .parse::<u32>()
-            .map_err(|_| "Couldn't parse flow rate")?;
+            .map_err(|e| ParseError::at(s, flow_rate, format!("bad flow rate: {}", e)))?;
         let exits: Vec<String> = captures
             .get(3)
             .unwrap()
@@ -98,32 +101,118 @@ pub fn normalize_valves(valves: &[Valve]) -> Vec<NormalizedValve> {
     result
 }
 
-fn parse_valves(s: &str) -> Result<Vec<NormalizedValve>, String> {
-    s.trim()
-        .lines()
-        .map(Valve::from_str)
-        .collect::<Result<Vec<_>, _>>()
-        .map(|valves| normalize_valves(&valves[..]))
-        .map_err(|e| e.to_string())
+// Reads the valves, checking that each is described once, that every
+// tunnel leads to one of them, and that we have somewhere to start.
+pub fn parse_valves(s: &str) -> Result<Vec<NormalizedValve>, ParseError> {
+    let mut valves = Vec::new();
+    let mut lines = Vec::new();
+    let mut ids = HashSet::new();
+    for (lineindex, line) in s.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let valve: Valve = line
+            .parse()
+            .map_err(|e: ParseError| e.on_line(lineindex + 1))?;
+        if !ids.insert(valve.id.clone()) {
+            let id = &line[line.find(valve.id.as_str()).unwrap_or(0)..][..valve.id.len()];
+            return Err(ParseError::at(s, id, "duplicate valve"));
+        }
+        valves.push(valve);
+        lines.push(line);
+    }
+
+    for (valve, line) in valves.iter().zip(&lines) {
+        if let Some(exit) = valve.exits.iter().find(|exit| !ids.contains(*exit)) {
+            let tunnels = &line[line.find(';').unwrap_or(0)..];
+            let exit = tunnels
+                .split([' ', ','])
+                .find(|word| word == exit)
+                .unwrap_or(tunnels);
+            return Err(ParseError::at(s, exit, "no such valve"));
+        }
+    }
+
+    if !ids.contains("AA") {
+        return Err(ParseError::at(s, &s[s.len()..], "missing valve AA"));
+    }
+
+    Ok(normalize_valves(&valves))
 }
 
 fn get_current_flow(open: &BitSet, valves: &[NormalizedValve]) -> u32 {
     open.iter().map(|id| valves[id].flow_rate).sum()
 }
 
-pub fn part_1(s: &str) -> u32 {
-    let valves = parse_valves(s).unwrap();
-    dynamic_programming::find_optimal_total_flow(0, &valves, 30)
+pub fn part_1(s: &str) -> Result<u32, ParseError> {
+    let valves = parse_valves(s)?;
+    Ok(dynamic_programming::find_optimal_total_flow(0, &valves, 30))
 }
 
-pub fn part_1_with_search(s: &str) -> u32 {
-    let valves = parse_valves(s).unwrap();
-    search::find_optimal_total_flow(&[0], &valves, 30)
+pub fn part_1_with_search(s: &str) -> Result<u32, ParseError> {
+    let valves = parse_valves(s)?;
+    Ok(search::find_optimal_total_flow(&[0], &valves, 30))
 }
 
-pub fn part_2_with_search(s: &str) -> u32 {
-    let valves = parse_valves(s).unwrap();
-    search::find_optimal_total_flow(&[0, 0], &valves, 26)
+pub fn part_2_with_search(s: &str) -> Result<u32, ParseError> {
+    let valves = parse_valves(s)?;
+    Ok(search::find_optimal_total_flow(&[0, 0], &valves, 26))
+}
+
+// Minutes before the volcano erupts, alone and with the elephant's help.
+#[derive(Debug)]
+pub struct Day16 {
+    pub part1_minutes: u32,
+    pub part2_minutes: u32,
+}
+
+impl Default for Day16 {
+    fn default() -> Self {
+        Day16 {
+            part1_minutes: 30,
+            part2_minutes: 26,
+        }
+    }
+}
+
+impl Solution for Day16 {
+    type Input = Vec<NormalizedValve>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_valves(input)?)
+    }
+
+    fn part1(&self, valves: &Self::Input) -> Result<u32, common::Error> {
+        Ok(dynamic_programming::find_optimal_total_flow(
+            0,
+            valves,
+            self.part1_minutes as usize,
+        ))
+    }
+
+    fn part2(&self, valves: &Self::Input) -> Result<u32, common::Error> {
+        Ok(search::find_optimal_total_flow(
+            &[0, 0],
+            valves,
+            self.part2_minutes,
+        ))
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        match name {
+            "part1-minutes" => self.part1_minutes = value.parse()?,
+            "part2-minutes" => self.part2_minutes = value.parse()?,
+            _ => {
+                return Err(common::unknown_param(
+                    name,
+                    &["part1-minutes", "part2-minutes"],
+                ))
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -170,11 +259,43 @@ Valve BB has flow rate=13; tunnels lead to valves AA";
         );
     }
 
+    #[test]
+    fn test_parse_valves_errors() {
+        assert_eq!(
+            parse_valves("Valve AA has flow rate=0; tunnels lead to valves BB\nValve BB"),
+            Err(ParseError::new(
+                2,
+                1,
+                "Valve BB",
+                "expected \"Valve ID has flow rate=N; tunnels lead to valves ID, ...\""
+            ))
+        );
+        assert_eq!(
+            parse_valves(
+                "Valve AA has flow rate=0; tunnels lead to valves BB
+Valve BB has flow rate=13; tunnels lead to valves AA
+Valve AA has flow rate=2; tunnels lead to valves BB"
+            ),
+            Err(ParseError::new(3, 7, "AA", "duplicate valve"))
+        );
+        assert_eq!(
+            parse_valves("Valve BB has flow rate=13; tunnels lead to valves BB"),
+            Err(ParseError::new(1, 53, "", "missing valve AA"))
+        );
+        assert_eq!(
+            parse_valves(
+                "Valve AA has flow rate=0; tunnels lead to valves AA, BB
+Valve BB has flow rate=13; tunnels lead to valves AA, CC"
+            ),
+            Err(ParseError::new(2, 55, "CC", "no such valve"))
+        );
+    }
+
     #[test]
     fn test_get_current_flow_empty() {
         let input = "\
 Valve AA has flow rate=5; tunnels lead to valves BB
-Valve BB has flow rate=13; tunnels lead to valves CC";
+Valve BB has flow rate=13; tunnels lead to valves AA";
         let valves = parse_valves(input).unwrap();
         assert_eq!(get_current_flow(&BitSet::new(), &valves), 0);
     }
@@ -183,7 +304,7 @@ Valve BB has flow rate=13; tunnels lead to valves CC";
     fn test_get_current_flow_single() {
         let input = "\
 Valve AA has flow rate=5; tunnels lead to valves BB
-Valve BB has flow rate=13; tunnels lead to valves CC";
+Valve BB has flow rate=13; tunnels lead to valves AA";
         let valves = parse_valves(input).unwrap();
         assert_eq!(
             get_current_flow(&BitSet::from_bytes(&[0b10000000]), &valves),
@@ -232,6 +353,6 @@ Valve JJ has flow rate=21; tunnel leads to valve II";
 
     #[test]
     fn test_part_2() {
-        assert_eq!(part_2_with_search(SMALL_INPUT), 1707);
+        assert_eq!(part_2_with_search(SMALL_INPUT), Ok(1707));
     }
 }
@@ -85,8 +85,8 @@ impl PlayerState {
         valves: &[NormalizedValve],
         distances: &[Vec<u32>],
     ) -> Vec<PlayerState> {
-        match self {
-            &PlayerState::Wait { at, time_left } => {
+        match *self {
+            PlayerState::Wait { at, time_left } => {
                 if time_left != 0 {
                     return vec![self.clone()];
                 }
@@ -131,7 +131,7 @@ impl PlayerState {
                     results
                 }
             }
-            &PlayerState::Travel { to: at, time_left } => {
+            PlayerState::Travel { to: at, time_left } => {
                 if time_left == 0 {
                     vec![PlayerState::Open {
                         at,
@@ -141,12 +141,9 @@ impl PlayerState {
                     vec![self.clone()]
                 }
             }
-            &PlayerState::Open { at, time_left } => {
+            PlayerState::Open { at, time_left } => {
                 if time_left == 0 {
-                    vec![PlayerState::Wait {
-                        at: at,
-                        time_left: 0,
-                    }]
+                    vec![PlayerState::Wait { at, time_left: 0 }]
                 } else {
                     vec![self.clone()]
                 }
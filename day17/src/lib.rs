@@ -0,0 +1,456 @@
+use common::observe::Observer;
+use common::{ParseError, Solution};
+use grid::image::Palette;
+use grid::{Pos, SparseGrid};
+use std::cmp::max;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct Piece {
+    pos: Vec<Pos>,
+}
+
+impl Piece {
+    fn shift(&self, x: i64, y: i64) -> Self {
+        Piece {
+            pos: self.pos.iter().map(|&p| p + Pos::new(x, y)).collect(),
+        }
+    }
+
+    fn left(&self) -> Self {
+        self.shift(-1, 0)
+    }
+
+    fn right(&self) -> Self {
+        self.shift(1, 0)
+    }
+
+    fn down(&self) -> Self {
+        self.shift(0, -1)
+    }
+}
+
+fn horiz() -> Piece {
+    Piece {
+        pos: vec![
+            Pos::new(0, 0),
+            Pos::new(1, 0),
+            Pos::new(2, 0),
+            Pos::new(3, 0),
+        ],
+    }
+}
+
+fn plus() -> Piece {
+    Piece {
+        pos: vec![
+            Pos::new(1, 0),
+            Pos::new(0, 1),
+            Pos::new(1, 1),
+            Pos::new(2, 1),
+            Pos::new(1, 2),
+        ],
+    }
+}
+
+fn corner() -> Piece {
+    Piece {
+        pos: vec![
+            Pos::new(0, 0),
+            Pos::new(1, 0),
+            Pos::new(2, 0),
+            Pos::new(2, 1),
+            Pos::new(2, 2),
+        ],
+    }
+}
+fn vertical() -> Piece {
+    Piece {
+        pos: vec![
+            Pos::new(0, 0),
+            Pos::new(0, 1),
+            Pos::new(0, 2),
+            Pos::new(0, 3),
+        ],
+    }
+}
+
+fn square() -> Piece {
+    Piece {
+        pos: vec![
+            Pos { x: 0, y: 0 },
+            Pos { x: 1, y: 0 },
+            Pos { x: 0, y: 1 },
+            Pos { x: 1, y: 1 },
+        ],
+    }
+}
+
+const SKYLINE_DEPTH: i64 = 64;
+
+#[derive(Debug)]
+pub struct Stage {
+    filled: SparseGrid<()>,
+
+    // the highest y that has a filled piece.  -1 at the very beginning which simulates the floor.
+    top_y: i64,
+
+    // the highest filled y in each column.
+    column_tops: [i64; 7],
+}
+
+impl Stage {
+    fn new() -> Self {
+        Self {
+            filled: SparseGrid::new(),
+            top_y: -1,
+            column_tops: [-1; 7],
+        }
+    }
+
+    fn add(&mut self, piece: &Piece) {
+        self.filled.extend(piece.pos.iter().map(|&p| (p, ())));
+        self.top_y = max(self.top_y, piece.pos.iter().map(|p| p.y).max().unwrap_or(0));
+        for p in &piece.pos {
+            self.column_tops[p.x as usize] = max(self.column_tops[p.x as usize], p.y);
+        }
+    }
+
+    // The shape of the top of the stage, relative to top_y.  Used to detect
+    // when the simulation has fallen into a cycle.  A column the jets never
+    // blow rocks into only gets deeper, so depths are capped at SKYLINE_DEPTH,
+    // which is further than rocks ever fall past the top.
+    fn skyline(&self) -> [i64; 7] {
+        self.column_tops
+            .map(|y| (self.top_y - y).min(SKYLINE_DEPTH))
+    }
+
+    // How tall the tower of settled rocks is.
+    pub fn height(&self) -> i64 {
+        self.top_y + 1
+    }
+}
+
+// Draws the chamber as in the puzzle, top down, with # for settled rock.
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for y in (0..=self.top_y).rev() {
+            let row: String = (0..7)
+                .map(|x| {
+                    if self.filled.contains(Pos::new(x, y)) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            writeln!(f, "|{}|", row)?;
+        }
+        writeln!(f, "+-------+")
+    }
+}
+
+// Returns true if any block in the piece collides with the stage or its boundnaries.
+fn is_colliding(piece: &Piece, stage: &Stage) -> bool {
+    piece
+        .pos
+        .iter()
+        .any(|p| stage.filled.contains(*p) || p.x < 0 || p.y < 0 || p.x >= 7)
+}
+
+fn place_initial(p: &Piece, stage: &Stage) -> Piece {
+    p.shift(2, stage.top_y + 4)
+}
+
+// Reads the jet pattern, which must be a non-empty run of '<' and '>'.
+pub fn parse_jets(input: &str) -> Result<String, ParseError> {
+    let jets = input.trim();
+    if let Some(index) = jets.find(|ch| ch != '<' && ch != '>') {
+        let bad = &jets[index..][..jets[index..].chars().next().map_or(0, char::len_utf8)];
+        return Err(ParseError::at(input, bad, "expected '<' or '>'"));
+    }
+    if jets.is_empty() {
+        return Err(ParseError::at(
+            input,
+            &input[input.len()..],
+            "missing jet pattern",
+        ));
+    }
+    Ok(jets.to_string())
+}
+
+pub fn height_after_blocks_fall(jet_pattern_input: &str, max_stones: i64) -> i64 {
+    height_after_blocks_fall_with(jet_pattern_input, max_stones, &mut ())
+}
+
+// As height_after_blocks_fall, showing the observer the chamber after each
+// rock settles, numbered by rock.  Rocks skipped over in whole cycles aren't
+// shown, so the numbers jump once a cycle is found.
+pub fn height_after_blocks_fall_with(
+    jet_pattern_input: &str,
+    max_stones: i64,
+    observer: &mut impl Observer<Stage>,
+) -> i64 {
+    drop_rocks(jet_pattern_input, max_stones, true, observer).0
+}
+
+// The whole tower once the rocks have fallen, showing the observer the
+// chamber after each rock settles.  No cycles are skipped, so it's only
+// practical for a few thousand rocks.
+pub fn tower_with(
+    jet_pattern_input: &str,
+    max_stones: i64,
+    observer: &mut impl Observer<Stage>,
+) -> Stage {
+    drop_rocks(jet_pattern_input, max_stones, false, observer).1
+}
+
+// Gray rock in a dark chamber.
+pub fn palette() -> Palette {
+    Palette::new([20, 20, 30])
+        .with('#', [170, 170, 160])
+        .with('|', [90, 60, 40])
+        .with('-', [90, 60, 40])
+        .with('+', [90, 60, 40])
+}
+
+// Returns the height and the stage the rocks fell on.
+fn drop_rocks(
+    jet_pattern_input: &str,
+    max_stones: i64,
+    skip_cycles: bool,
+    observer: &mut impl Observer<Stage>,
+) -> (i64, Stage) {
+    // pieces will rotate among the following:
+    let pieces = [horiz(), plus(), corner(), vertical(), square()];
+    let mut piece_index = 0;
+
+    // the instructions, similarly, will rotate:
+    let jets: Vec<char> = jet_pattern_input.trim().chars().collect();
+    let mut jet_index = 0;
+
+    let mut stage = Stage::new();
+
+    // Once the same piece, jet and skyline come around again, the stage
+    // repeats itself: we can skip over whole cycles of stones at once.
+    let mut seen: HashMap<(usize, usize, [i64; 7]), (i64, i64)> = HashMap::new();
+    let mut skipped_height = 0;
+
+    let mut count = 0;
+    let mut piece = place_initial(&pieces[piece_index], &stage);
+
+    loop {
+        // Handle jet movement.
+        let jet = jets[jet_index];
+        jet_index = (jet_index + 1) % jets.len();
+        let mut blown = piece.clone();
+        if jet == '<' {
+            blown = blown.left();
+        } else if jet == '>' {
+            blown = blown.right();
+        }
+        if !is_colliding(&blown, &stage) {
+            piece = blown;
+        }
+
+        // Handle falling.
+        let fallen = piece.clone().down();
+        if is_colliding(&fallen, &stage) {
+            stage.add(&piece);
+            count += 1;
+            observer.observe(count as usize, &stage);
+
+            piece_index = (piece_index + 1) % pieces.len();
+            piece = place_initial(&pieces[piece_index], &stage);
+
+            if skip_cycles && skipped_height == 0 {
+                let key = (piece_index, jet_index, stage.skyline());
+                if let Some((prev_count, prev_top_y)) = seen.insert(key, (count, stage.top_y)) {
+                    let cycle_length = count - prev_count;
+                    let cycles = (max_stones - count) / cycle_length;
+                    skipped_height = cycles * (stage.top_y - prev_top_y);
+                    count += cycles * cycle_length;
+                }
+            }
+        } else {
+            piece = fallen;
+        }
+
+        if count >= max_stones {
+            break;
+        }
+    }
+
+    (stage.top_y + 1 + skipped_height, stage)
+}
+
+// How many rocks fall in each part.
+#[derive(Debug)]
+pub struct Day17 {
+    pub part1_rocks: i64,
+    pub part2_rocks: i64,
+}
+
+impl Default for Day17 {
+    fn default() -> Self {
+        Day17 {
+            part1_rocks: 2022,
+            part2_rocks: 1000000000000,
+        }
+    }
+}
+
+impl Solution for Day17 {
+    // The jet pattern.
+    type Input = String;
+    type Part1 = i64;
+    type Part2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_jets(input)?)
+    }
+
+    fn part1(&self, jets: &Self::Input) -> Result<i64, common::Error> {
+        Ok(height_after_blocks_fall(jets, self.part1_rocks))
+    }
+
+    fn part2(&self, jets: &Self::Input) -> Result<i64, common::Error> {
+        Ok(height_after_blocks_fall(jets, self.part2_rocks))
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        match name {
+            "part1-rocks" => self.part1_rocks = value.parse()?,
+            "part2-rocks" => self.part2_rocks = value.parse()?,
+            _ => return Err(common::unknown_param(name, &["part1-rocks", "part2-rocks"])),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_place_initial_empty() {
+        let p = place_initial(&horiz(), &Stage::new());
+        assert_eq!(
+            p.pos,
+            vec![
+                Pos::new(2, 3),
+                Pos::new(3, 3),
+                Pos::new(4, 3),
+                Pos::new(5, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_place_initial_after_horiz_on_floor() {
+        let mut stage = Stage::new();
+        stage.add(&horiz());
+        let p = place_initial(&plus(), &stage);
+        assert_eq!(
+            p.pos,
+            vec![
+                Pos::new(3, 4),
+                Pos::new(2, 5),
+                Pos::new(3, 5),
+                Pos::new(4, 5),
+                Pos::new(3, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_colliding() {
+        let stage = Stage::new();
+
+        let piece = horiz();
+        assert!(!is_colliding(&piece, &stage));
+
+        let piece = horiz().down();
+        assert!(is_colliding(&piece, &stage));
+
+        let piece = horiz().shift(3, 0);
+        assert!(!is_colliding(&piece, &stage));
+
+        let piece = horiz().shift(4, 0);
+        assert!(is_colliding(&piece, &stage));
+
+        let piece = horiz().shift(-1, 0);
+        assert!(is_colliding(&piece, &stage));
+    }
+
+    #[test]
+    fn test_parse_jets() {
+        assert_eq!(parse_jets("<<>\n"), Ok("<<>".to_string()));
+        assert_eq!(
+            parse_jets("<<>\n>x<"),
+            Err(ParseError::new(1, 4, "\n", "expected '<' or '>'"))
+        );
+        assert_eq!(
+            parse_jets("<>^<"),
+            Err(ParseError::new(1, 3, "^", "expected '<' or '>'"))
+        );
+        assert_eq!(
+            parse_jets("\n"),
+            Err(ParseError::new(2, 1, "", "missing jet pattern"))
+        );
+    }
+
+    #[test]
+    fn test_observed_rocks() {
+        use common::observe::{Counter, Inspect, Invariant};
+
+        let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        let mut frames = Vec::new();
+        let mut record = Inspect(|_, stage: &Stage| frames.push(stage.to_string()));
+        let mut growing = Invariant::new("at most four rows per rock", {
+            let mut height = 0;
+            move |stage: &Stage| {
+                let grew = stage.height() - height;
+                height = stage.height();
+                (0..=4).contains(&grew)
+            }
+        });
+        let mut counter = Counter::default();
+        height_after_blocks_fall_with(input, 3, &mut (&mut record, (&mut growing, &mut counter)));
+        assert_eq!(counter.steps, 3);
+        assert_eq!(
+            frames[2],
+            "|..#....|\n|..#....|\n|####...|\n|..###..|\n|...#...|\n|..####.|\n+-------+\n"
+        );
+    }
+
+    #[test]
+    fn test_tower() {
+        let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        assert_eq!(tower_with(input, 2022, &mut ()).height(), 3068);
+    }
+
+    #[test]
+    fn test_part_1() {
+        let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        assert_eq!(height_after_blocks_fall(input, 2022), 3068);
+    }
+
+    #[test]
+    fn test_part_2() {
+        let input = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>";
+        assert_eq!(
+            height_after_blocks_fall(input, 1000000000000),
+            1514285714288
+        );
+    }
+
+    #[test]
+    fn test_one_way_jets() {
+        // Nothing is ever blown into the right-hand columns.
+        let tower = tower_with("<", 2022, &mut ());
+        assert_eq!(height_after_blocks_fall("<", 2022), tower.height());
+        assert!(height_after_blocks_fall("<", 1000000000000) > 0);
+    }
+}
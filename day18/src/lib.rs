@@ -0,0 +1,270 @@
+use common::{parallel, ParseError, Solution};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Pos {
+    x: i32,
+    y: i32,
+    z: i32,
+}
+
+impl Pos {
+    fn new(x: i32, y: i32, z: i32) -> Self {
+        Pos { x, y, z }
+    }
+
+    fn faces(&self) -> Vec<Pos> {
+        let mut result = Vec::new();
+        for i in [-1, 1] {
+            result.push(Pos::new(self.x + i, self.y, self.z));
+        }
+        for j in [-1, 1] {
+            result.push(Pos::new(self.x, self.y + j, self.z));
+        }
+        for k in [-1, 1] {
+            result.push(Pos::new(self.x, self.y, self.z + k));
+        }
+        result
+    }
+}
+
+pub fn surface_area_1(cubes: &[Pos]) -> usize {
+    let cubes_set = cubes.iter().copied().collect::<HashSet<Pos>>();
+
+    // The number of exposed faces are those that are facing empty space
+    // (not occupied by an existing cube).
+    cubes
+        .iter()
+        .flat_map(Pos::faces)
+        .filter(|c| !cubes_set.contains(c))
+        .count()
+}
+
+pub fn surface_area_2(cubes: &[Pos]) -> usize {
+    if cubes.is_empty() {
+        return 0;
+    }
+    let cubes_set = cubes.iter().copied().collect::<HashSet<Pos>>();
+
+    // The number of exposed faces are those that are facing empty space
+    // * not occupied by an existing cube
+    // * can reach the outside.
+    //
+    // Each thread floods with its own searcher.  A pocket of air either
+    // reaches the outside or it doesn't, so what one searcher has cached
+    // never changes another's answers.
+    let faces: Vec<Pos> = cubes
+        .iter()
+        .flat_map(Pos::faces)
+        .filter(|c| !cubes_set.contains(c))
+        .collect();
+    parallel::map_with(
+        &faces,
+        || FloodingBoundarySearch::new(&cubes_set),
+        |searcher, &c| searcher.can_reach_outside(c),
+    )
+    .into_iter()
+    .filter(|&outside| outside)
+    .count()
+}
+
+struct FloodingBoundarySearch<'a> {
+    cubes: &'a HashSet<Pos>,
+    cache: HashMap<Pos, bool>,
+    x_bounds: (i32, i32),
+    y_bounds: (i32, i32),
+    z_bounds: (i32, i32),
+}
+
+impl<'a> FloodingBoundarySearch<'a> {
+    fn new(cubes: &'a HashSet<Pos>) -> Self {
+        let x_bounds = (
+            cubes.iter().map(|c| c.x).min().unwrap(),
+            cubes.iter().map(|c| c.x).max().unwrap(),
+        );
+        let y_bounds = (
+            cubes.iter().map(|c| c.y).min().unwrap(),
+            cubes.iter().map(|c| c.y).max().unwrap(),
+        );
+        let z_bounds = (
+            cubes.iter().map(|c| c.z).min().unwrap(),
+            cubes.iter().map(|c| c.z).max().unwrap(),
+        );
+        Self {
+            cubes,
+            cache: HashMap::new(),
+            x_bounds,
+            y_bounds,
+            z_bounds,
+        }
+    }
+
+    fn can_reach_outside(&mut self, pos: Pos) -> bool {
+        let mut visited = HashSet::new();
+        let result = self.flood_search(pos, &mut visited);
+        for pos in visited {
+            self.cache.insert(pos, result);
+        }
+        result
+    }
+
+    fn flood_search(&mut self, pos: Pos, visited: &mut HashSet<Pos>) -> bool {
+        let mut queue = vec![pos];
+
+        while let Some(pos) = queue.pop() {
+            visited.insert(pos);
+
+            // Check the cache
+            if let Some(answer) = self.cache.get(&pos) {
+                return *answer;
+            }
+
+            // Check the boundaries
+            if pos.x < self.x_bounds.0
+                || pos.x > self.x_bounds.1
+                || pos.y < self.y_bounds.0
+                || pos.y > self.y_bounds.1
+                || pos.z < self.z_bounds.0
+                || pos.z > self.z_bounds.1
+            {
+                return true;
+            }
+
+            // Finally, check our neighbors (filtering folks we've visited)
+            queue.extend(
+                pos.faces()
+                    .into_iter()
+                    .filter(|c| !self.cubes.contains(c))
+                    .filter(|p| !visited.contains(p)),
+            );
+        }
+
+        // If we exhaust all possibilities, return false.
+        false
+    }
+}
+
+fn parse_pos(l: &str) -> Result<Pos, ParseError> {
+    let vals = l
+        .split(',')
+        .map(|s| {
+            s.trim()
+                .parse::<i32>()
+                .map_err(|e| ParseError::at(l, s, format!("bad coordinate: {}", e)))
+        })
+        .collect::<Result<Vec<i32>, _>>()?;
+    match vals[..] {
+        [x, y, z] => Ok(Pos::new(x, y, z)),
+        _ => Err(ParseError::new(1, 1, l, "expected x,y,z")),
+    }
+}
+
+pub fn parse(s: &str) -> Result<Vec<Pos>, ParseError> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(lineindex, l)| parse_pos(l).map_err(|e| e.on_line(lineindex + 1)))
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Pos>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse(input)?)
+    }
+
+    fn part1(&self, cubes: &Self::Input) -> Result<usize, common::Error> {
+        Ok(surface_area_1(cubes))
+    }
+
+    fn part2(&self, cubes: &Self::Input) -> Result<usize, common::Error> {
+        Ok(surface_area_2(cubes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn surface_area_1_small_example() {
+        assert_eq!(
+            surface_area_1(&[Pos::new(1, 1, 1), Pos::new(2, 1, 1)]),
+            10
+        );
+    }
+
+    const SMALL_INPUT: &str = "\
+2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5
+";
+
+    #[test]
+    fn test_parse() {
+        let positions = parse(SMALL_INPUT).unwrap();
+        assert_eq!(
+            positions,
+            vec![
+                Pos::new(2, 2, 2),
+                Pos::new(1, 2, 2),
+                Pos::new(3, 2, 2),
+                Pos::new(2, 1, 2),
+                Pos::new(2, 3, 2),
+                Pos::new(2, 2, 1),
+                Pos::new(2, 2, 3),
+                Pos::new(2, 2, 4),
+                Pos::new(2, 2, 6),
+                Pos::new(1, 2, 5),
+                Pos::new(3, 2, 5),
+                Pos::new(2, 1, 5),
+                Pos::new(2, 3, 5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("2,2,2\n1,x,2"),
+            Err(ParseError::new(
+                2,
+                3,
+                "x",
+                "bad coordinate: invalid digit found in string"
+            ))
+        );
+        assert_eq!(
+            parse("2,2,2\n\n1,2"),
+            Err(ParseError::new(3, 1, "1,2", "expected x,y,z"))
+        );
+    }
+
+    #[test]
+    fn surface_area_1_small_input() {
+        let positions = parse(SMALL_INPUT).unwrap();
+        assert_eq!(surface_area_1(&positions), 64);
+    }
+
+    #[test]
+    fn surface_area_2_small_input() {
+        let positions = parse(SMALL_INPUT).unwrap();
+        assert_eq!(surface_area_2(&positions), 58);
+    }
+}
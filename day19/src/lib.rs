@@ -0,0 +1,427 @@
+use common::{parallel, ParseError, Solution};
+use regex::Regex;
+use std::sync::OnceLock;
+
+#[derive(Debug, PartialEq, Eq, Default, Clone, Copy, Hash)]
+pub struct Currency {
+    pub ore: u32,
+    pub clay: u32,
+    pub obsidian: u32,
+    pub geode: u32,
+}
+
+impl Currency {
+    fn div(&self, other: Currency) -> u32 {
+        [
+            self.ore.checked_div(other.ore).unwrap_or(u32::MAX),
+            self.clay.checked_div(other.clay).unwrap_or(u32::MAX),
+            self.obsidian
+                .checked_div(other.obsidian)
+                .unwrap_or(u32::MAX),
+            self.geode.checked_div(other.geode).unwrap_or(u32::MAX),
+        ]
+        .into_iter()
+        .min()
+        .unwrap_or(0)
+    }
+
+    fn sub(&self, other: Currency) -> Currency {
+        Currency {
+            ore: self.ore - other.ore,
+            clay: self.clay - other.clay,
+            obsidian: self.obsidian - other.obsidian,
+            geode: self.geode - other.geode,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Blueprint {
+    pub ore: Currency,
+    pub clay: Currency,
+    pub obsidian: Currency,
+    pub geode: Currency,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+struct State {
+    purse: Currency,
+    time_left: u32,
+
+    ore_robots: u32,
+    clay_robots: u32,
+    obsidian_robots: u32,
+    geode_robots: u32,
+}
+
+impl State {
+    fn new(time_left: u32) -> Self {
+        State {
+            purse: Currency::default(),
+            time_left,
+            ore_robots: 1,
+            clay_robots: 0,
+            obsidian_robots: 0,
+            geode_robots: 0,
+        }
+    }
+}
+
+fn get_neighbors(state: &State, blueprint: &Blueprint) -> Vec<State> {
+    // The factory builds at most one robot per minute, so we can either wait
+    // or buy a single robot that we can afford.
+    let mut neighbors: Vec<State> = vec![*state];
+
+    if state.purse.div(blueprint.geode) > 0 {
+        neighbors.push(State {
+            purse: state.purse.sub(blueprint.geode),
+            geode_robots: state.geode_robots + 1,
+            ..*state
+        });
+    }
+
+    // There's no point in having more robots of a kind than we can spend
+    // of its resource in a single minute.
+    if state.purse.div(blueprint.obsidian) > 0 && state.obsidian_robots < blueprint.geode.obsidian {
+        neighbors.push(State {
+            purse: state.purse.sub(blueprint.obsidian),
+            obsidian_robots: state.obsidian_robots + 1,
+            ..*state
+        });
+    }
+
+    if state.purse.div(blueprint.clay) > 0 && state.clay_robots < blueprint.obsidian.clay {
+        neighbors.push(State {
+            purse: state.purse.sub(blueprint.clay),
+            clay_robots: state.clay_robots + 1,
+            ..*state
+        });
+    }
+
+    let max_ore_cost = [
+        blueprint.ore,
+        blueprint.clay,
+        blueprint.obsidian,
+        blueprint.geode,
+    ]
+    .iter()
+    .map(|cost| cost.ore)
+    .max()
+    .unwrap_or(0);
+    if state.purse.div(blueprint.ore) > 0 && state.ore_robots < max_ore_cost {
+        neighbors.push(State {
+            purse: state.purse.sub(blueprint.ore),
+            ore_robots: state.ore_robots + 1,
+            ..*state
+        });
+    }
+
+    // Now harvest, with the robots we had before buying.
+    for neighbors in neighbors.iter_mut() {
+        neighbors.purse.ore += state.ore_robots;
+        neighbors.purse.clay += state.clay_robots;
+        neighbors.purse.obsidian += state.obsidian_robots;
+        neighbors.purse.geode += state.geode_robots;
+
+        neighbors.time_left -= 1;
+    }
+
+    neighbors
+}
+
+// Compute the quality of a blueprint, optimizing number of geodes.
+pub fn optimize_geodes(blueprint: &Blueprint) -> u32 {
+    max_geodes(blueprint, 24)
+}
+
+// Computes the most geodes that can be opened with the blueprint in the
+// given number of minutes.  Depth-first, pruning states whose optimistic
+// estimate can't beat the best so far.
+pub fn max_geodes(blueprint: &Blueprint, minutes: u32) -> u32 {
+    let mut best = 0;
+    let mut stack = vec![State::new(minutes)];
+
+    while let Some(state) = stack.pop() {
+        if state.time_left <= 1 {
+            let result = state.purse.geode + state.geode_robots * state.time_left;
+            if result > best {
+                best = result;
+            }
+            continue;
+        }
+
+        if estimate(&state, blueprint) <= best {
+            continue;
+        }
+
+        stack.extend(get_neighbors(&state, blueprint));
+    }
+
+    best
+}
+
+// An optimistic bound on the geodes reachable from the state: pretend ore
+// is free and that the factory can build one of every robot each minute.
+fn estimate(state: &State, blueprint: &Blueprint) -> u32 {
+    let mut purse = state.purse;
+    let (mut clay_robots, mut obsidian_robots, mut geode_robots) =
+        (state.clay_robots, state.obsidian_robots, state.geode_robots);
+
+    for _ in 0..state.time_left {
+        let build_geode = purse.obsidian >= blueprint.geode.obsidian;
+        let build_obsidian = purse.clay >= blueprint.obsidian.clay;
+        if build_geode {
+            purse.obsidian -= blueprint.geode.obsidian;
+        }
+        if build_obsidian {
+            purse.clay -= blueprint.obsidian.clay;
+        }
+
+        purse.clay += clay_robots;
+        purse.obsidian += obsidian_robots;
+        purse.geode += geode_robots;
+
+        clay_robots += 1;
+        if build_obsidian {
+            obsidian_robots += 1;
+        }
+        if build_geode {
+            geode_robots += 1;
+        }
+    }
+
+    purse.geode
+}
+
+// Parses one blueprint, without its leading "Blueprint" word, into its id
+// and robot costs.  A blueprint may be spread across several lines.
+fn parse_blueprint(s: &str) -> Result<(u32, Blueprint), ParseError> {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(
+            r"^(\d+):\s+Each ore robot costs (\d+) ore\.\s+Each clay robot costs (\d+) ore\.\s+Each obsidian robot costs (\d+) ore and (\d+) clay\.\s+Each geode robot costs (\d+) ore and (\d+) obsidian\.$",
+        )
+        .unwrap()
+    });
+    let caps = pattern.captures(s).ok_or_else(|| {
+        let first_line = s.lines().next().unwrap_or(s);
+        ParseError::at(s, first_line, "could not parse blueprint")
+    })?;
+    let [id, ore_ore, clay_ore, obsidian_ore, obsidian_clay, geode_ore, geode_obsidian] =
+        [1, 2, 3, 4, 5, 6, 7].map(|i| caps.get(i).unwrap().as_str());
+    let number = |n: &str| {
+        n.parse::<u32>()
+            .map_err(|e| ParseError::at(s, n, format!("bad number: {}", e)))
+    };
+
+    Ok((
+        number(id)?,
+        Blueprint {
+            ore: Currency {
+                ore: number(ore_ore)?,
+                ..Currency::default()
+            },
+            clay: Currency {
+                ore: number(clay_ore)?,
+                ..Currency::default()
+            },
+            obsidian: Currency {
+                ore: number(obsidian_ore)?,
+                clay: number(obsidian_clay)?,
+                ..Currency::default()
+            },
+            geode: Currency {
+                ore: number(geode_ore)?,
+                obsidian: number(geode_obsidian)?,
+                ..Currency::default()
+            },
+        },
+    ))
+}
+
+// Parses every blueprint in the input, paired with its id.
+pub fn parse_blueprints(s: &str) -> Result<Vec<(u32, Blueprint)>, ParseError> {
+    s.split("Blueprint")
+        .map(str::trim)
+        .filter(|chunk| !chunk.is_empty())
+        .map(|chunk| parse_blueprint(chunk).map_err(|e| e.within(s, chunk)))
+        .collect()
+}
+
+// Sums each blueprint's quality level: its id times the geodes it can open
+// in the minutes given.
+pub fn quality_level_sum(blueprints: &[(u32, Blueprint)], minutes: u32) -> u32 {
+    parallel::map(blueprints, |(id, blueprint)| {
+        id * max_geodes(blueprint, minutes)
+    })
+    .into_iter()
+    .sum()
+}
+
+// Minutes to open geodes in, before and after the elephants.
+#[derive(Debug)]
+pub struct Day19 {
+    pub part1_minutes: u32,
+    pub part2_minutes: u32,
+}
+
+impl Default for Day19 {
+    fn default() -> Self {
+        Day19 {
+            part1_minutes: 24,
+            part2_minutes: 32,
+        }
+    }
+}
+
+impl Solution for Day19 {
+    type Input = Vec<(u32, Blueprint)>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_blueprints(input)?)
+    }
+
+    fn part1(&self, blueprints: &Self::Input) -> Result<u32, common::Error> {
+        Ok(quality_level_sum(blueprints, self.part1_minutes))
+    }
+
+    // The elephants ate all but the first three blueprints.
+    fn part2(&self, blueprints: &Self::Input) -> Result<u32, common::Error> {
+        let first = &blueprints[..blueprints.len().min(3)];
+        Ok(parallel::map(first, |(_, blueprint)| {
+            max_geodes(blueprint, self.part2_minutes)
+        })
+        .into_iter()
+        .product())
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        match name {
+            "part1-minutes" => self.part1_minutes = value.parse()?,
+            "part2-minutes" => self.part2_minutes = value.parse()?,
+            _ => {
+                return Err(common::unknown_param(
+                    name,
+                    &["part1-minutes", "part2-minutes"],
+                ))
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn optimize_geodes_small_example() {
+        let b = Blueprint {
+            ore: Currency {
+                ore: 4,
+                ..Currency::default()
+            },
+            clay: Currency {
+                ore: 2,
+                ..Currency::default()
+            },
+            obsidian: Currency {
+                ore: 3,
+                clay: 14,
+                ..Currency::default()
+            },
+            geode: Currency {
+                ore: 2,
+                obsidian: 7,
+                ..Currency::default()
+            },
+        };
+        assert_eq!(optimize_geodes(&b), 9);
+    }
+
+    #[test]
+    fn optimize_geodes_second_example() {
+        let b = Blueprint {
+            ore: Currency {
+                ore: 2,
+                ..Currency::default()
+            },
+            clay: Currency {
+                ore: 3,
+                ..Currency::default()
+            },
+            obsidian: Currency {
+                ore: 3,
+                clay: 8,
+                ..Currency::default()
+            },
+            geode: Currency {
+                ore: 3,
+                obsidian: 12,
+                ..Currency::default()
+            },
+        };
+        assert_eq!(optimize_geodes(&b), 12);
+        assert_eq!(max_geodes(&b, 32), 62);
+    }
+
+    const EXAMPLE: &str = "
+Blueprint 1:
+  Each ore robot costs 4 ore.
+  Each clay robot costs 2 ore.
+  Each obsidian robot costs 3 ore and 14 clay.
+  Each geode robot costs 2 ore and 7 obsidian.
+
+Blueprint 2:
+  Each ore robot costs 2 ore.
+  Each clay robot costs 3 ore.
+  Each obsidian robot costs 3 ore and 8 clay.
+  Each geode robot costs 3 ore and 12 obsidian.
+";
+
+    #[test]
+    fn test_parse_blueprints() {
+        let blueprints = parse_blueprints(EXAMPLE).unwrap();
+        assert_eq!(blueprints.len(), 2);
+        assert_eq!(blueprints[1].0, 2);
+        assert_eq!(
+            blueprints[1].1.obsidian,
+            Currency {
+                ore: 3,
+                clay: 8,
+                ..Currency::default()
+            }
+        );
+
+        let one_line = "Blueprint 7: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.";
+        assert_eq!(parse_blueprints(one_line).unwrap()[0].0, 7);
+
+        assert!(parse_blueprints("Blueprint 1: Each ore robot costs lots.").is_err());
+        assert!(parse_blueprints("nonsense").is_err());
+        assert_eq!(
+            parse_blueprints(
+                EXAMPLE
+                    .replace("costs 3 ore and 8", "costs 3 ore and 99999999999")
+                    .as_str()
+            ),
+            Err(ParseError::new(
+                11,
+                39,
+                "99999999999",
+                "bad number: number too large to fit in target type"
+            ))
+        );
+        assert_eq!(
+            parse_blueprints("Blueprint 1:\n  Each ore robot costs lots."),
+            Err(ParseError::new(1, 11, "1:", "could not parse blueprint"))
+        );
+    }
+
+    #[test]
+    fn test_quality_level_sum() {
+        let blueprints = parse_blueprints(EXAMPLE).unwrap();
+        assert_eq!(quality_level_sum(&blueprints, 24), 33);
+    }
+}
@@ -0,0 +1,252 @@
+// What the columns of a strategy guide mean, read from a small config:
+//
+//     # The puzzle's part 2.
+//     shapes: Rock Paper Scissors
+//     lhs: A=Rock B=Paper C=Scissors
+//     rhs: X=Loss Y=Draw Z=Win
+//
+// shapes, which defaults to rock paper scissors, lists the game's shapes
+// in order round the circle; see game.  scores and points can change what
+// they're worth, e.g. "scores: 1 2 3" and "points: loss=0 draw=3 win=6".
+// Each symbol in the left column is a shape the opponent plays.  One in the
+// right column is either the shape to play, or the outcome to aim for.
+
+use crate::game::{Game, Points};
+use crate::Outcome;
+use std::str::FromStr;
+
+pub const PART_1: &str = "lhs: A=Rock B=Paper C=Scissors\nrhs: X=Rock Y=Paper Z=Scissors\n";
+pub const PART_2: &str = "lhs: A=Rock B=Paper C=Scissors\nrhs: X=Loss Y=Draw Z=Win\n";
+
+/// What a symbol in the right column says to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Play {
+    Shape(usize),
+    Outcome(Outcome),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Encoding {
+    game: Game,
+    // Symbols in the order they were listed, for error messages.
+    lhs: Vec<(String, usize)>,
+    rhs: Vec<(String, Play)>,
+}
+
+impl Encoding {
+    pub fn part_1() -> Self {
+        PART_1.parse().expect("part 1's encoding")
+    }
+
+    pub fn part_2() -> Self {
+        PART_2.parse().expect("part 2's encoding")
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn lhs(&self, symbol: &str) -> Option<usize> {
+        lookup(&self.lhs, symbol)
+    }
+
+    pub fn rhs(&self, symbol: &str) -> Option<Play> {
+        lookup(&self.rhs, symbol)
+    }
+
+    /// What the left column may hold, e.g. "expected A, B or C".
+    pub fn expected_lhs(&self) -> String {
+        expected(&self.lhs)
+    }
+
+    pub fn expected_rhs(&self) -> String {
+        expected(&self.rhs)
+    }
+
+    /// The shape to play against `lhs`.
+    pub fn shape(&self, lhs: usize, play: Play) -> usize {
+        match play {
+            Play::Shape(shape) => shape,
+            Play::Outcome(outcome) => self.game.force_rhs_outcome(lhs, outcome),
+        }
+    }
+
+    /// The points for doing what `play` says against `lhs`.
+    pub fn score_round(&self, lhs: usize, play: Play) -> u32 {
+        self.game.score_round(lhs, self.shape(lhs, play))
+    }
+}
+
+fn lookup<K: AsRef<str>, T: Copy>(symbols: &[(K, T)], symbol: &str) -> Option<T> {
+    symbols
+        .iter()
+        .find(|(other, _)| other.as_ref() == symbol)
+        .map(|&(_, value)| value)
+}
+
+fn expected<T>(symbols: &[(String, T)]) -> String {
+    let symbols: Vec<&str> = symbols.iter().map(|(symbol, _)| symbol.as_str()).collect();
+    match symbols.split_last() {
+        None => String::from("expected nothing"),
+        Some((last, [])) => format!("expected {}", last),
+        Some((last, rest)) => format!("expected {} or {}", rest.join(", "), last),
+    }
+}
+
+fn parse_outcome(name: &str) -> Option<Outcome> {
+    match name.to_ascii_lowercase().as_str() {
+        "win" => Some(Outcome::Win),
+        "draw" => Some(Outcome::Draw),
+        "loss" | "lose" => Some(Outcome::Loss),
+        _ => None,
+    }
+}
+
+// Reads "SYMBOL=VALUE SYMBOL=VALUE ..." pairs.
+fn parse_pairs<T>(
+    value: &str,
+    parse: impl Fn(&str) -> Option<T>,
+    what: &str,
+) -> Result<Vec<(String, T)>, String> {
+    let mut pairs: Vec<(String, T)> = Vec::new();
+    for pair in value.split_whitespace() {
+        let (symbol, name) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("expected SYMBOL=VALUE, not {:?}", pair))?;
+        if symbol.is_empty() || pairs.iter().any(|(other, _)| other == symbol) {
+            return Err(format!("bad or repeated symbol in {:?}", pair));
+        }
+        let value = parse(name).ok_or_else(|| format!("{:?} is not {}", name, what))?;
+        pairs.push((symbol.to_string(), value));
+    }
+    Ok(pairs)
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let mut settings: Vec<(&str, &str)> = Vec::new();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once(':')
+                .ok_or_else(|| format!("expected KEY: VALUE, not {:?}", line))?;
+            let key = key.trim();
+            if !["shapes", "scores", "points", "lhs", "rhs"].contains(&key) {
+                return Err(format!("unknown setting {:?}", key));
+            }
+            if settings.iter().any(|&(other, _)| other == key) {
+                return Err(format!("{} is set twice", key));
+            }
+            settings.push((key, value));
+        }
+        let setting = |key| lookup(&settings, key).map(str::trim);
+
+        let mut game = match setting("shapes") {
+            Some(shapes) => Game::new(&shapes.split_whitespace().collect::<Vec<_>>())?,
+            None => Game::rock_paper_scissors(),
+        };
+        if let Some(scores) = setting("scores") {
+            let scores = scores
+                .split_whitespace()
+                .map(|score| {
+                    score
+                        .parse()
+                        .map_err(|e| format!("bad score {:?}: {}", score, e))
+                })
+                .collect::<Result<Vec<u32>, String>>()?;
+            game = game.with_scores(&scores)?;
+        }
+        if let Some(points) = setting("points") {
+            let points = parse_pairs(points, |value| value.parse::<u32>().ok(), "a number")?;
+            let mut all = Points::default();
+            for (outcome, value) in points {
+                match parse_outcome(&outcome) {
+                    Some(Outcome::Win) => all.win = value,
+                    Some(Outcome::Draw) => all.draw = value,
+                    Some(Outcome::Loss) => all.loss = value,
+                    None => return Err(format!("{:?} is not an outcome", outcome)),
+                }
+            }
+            game = game.with_points(all);
+        }
+
+        let lhs = parse_pairs(
+            setting("lhs").ok_or("lhs is missing")?,
+            |name| game.shape(name),
+            "a shape",
+        )?;
+        let rhs = parse_pairs(
+            setting("rhs").ok_or("rhs is missing")?,
+            |name| match game.shape(name) {
+                Some(shape) => Some(Play::Shape(shape)),
+                None => parse_outcome(name).map(Play::Outcome),
+            },
+            "a shape or an outcome",
+        )?;
+        Ok(Encoding { game, lhs, rhs })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let part_1 = Encoding::part_1();
+        assert_eq!(part_1.lhs("B"), Some(1));
+        assert_eq!(part_1.rhs("Z"), Some(Play::Shape(2)));
+        assert_eq!(part_1.lhs("X"), None);
+        assert_eq!(part_1.expected_lhs(), "expected A, B or C");
+
+        let part_2 = Encoding::part_2();
+        assert_eq!(part_2.rhs("X"), Some(Play::Outcome(Outcome::Loss)));
+        // Scissors beats paper.
+        assert_eq!(part_2.score_round(1, Play::Outcome(Outcome::Win)), 9);
+    }
+
+    #[test]
+    fn test_parse_dialect() {
+        let encoding: Encoding = "
+            # Rock paper scissors lizard Spock, written with digits.
+            shapes: Rock Spock Paper Lizard Scissors
+            scores: 5 4 3 2 1
+            points: win=10 loss=1
+            lhs: 1=rock 2=spock 3=paper 4=lizard 5=scissors
+            rhs: r=rock w=win d=draw
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(encoding.game().len(), 5);
+        assert_eq!(encoding.lhs("4"), Some(3));
+        assert_eq!(encoding.rhs("w"), Some(Play::Outcome(Outcome::Win)));
+        // Rock blunts scissors.
+        assert_eq!(encoding.score_round(4, Play::Shape(0)), 15);
+        // A draw with paper plays paper, which scores 3 here.
+        assert_eq!(encoding.score_round(2, Play::Outcome(Outcome::Draw)), 6);
+        // Spock and paper both beat rock, and Spock scores more.
+        assert_eq!(encoding.shape(0, Play::Outcome(Outcome::Win)), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!("rhs: X=Rock".parse::<Encoding>().is_err());
+        assert!("lhs: A=Rock\nrhs: X=Rock\nrhs: Y=Paper"
+            .parse::<Encoding>()
+            .is_err());
+        assert!("lhs: A=Stone\nrhs: X=Rock".parse::<Encoding>().is_err());
+        assert!("lhs: A=Rock A=Paper\nrhs: X=Rock"
+            .parse::<Encoding>()
+            .is_err());
+        assert!("lhs: A=Rock\nrhs: X=Rock\ncolor: red"
+            .parse::<Encoding>()
+            .is_err());
+        assert!("points: tie=1\nlhs: A=Rock\nrhs: X=Rock"
+            .parse::<Encoding>()
+            .is_err());
+    }
+}
@@ -0,0 +1,228 @@
+// Games like rock paper scissors, with any odd number of shapes from three
+// up in a circle.  Each shape beats the half of the others just before it in
+// the circle, and loses to the half just after, so every shape beats as
+// many as it loses to.  Rock paper scissors is the three-shape game:
+//
+//     let game = Game::rock_paper_scissors();
+//     assert_eq!(game.score_round(0, 1), 8); // Paper covers rock.
+//
+// and rock paper scissors lizard Spock the five-shape one.
+
+use crate::Outcome;
+
+/// The points for how a round went, added to the score of the shape played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Points {
+    pub loss: u32,
+    pub draw: u32,
+    pub win: u32,
+}
+
+impl Default for Points {
+    fn default() -> Self {
+        Points {
+            loss: 0,
+            draw: 3,
+            win: 6,
+        }
+    }
+}
+
+/// The shapes, in the order that they go round the circle, and what
+/// they score.  Shapes are numbered from 0 in that order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    names: Vec<String>,
+    scores: Vec<u32>,
+    points: Points,
+}
+
+impl Game {
+    /// Shapes score 1, 2, 3 and so on in order, with the usual points for
+    /// each round.
+    pub fn new(names: &[&str]) -> Result<Self, String> {
+        if names.len() < 3 || names.len().is_multiple_of(2) {
+            return Err(format!(
+                "need an odd number of shapes, at least three, not {}",
+                names.len()
+            ));
+        }
+        if let Some((i, name)) = names
+            .iter()
+            .enumerate()
+            .find(|&(i, name)| names[..i].contains(name))
+        {
+            return Err(format!("shape {} is named {:?} already", i, name));
+        }
+        Ok(Game {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            scores: (1..=names.len() as u32).collect(),
+            points: Points::default(),
+        })
+    }
+
+    /// The puzzle's own game.
+    pub fn rock_paper_scissors() -> Self {
+        Game::new(&["Rock", "Paper", "Scissors"]).expect("three shapes")
+    }
+
+    /// Each shape here beats the two before it: scissors cuts paper and
+    /// decapitates lizard, lizard eats paper and poisons Spock, and so on.
+    pub fn rock_paper_scissors_lizard_spock() -> Self {
+        Game::new(&["Rock", "Spock", "Paper", "Lizard", "Scissors"]).expect("five shapes")
+    }
+
+    /// Replaces what each shape scores, in order.
+    pub fn with_scores(mut self, scores: &[u32]) -> Result<Self, String> {
+        if scores.len() != self.len() {
+            return Err(format!(
+                "need a score for each of {} shapes, not {}",
+                self.len(),
+                scores.len()
+            ));
+        }
+        self.scores = scores.to_vec();
+        Ok(self)
+    }
+
+    pub fn with_points(mut self, points: Points) -> Self {
+        self.points = points;
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn name(&self, shape: usize) -> Option<&str> {
+        self.names.get(shape).map(String::as_str)
+    }
+
+    /// The shape with the name, ignoring case.
+    pub fn shape(&self, name: &str) -> Option<usize> {
+        self.names
+            .iter()
+            .position(|other| other.eq_ignore_ascii_case(name))
+    }
+
+    pub fn score(&self, shape: usize) -> u32 {
+        self.scores[shape]
+    }
+
+    pub fn points(&self) -> Points {
+        self.points
+    }
+
+    /// How the round goes for `lhs`.
+    pub fn versus(&self, lhs: usize, rhs: usize) -> Outcome {
+        // How far round the circle rhs is from lhs.
+        let ahead = (rhs + self.len() - lhs) % self.len();
+        if ahead == 0 {
+            Outcome::Draw
+        } else if ahead <= self.len() / 2 {
+            Outcome::Loss
+        } else {
+            Outcome::Win
+        }
+    }
+
+    /// A shape that gets `outcome` against `lhs`: the highest scoring of
+    /// them, or the first in the circle after `lhs` if several tie.
+    pub fn force_rhs_outcome(&self, lhs: usize, outcome: Outcome) -> usize {
+        let n = self.len();
+        (1..=n)
+            .map(|ahead| (lhs + ahead) % n)
+            .filter(|&rhs| self.versus(rhs, lhs) == outcome)
+            .rev()
+            .max_by_key(|&rhs| self.scores[rhs])
+            .expect("there are shapes for every outcome")
+    }
+
+    /// The points for `rhs` in a round against `lhs`.
+    pub fn score_round(&self, lhs: usize, rhs: usize) -> u32 {
+        self.scores[rhs]
+            + match self.versus(rhs, lhs) {
+                Outcome::Win => self.points.win,
+                Outcome::Draw => self.points.draw,
+                Outcome::Loss => self.points.loss,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{score_round, Shape};
+
+    const SHAPES: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    #[test]
+    fn test_rock_paper_scissors_preset() {
+        let game = Game::rock_paper_scissors();
+        for lhs in SHAPES {
+            for rhs in SHAPES {
+                let (l, r) = (lhs as usize, rhs as usize);
+                assert_eq!(game.versus(l, r), lhs.versus(rhs));
+                assert_eq!(game.score_round(l, r), score_round(lhs, rhs));
+            }
+            for outcome in [Outcome::Win, Outcome::Draw, Outcome::Loss] {
+                assert_eq!(
+                    game.force_rhs_outcome(lhs as usize, outcome),
+                    lhs.force_rhs_outcome(outcome) as usize
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lizard_spock() {
+        let game = Game::rock_paper_scissors_lizard_spock();
+        let shape = |name| game.shape(name).unwrap();
+        let wins = [
+            ("scissors", "paper"),
+            ("paper", "rock"),
+            ("rock", "lizard"),
+            ("lizard", "spock"),
+            ("spock", "scissors"),
+            ("scissors", "lizard"),
+            ("lizard", "paper"),
+            ("paper", "spock"),
+            ("spock", "rock"),
+            ("rock", "scissors"),
+        ];
+        for (winner, loser) in wins {
+            assert_eq!(game.versus(shape(winner), shape(loser)), Outcome::Win);
+            assert_eq!(game.versus(shape(loser), shape(winner)), Outcome::Loss);
+        }
+        // Lizard and Scissors both beat Paper; Scissors scores more.
+        let rhs = game.force_rhs_outcome(shape("paper"), Outcome::Win);
+        assert_eq!(game.name(rhs), Some("Scissors"));
+    }
+
+    #[test]
+    fn test_custom_scores_and_points() {
+        let game = Game::rock_paper_scissors()
+            .with_scores(&[10, 20, 30])
+            .unwrap()
+            .with_points(Points {
+                loss: 1,
+                draw: 2,
+                win: 4,
+            });
+        assert_eq!(game.score_round(0, 1), 24);
+        assert_eq!(game.score_round(0, 0), 12);
+        assert_eq!(game.score_round(0, 2), 31);
+        assert!(game.with_scores(&[1, 2]).is_err());
+    }
+
+    #[test]
+    fn test_bad_games() {
+        assert!(Game::new(&["Rock", "Paper"]).is_err());
+        assert!(Game::new(&["Rock"]).is_err());
+        assert!(Game::new(&["Rock", "Paper", "Rock"]).is_err());
+    }
+}
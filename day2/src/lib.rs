@@ -0,0 +1,265 @@
+use common::{ParseError, Solution};
+use encoding::{Encoding, Play};
+use game::Game;
+use std::error::Error;
+use std::io::{BufRead, BufReader, Read};
+use std::sync::OnceLock;
+
+pub mod encoding;
+pub mod game;
+pub mod optimize;
+pub mod tournament;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Shape {
+    Rock = 0,
+    Paper = 1,
+    Scissors = 2,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+impl TryFrom<u32> for Shape {
+    type Error = String;
+
+    fn try_from(v: u32) -> Result<Shape, Self::Error> {
+        match v {
+            0 => Ok(Shape::Rock),
+            1 => Ok(Shape::Paper),
+            2 => Ok(Shape::Scissors),
+            _ => Err(format!("Could not convert {} into Shape", v)),
+        }
+    }
+}
+
+// The shapes are numbered as in the game.
+fn rock_paper_scissors() -> &'static Game {
+    static GAME: OnceLock<Game> = OnceLock::new();
+    GAME.get_or_init(Game::rock_paper_scissors)
+}
+
+impl Shape {
+    pub fn score(&self) -> u32 {
+        rock_paper_scissors().score(*self as usize)
+    }
+
+    pub fn versus(self, other: Self) -> Outcome {
+        rock_paper_scissors().versus(self as usize, other as usize)
+    }
+
+    pub fn force_rhs_outcome(self, outcome: Outcome) -> Shape {
+        let rhs = rock_paper_scissors().force_rhs_outcome(self as usize, outcome);
+        (rhs as u32).try_into().unwrap()
+    }
+}
+
+#[test]
+fn test_versus() {
+    assert_eq!(Shape::Rock.versus(Shape::Paper), Outcome::Loss);
+    assert_eq!(Shape::Rock.versus(Shape::Scissors), Outcome::Win);
+    assert_eq!(Shape::Rock.versus(Shape::Rock), Outcome::Draw);
+
+    assert_eq!(Shape::Paper.versus(Shape::Rock), Outcome::Win);
+    assert_eq!(Shape::Paper.versus(Shape::Scissors), Outcome::Loss);
+    assert_eq!(Shape::Paper.versus(Shape::Paper), Outcome::Draw);
+
+    assert_eq!(Shape::Scissors.versus(Shape::Rock), Outcome::Loss);
+    assert_eq!(Shape::Scissors.versus(Shape::Paper), Outcome::Win);
+    assert_eq!(Shape::Scissors.versus(Shape::Scissors), Outcome::Draw);
+}
+
+#[test]
+fn test_force_outcome() {
+    assert_eq!(Shape::Rock.force_rhs_outcome(Outcome::Win), Shape::Paper);
+    assert_eq!(
+        Shape::Rock.force_rhs_outcome(Outcome::Loss),
+        Shape::Scissors
+    );
+
+    assert_eq!(
+        Shape::Paper.force_rhs_outcome(Outcome::Win),
+        Shape::Scissors
+    );
+    assert_eq!(Shape::Paper.force_rhs_outcome(Outcome::Loss), Shape::Rock);
+
+    assert_eq!(Shape::Scissors.force_rhs_outcome(Outcome::Win), Shape::Rock);
+    assert_eq!(
+        Shape::Scissors.force_rhs_outcome(Outcome::Loss),
+        Shape::Paper
+    );
+}
+
+pub fn score_round(lhs: Shape, rhs: Shape) -> u32 {
+    rock_paper_scissors().score_round(lhs as usize, rhs as usize)
+}
+
+// Reads the strategy guide, one round per line, with the columns meaning
+// what the encoding says.
+fn parse_rounds(
+    to_read: impl Read,
+    encoding: &Encoding,
+) -> Result<Vec<(usize, Play)>, Box<dyn Error>> {
+    let mut rounds = Vec::new();
+    for (lineindex, line) in BufReader::new(to_read).lines().enumerate() {
+        let line = line?;
+        let error = |fragment: &str, message: &str| {
+            ParseError::at(&line, fragment, message).on_line(lineindex + 1)
+        };
+        let mut moves = line.split_ascii_whitespace();
+        match (moves.next(), moves.next(), moves.next()) {
+            (None, _, _) => {}
+            (Some(lhs), Some(rhs), None) => {
+                let lhs = encoding
+                    .lhs(lhs)
+                    .ok_or_else(|| error(lhs, &encoding.expected_lhs()))?;
+                let rhs = encoding
+                    .rhs(rhs)
+                    .ok_or_else(|| error(rhs, &encoding.expected_rhs()))?;
+                rounds.push((lhs, rhs));
+            }
+            _ => return Err(error(&line, "expected two columns").into()),
+        }
+    }
+    Ok(rounds)
+}
+
+/// Scores every round of the guide, read with the encoding.
+pub fn total_score(to_read: impl Read, encoding: &Encoding) -> Result<u32, Box<dyn Error>> {
+    let rounds = parse_rounds(to_read, encoding)?;
+    Ok(rounds
+        .into_iter()
+        .map(|(lhs, rhs)| encoding.score_round(lhs, rhs))
+        .sum())
+}
+
+/// Reads the guide's rounds as the opponent's shape and which of X, Y and
+/// Z, from 0 to 2, is in the second column; see optimize.
+pub fn read_guide(to_read: impl Read) -> Result<Vec<(Shape, usize)>, Box<dyn Error>> {
+    // Part 1 reads X, Y and Z as the shapes 0 to 2.
+    let encoding = Encoding::part_1();
+    parse_rounds(to_read, &encoding)?
+        .into_iter()
+        .map(|(lhs, rhs)| {
+            let lhs = Shape::try_from(lhs as u32)?;
+            Ok((lhs, encoding.shape(lhs as usize, rhs)))
+        })
+        .collect()
+}
+
+pub fn total_score_part_1(to_read: impl Read) -> Result<u32, Box<dyn Error>> {
+    total_score(to_read, &Encoding::part_1())
+}
+
+pub fn total_score_part_2(to_read: impl Read) -> Result<u32, Box<dyn Error>> {
+    total_score(to_read, &Encoding::part_2())
+}
+
+// Each part reads the guide with its own encoding, which can be replaced
+// with one read from a file, e.g. --part2 dialect.txt.
+#[derive(Debug)]
+pub struct Day2 {
+    pub part1: Encoding,
+    pub part2: Encoding,
+}
+
+impl Default for Day2 {
+    fn default() -> Self {
+        Day2 {
+            part1: Encoding::part_1(),
+            part2: Encoding::part_2(),
+        }
+    }
+}
+
+impl Solution for Day2 {
+    // The second column means something different in each part, so the
+    // strategy guide is kept as text and read by each part's encoding.
+    type Input = String;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<u32, common::Error> {
+        total_score(input.as_bytes(), &self.part1)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<u32, common::Error> {
+        total_score(input.as_bytes(), &self.part2)
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        let encoding = || -> Result<Encoding, common::Error> {
+            let config = std::fs::read_to_string(value).map_err(|e| format!("{}: {}", value, e))?;
+            Ok(config.parse().map_err(|e| format!("{}: {}", value, e))?)
+        };
+        match name {
+            "part1" => self.part1 = encoding()?,
+            "part2" => self.part2 = encoding()?,
+            _ => return Err(common::unknown_param(name, &["part1", "part2"])),
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_total_score_main_example() -> Result<(), Box<dyn Error>> {
+    assert_eq!(total_score_part_1(&b"A Y\nB X\nC Z"[..])?, 15);
+    Ok(())
+}
+
+#[test]
+fn test_total_score_part_2() -> Result<(), Box<dyn Error>> {
+    assert_eq!(total_score_part_2(&b"A Y\nB X\nC Z"[..])?, 12);
+    Ok(())
+}
+
+#[test]
+fn test_total_score_bad_input() {
+    let e = total_score_part_1(&b"A Y\nB W\n"[..]).unwrap_err();
+    let e = e.downcast_ref::<ParseError>().unwrap();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "W"));
+
+    let e = total_score_part_2(&b"A Y\nA\n"[..]).unwrap_err();
+    assert_eq!(e.downcast_ref::<ParseError>().unwrap().line, 2);
+
+    assert_eq!(total_score_part_1(&b"A Y\n\nB X\n"[..]).unwrap(), 9);
+}
+
+#[test]
+fn test_set_param() -> Result<(), Box<dyn Error>> {
+    let path = std::env::temp_dir().join(format!("day2-dialect-{}.txt", std::process::id()));
+    std::fs::write(
+        &path,
+        "lhs: R=Rock P=Paper S=Scissors\nrhs: L=Loss D=Draw W=Win\n",
+    )?;
+    let mut day = Day2::default();
+    day.set_param("part1", path.to_str().unwrap())?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(day.part1(&String::from("R D\nP L\nS W\n"))?, 12);
+    assert!(day.set_param("part1", "no-such-dialect.txt").is_err());
+    assert!(day.set_param("part3", "dialect.txt").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_read_guide() -> Result<(), Box<dyn Error>> {
+    let rounds = read_guide(&b"A Y\nB X\nC Z"[..])?;
+    assert_eq!(
+        rounds,
+        [(Shape::Rock, 1), (Shape::Paper, 0), (Shape::Scissors, 2)]
+    );
+    let best = optimize::totals(&rounds)[0];
+    assert_eq!(best.1, 24);
+    // The best mapping reads back as an encoding.
+    let encoding = format!("lhs: A=Rock B=Paper C=Scissors\nrhs: {}\n", best.0);
+    assert_eq!(total_score(&b"A Y\nB X\nC Z"[..], &encoding.parse()?)?, 24);
+    Ok(())
+}
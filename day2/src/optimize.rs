@@ -0,0 +1,173 @@
+// Working out what the guide's second column should mean.  X, Y and Z
+// could stand for the three shapes in any order, or for the three outcomes
+// in any order: twelve mappings in all.  These score a guide under every
+// one, both as written and against an opponent who plays at random.
+
+use crate::{score_round, Outcome, Shape};
+use std::cmp::Reverse;
+use std::fmt;
+
+const SHAPES: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+const OUTCOMES: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+pub const SYMBOLS: [char; 3] = ['X', 'Y', 'Z'];
+
+const PERMUTATIONS: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+/// What X, Y and Z mean, in that order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mapping {
+    Shapes([Shape; 3]),
+    Outcomes([Outcome; 3]),
+}
+
+impl Mapping {
+    /// Every mapping, the shapes first.  Part 1's is the first and part
+    /// 2's is the seventh.
+    pub fn all() -> Vec<Mapping> {
+        let shapes = PERMUTATIONS.map(|p| Mapping::Shapes(p.map(|i| SHAPES[i])));
+        let outcomes = PERMUTATIONS.map(|p| Mapping::Outcomes(p.map(|i| OUTCOMES[i])));
+        shapes.into_iter().chain(outcomes).collect()
+    }
+
+    /// The shape to play against `lhs` for the symbol, 0 for X to 2 for Z.
+    pub fn shape(&self, lhs: Shape, symbol: usize) -> Shape {
+        match self {
+            Mapping::Shapes(shapes) => shapes[symbol],
+            Mapping::Outcomes(outcomes) => lhs.force_rhs_outcome(outcomes[symbol]),
+        }
+    }
+
+    pub fn score(&self, lhs: Shape, symbol: usize) -> u32 {
+        score_round(lhs, self.shape(lhs, symbol))
+    }
+}
+
+// As an encoding's rhs setting, e.g. "X=Rock Y=Paper Z=Scissors".
+impl fmt::Display for Mapping {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, symbol) in SYMBOLS.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            match self {
+                Mapping::Shapes(shapes) => write!(f, "{}={:?}", symbol, shapes[i])?,
+                Mapping::Outcomes(outcomes) => write!(f, "{}={:?}", symbol, outcomes[i])?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The guide's total score under every mapping, best first; the last is
+/// the worst.  Mappings that tie stay in the order of `Mapping::all`.
+pub fn totals(rounds: &[(Shape, usize)]) -> Vec<(Mapping, u32)> {
+    let mut totals: Vec<(Mapping, u32)> = Mapping::all()
+        .into_iter()
+        .map(|mapping| {
+            let total = rounds
+                .iter()
+                .map(|&(lhs, symbol)| mapping.score(lhs, symbol))
+                .sum();
+            (mapping, total)
+        })
+        .collect();
+    totals.sort_by_key(|&(_, total)| Reverse(total));
+    totals
+}
+
+/// The total each mapping can expect when the opponent ignores the guide
+/// and plays rock, paper or scissors with the given weights, best first.
+/// None if the weights don't add up to something positive.
+pub fn expected_totals(
+    rounds: &[(Shape, usize)],
+    weights: [f64; 3],
+) -> Option<Vec<(Mapping, f64)>> {
+    let sum: f64 = weights.iter().sum();
+    if !(sum.is_finite() && sum > 0.0) || weights.iter().any(|&w| w < 0.0) {
+        return None;
+    }
+    let mut totals: Vec<(Mapping, f64)> = Mapping::all()
+        .into_iter()
+        .map(|mapping| {
+            let total = rounds
+                .iter()
+                .map(|&(_, symbol)| {
+                    SHAPES
+                        .iter()
+                        .zip(weights)
+                        .map(|(&lhs, weight)| weight / sum * mapping.score(lhs, symbol) as f64)
+                        .sum::<f64>()
+                })
+                .sum();
+            (mapping, total)
+        })
+        .collect();
+    totals.sort_by(|a, b| b.1.total_cmp(&a.1));
+    Some(totals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A Y, B X, C Z.
+    const EXAMPLE: [(Shape, usize); 3] =
+        [(Shape::Rock, 1), (Shape::Paper, 0), (Shape::Scissors, 2)];
+
+    fn total(totals: &[(Mapping, u32)], mapping: Mapping) -> Option<u32> {
+        totals
+            .iter()
+            .find(|&&(other, _)| other == mapping)
+            .map(|&(_, total)| total)
+    }
+
+    #[test]
+    fn test_all() {
+        let all = Mapping::all();
+        assert_eq!(all.len(), 12);
+        assert_eq!(all[0].to_string(), "X=Rock Y=Paper Z=Scissors");
+        assert_eq!(all[6].to_string(), "X=Loss Y=Draw Z=Win");
+    }
+
+    #[test]
+    fn test_totals() {
+        let all = Mapping::all();
+        let totals = totals(&EXAMPLE);
+        assert_eq!(totals.len(), 12);
+        // The puzzle's own readings.
+        assert_eq!(total(&totals, all[0]), Some(15));
+        assert_eq!(total(&totals, all[6]), Some(12));
+        assert!(totals.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        // Best is to win every round, and worst to lose every one.
+        let best = Mapping::Shapes([Shape::Scissors, Shape::Paper, Shape::Rock]);
+        let worst = Mapping::Shapes([Shape::Rock, Shape::Scissors, Shape::Paper]);
+        assert_eq!(totals[0], (best, 24));
+        assert_eq!(totals[11], (worst, 6));
+    }
+
+    #[test]
+    fn test_expected_totals() {
+        let even = expected_totals(&EXAMPLE, [1.0, 1.0, 1.0]).unwrap();
+        // Against an even opponent, each shape wins, draws and loses equally
+        // often, so a round is worth 3 more than the shape: 5, 4 and 6.
+        let shapes = even
+            .iter()
+            .find(|(mapping, _)| *mapping == Mapping::all()[0]);
+        assert!((shapes.unwrap().1 - 15.0).abs() < 1e-9);
+        assert!(expected_totals(&EXAMPLE, [0.0; 3]).is_none());
+        assert!(expected_totals(&EXAMPLE, [1.0, -1.0, 1.0]).is_none());
+
+        // An opponent who only plays rock is best met with paper.
+        let all_y = [(Shape::Rock, 1), (Shape::Paper, 1), (Shape::Scissors, 1)];
+        let rock = expected_totals(&all_y, [1.0, 0.0, 0.0]).unwrap();
+        assert!((rock[0].1 - 24.0).abs() < 1e-9);
+        assert_eq!(rock[0].0.shape(Shape::Rock, 1), Shape::Paper);
+    }
+}
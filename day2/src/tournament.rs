@@ -0,0 +1,246 @@
+// Rock paper scissors bots playing each other.  Every bot plays every
+// other over some number of rounds, scored as in the puzzle with
+// score_round, and the standings add up the points:
+//
+//     let bots: Vec<Box<dyn Strategy>> =
+//         vec![Box::new(Fixed(Shape::Rock)), Box::new(BeatLast)];
+//     println!("{}", round_robin(&bots, 100));
+
+use crate::{score_round, Outcome, Shape};
+use std::fmt;
+
+const SHAPES: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+pub trait Strategy {
+    fn name(&self) -> String;
+
+    /// The shape to play next, given the match so far as (ours, theirs)
+    /// for each round.
+    fn play(&self, history: &[(Shape, Shape)]) -> Shape;
+}
+
+fn beat(shape: Shape) -> Shape {
+    shape.force_rhs_outcome(Outcome::Win)
+}
+
+/// Always plays the same shape.
+#[derive(Debug, Clone, Copy)]
+pub struct Fixed(pub Shape);
+
+impl Strategy for Fixed {
+    fn name(&self) -> String {
+        format!("always {:?}", self.0)
+    }
+
+    fn play(&self, _history: &[(Shape, Shape)]) -> Shape {
+        self.0
+    }
+}
+
+/// Plays the shapes in turn, over and over, or rock if there are none.
+#[derive(Debug, Clone)]
+pub struct Cycle(pub Vec<Shape>);
+
+impl Strategy for Cycle {
+    fn name(&self) -> String {
+        let shapes: Vec<String> = self.0.iter().map(|shape| format!("{:?}", shape)).collect();
+        format!("cycle {}", shapes.join(" "))
+    }
+
+    fn play(&self, history: &[(Shape, Shape)]) -> Shape {
+        self.0
+            .get(history.len() % self.0.len().max(1))
+            .copied()
+            .unwrap_or(Shape::Rock)
+    }
+}
+
+/// Beats whatever the opponent has played most, or rock, the first in
+/// case of a tie.
+#[derive(Debug, Clone, Copy)]
+pub struct Frequency;
+
+impl Strategy for Frequency {
+    fn name(&self) -> String {
+        String::from("frequency")
+    }
+
+    fn play(&self, history: &[(Shape, Shape)]) -> Shape {
+        let counts = SHAPES.map(|shape| {
+            history
+                .iter()
+                .filter(|(_, theirs)| *theirs == shape)
+                .count()
+        });
+        let most = (0..3).rev().max_by_key(|&i| counts[i]).unwrap_or(0);
+        beat(SHAPES[most])
+    }
+}
+
+/// Beats whatever the opponent played last, starting with rock.
+#[derive(Debug, Clone, Copy)]
+pub struct BeatLast;
+
+impl Strategy for BeatLast {
+    fn name(&self) -> String {
+        String::from("beat last")
+    }
+
+    fn play(&self, history: &[(Shape, Shape)]) -> Shape {
+        history
+            .last()
+            .map_or(Shape::Rock, |&(_, theirs)| beat(theirs))
+    }
+}
+
+/// Plays `rounds` rounds, giving the shapes each bot played in each.
+pub fn play_match(a: &dyn Strategy, b: &dyn Strategy, rounds: usize) -> Vec<(Shape, Shape)> {
+    let mut history = Vec::with_capacity(rounds);
+    // The same rounds as b sees them.
+    let mut flipped = Vec::with_capacity(rounds);
+    for _ in 0..rounds {
+        let round = (a.play(&history), b.play(&flipped));
+        history.push(round);
+        flipped.push((round.1, round.0));
+    }
+    history
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub points: u32,
+    // Rounds, not matches.
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Standing {
+    fn add(&mut self, ours: Shape, theirs: Shape) {
+        self.points += score_round(theirs, ours);
+        match ours.versus(theirs) {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Loss => self.losses += 1,
+        }
+    }
+}
+
+/// The bots, most points first.  Bots with equal points stay in the
+/// order they were entered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standings(pub Vec<Standing>);
+
+impl fmt::Display for Standings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .map(|standing| standing.name.chars().count())
+            .chain(["bot".len()])
+            .max()
+            .unwrap_or(0);
+        writeln!(
+            f,
+            "rank  {:width$}  points  wins  draws  losses",
+            "bot",
+            width = width
+        )?;
+        for (i, standing) in self.0.iter().enumerate() {
+            writeln!(
+                f,
+                "{:>4}  {:width$}  {:>6}  {:>4}  {:>5}  {:>6}",
+                i + 1,
+                standing.name,
+                standing.points,
+                standing.wins,
+                standing.draws,
+                standing.losses,
+                width = width
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays every bot against every other, `rounds` rounds a match.
+pub fn round_robin(bots: &[Box<dyn Strategy>], rounds: usize) -> Standings {
+    let mut standings: Vec<Standing> = bots
+        .iter()
+        .map(|bot| Standing {
+            name: bot.name(),
+            points: 0,
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        })
+        .collect();
+    for a in 0..bots.len() {
+        for b in a + 1..bots.len() {
+            for (ours, theirs) in play_match(bots[a].as_ref(), bots[b].as_ref(), rounds) {
+                standings[a].add(ours, theirs);
+                standings[b].add(theirs, ours);
+            }
+        }
+    }
+    standings.sort_by_key(|standing| std::cmp::Reverse(standing.points));
+    Standings(standings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bots() {
+        let history = [
+            (Shape::Rock, Shape::Paper),
+            (Shape::Rock, Shape::Scissors),
+            (Shape::Rock, Shape::Paper),
+        ];
+        assert_eq!(Fixed(Shape::Paper).play(&history), Shape::Paper);
+        let cycle = Cycle(vec![Shape::Rock, Shape::Scissors]);
+        assert_eq!(cycle.play(&history), Shape::Scissors);
+        assert_eq!(cycle.play(&[]), Shape::Rock);
+        assert_eq!(Frequency.play(&history), Shape::Scissors);
+        assert_eq!(Frequency.play(&[]), Shape::Paper);
+        assert_eq!(BeatLast.play(&history), Shape::Scissors);
+        assert_eq!(BeatLast.play(&[]), Shape::Rock);
+    }
+
+    #[test]
+    fn test_play_match() {
+        let rounds = play_match(&Fixed(Shape::Rock), &BeatLast, 3);
+        assert_eq!(
+            rounds,
+            [
+                (Shape::Rock, Shape::Rock),
+                (Shape::Rock, Shape::Paper),
+                (Shape::Rock, Shape::Paper),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_robin() {
+        let bots: Vec<Box<dyn Strategy>> = vec![
+            Box::new(Fixed(Shape::Rock)),
+            Box::new(BeatLast),
+            Box::new(Cycle(vec![Shape::Scissors])),
+        ];
+        let standings = round_robin(&bots, 10);
+        let names: Vec<&str> = standings.0.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["beat last", "always Rock", "cycle Scissors"]);
+        // Each plays 10 rounds against each of the other two.
+        for standing in &standings.0 {
+            assert_eq!(standing.wins + standing.draws + standing.losses, 20);
+        }
+        // Rock draws once with beat last, then loses to paper, and always
+        // beats scissors: 1 * 4 + 9 * 1 + 10 * 7.
+        assert_eq!(standings.0[1].points, 83);
+        assert!(standings
+            .to_string()
+            .starts_with("rank  bot             points  wins  draws  losses\n"));
+    }
+}
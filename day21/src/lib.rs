@@ -1,5 +1,6 @@
 mod parser;
 
+use common::{ParseError, Solution};
 use std::collections::HashMap;
 
 use parser::{Expr, Job, Op};
@@ -10,18 +11,28 @@ pub struct JobList<'a> {
 }
 
 impl<'a> JobList<'a> {
-    pub fn get_money(&self, name: &str) -> i64 {
-        match &self.jobs.get(name).expect(name).1 {
-            Expr::Num(n) => *n,
+    /// What the monkey yells, or why it can't: it waits on a monkey that
+    /// isn't there, or its sum doesn't fit in an i64 or divides by zero.
+    pub fn get_money(&self, name: &str) -> Result<i64, String> {
+        let job = self
+            .jobs
+            .get(name)
+            .ok_or_else(|| format!("no such monkey {}", name))?;
+        match &job.1 {
+            Expr::Num(n) => Ok(*n),
             Expr::BinOp { op, lhs, rhs } => {
-                let lhs_money = self.get_money(lhs);
-                let rhs_money = self.get_money(rhs);
-                match op {
-                    Op::Add => lhs_money + rhs_money,
-                    Op::Sub => lhs_money - rhs_money,
-                    Op::Mul => lhs_money * rhs_money,
-                    Op::Div => lhs_money / rhs_money,
-                }
+                let lhs_money = self.get_money(lhs)?;
+                let rhs_money = self.get_money(rhs)?;
+                let money = match op {
+                    Op::Add => lhs_money.checked_add(rhs_money),
+                    Op::Sub => lhs_money.checked_sub(rhs_money),
+                    Op::Mul => lhs_money.checked_mul(rhs_money),
+                    Op::Div => lhs_money.checked_div(rhs_money),
+                };
+                money.ok_or_else(|| match op {
+                    Op::Div if rhs_money == 0 => format!("{} divides by zero", name),
+                    _ => format!("{} overflows", name),
+                })
             }
         }
     }
@@ -31,7 +42,7 @@ impl<'a> JobList<'a> {
     // should have a loss of 0.
     pub fn loss(&self, guess: f64) -> f64 {
         match &self.jobs.get("root").unwrap().1 {
-            Expr::BinOp { op, lhs, rhs } => f64::powf(
+            Expr::BinOp { lhs, rhs, .. } => f64::powf(
                 self.get_money_part_2(lhs, guess) - self.get_money_part_2(rhs, guess),
                 2.0,
             ),
@@ -59,16 +70,104 @@ impl<'a> JobList<'a> {
     }
 }
 
-pub fn parse_all_jobs(s: &str) -> JobList {
-    let jobs: Vec<Job> = s
-        .lines()
-        .map(parser::parse_job)
-        .filter_map(|r| r.ok())
-        .map(|r| r.1)
-        .collect();
+pub fn parse_all_jobs(s: &str) -> Result<JobList<'_>, ParseError> {
+    let mut jobs = HashMap::new();
+    for line in s.lines().filter(|line| !line.trim().is_empty()) {
+        let job = match parser::parse_job(line) {
+            Ok((rest, _)) if !rest.trim().is_empty() => {
+                return Err(ParseError::at(s, rest.trim(), "unexpected text after job"));
+            }
+            Ok((_, job)) => job,
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => {
+                let text = e.input.split_whitespace().next();
+                let text = text.unwrap_or(&e.input[e.input.len()..]);
+                return Err(ParseError::at(s, text, "could not parse job"));
+            }
+            Err(nom::Err::Incomplete(_)) => {
+                return Err(ParseError::at(s, line, "could not parse job"));
+            }
+        };
+        if jobs.contains_key(job.0) {
+            return Err(ParseError::at(s, job.0, "duplicate monkey"));
+        }
+        jobs.insert(job.0, job);
+    }
+
+    Ok(JobList { jobs })
+}
 
-    JobList {
-        jobs: jobs.into_iter().map(|j| (j.0, j)).collect(),
+// The monkeys a monkey waits on.
+fn waits_on<'a>(jobs: &JobList<'a>, name: &str) -> Vec<&'a str> {
+    match jobs.jobs.get(name).map(|job| &job.1) {
+        Some(&Expr::BinOp { lhs, rhs, .. }) => vec![lhs, rhs],
+        _ => vec![],
+    }
+}
+
+// A monkey that ends up waiting on itself, as it's named by the monkey
+// that closes the loop.  Searched depth first, without recursion, from the
+// monkeys in the order they're listed, so the same input always finds the
+// same one.
+fn find_cycle<'a>(jobs: &JobList<'a>) -> Option<&'a str> {
+    #[derive(PartialEq)]
+    enum Mark {
+        Waiting,
+        Done,
+    }
+    let mut names: Vec<&str> = jobs.jobs.keys().copied().collect();
+    names.sort_by_key(|name| name.as_ptr() as usize);
+    let mut marks: HashMap<&str, Mark> = HashMap::new();
+    for start in names {
+        if marks.contains_key(start) {
+            continue;
+        }
+        marks.insert(start, Mark::Waiting);
+        // Each monkey on the path, and how many of its waits are explored.
+        let mut path: Vec<(&str, usize)> = vec![(start, 0)];
+        while let Some(&(name, explored)) = path.last() {
+            match waits_on(jobs, name).get(explored) {
+                Some(&other) => {
+                    path.last_mut().unwrap().1 += 1;
+                    match marks.get(other) {
+                        Some(Mark::Waiting) => return Some(other),
+                        Some(Mark::Done) => {}
+                        None => {
+                            marks.insert(other, Mark::Waiting);
+                            path.push((other, 0));
+                        }
+                    }
+                }
+                None => {
+                    marks.insert(name, Mark::Done);
+                    path.pop();
+                }
+            }
+        }
+    }
+    None
+}
+
+// Checks that there's a root, that every monkey waited on exists, and that
+// none waits on itself, so that the parts never go looking for a monkey
+// that isn't there or wait forever.
+pub fn check_jobs(s: &str, jobs: &JobList) -> Result<(), ParseError> {
+    if !jobs.jobs.contains_key("root") {
+        return Err(ParseError::at(s, &s[s.len()..], "missing monkey root"));
+    }
+    let missing = jobs.jobs.values().flat_map(|job| match job.1 {
+        Expr::BinOp { lhs, rhs, .. } => vec![lhs, rhs],
+        Expr::Num(_) => vec![],
+    });
+    match missing
+        .filter(|name| !jobs.jobs.contains_key(name))
+        .map(|name| ParseError::at(s, name, "no such monkey"))
+        .min_by_key(|e| (e.line, e.column))
+    {
+        Some(e) => Err(e),
+        None => match find_cycle(jobs) {
+            Some(name) => Err(ParseError::at(s, name, "monkey waits on itself")),
+            None => Ok(()),
+        },
     }
 }
 
@@ -77,15 +176,10 @@ const LEARNING: f64 = 0.001;
 pub fn find_minimum(f: impl Fn(f64) -> f64, delta: f64) -> f64 {
     // Compute derivative (f(x+delta) -f(x)) / delta
     let mut x: f64 = 1.0;
-    for i in 0..1000 {
+    for _ in 0..1000 {
         let fx = f(x);
         let fdelta = f(x + delta);
         let neg_deriv = -(fdelta - fx) / delta;
-
-        if i % 10 == 0 {
-            println!("{}: guess={}, fx={}, neg_deriv={}", i, x, fx, neg_deriv);
-        }
-
         x = avg(x, x + neg_deriv * LEARNING);
     }
     x
@@ -96,6 +190,34 @@ fn avg(x: f64, y: f64) -> f64 {
     (x + y) / 2.0
 }
 
+#[derive(Debug, Default)]
+pub struct Day21;
+
+impl Solution for Day21 {
+    // A JobList borrows its names from the input, so we keep the text and
+    // build the list in each part.
+    type Input = String;
+    type Part1 = i64;
+    type Part2 = f64;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        check_jobs(input, &parse_all_jobs(input)?)?;
+        Ok(input.to_string())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<i64, common::Error> {
+        Ok(parse_all_jobs(input)?.get_money("root")?)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<f64, common::Error> {
+        let joblist = parse_all_jobs(input)?;
+        if let Expr::Num(_) = joblist.jobs["root"].1 {
+            return Err("root should be waiting on two monkeys".into());
+        }
+        Ok(find_minimum(|x| joblist.loss(x), 0.1))
+    }
+}
+
 #[test]
 fn test_parse_all_jobs() {
     let parsed = parse_all_jobs(
@@ -105,7 +227,8 @@ dbpl: 5
 cczh: sllz + lgvd
 zczc: 2
 ",
-    );
+    )
+    .unwrap();
 
     let expected_jobs = vec![
         Job(
@@ -136,6 +259,47 @@ zczc: 2
     );
 }
 
+#[test]
+fn test_parse_all_jobs_errors() {
+    assert_eq!(
+        parse_all_jobs("root: pppw + sjmn\ndbpl: 5x"),
+        Err(ParseError::new(2, 8, "x", "unexpected text after job"))
+    );
+    assert_eq!(
+        parse_all_jobs("root: pppw ? sjmn"),
+        Err(ParseError::new(1, 12, "?", "could not parse job"))
+    );
+    assert_eq!(
+        parse_all_jobs("root: pppw + sjmn\n\nroot: 5"),
+        Err(ParseError::new(3, 1, "root", "duplicate monkey"))
+    );
+}
+
+#[test]
+fn test_check_jobs() {
+    let s = "root: pppw + sjmn\npppw: 5\nsjmn: cczh * pppw\n";
+    assert_eq!(
+        check_jobs(s, &parse_all_jobs(s).unwrap()),
+        Err(ParseError::new(3, 7, "cczh", "no such monkey"))
+    );
+    let s = "pppw: 5\n";
+    assert_eq!(
+        check_jobs(s, &parse_all_jobs(s).unwrap()),
+        Err(ParseError::new(2, 1, "", "missing monkey root"))
+    );
+    assert!(Day21::parse("").is_err());
+    assert!(Day21::parse("root: 5\n").is_ok());
+    assert!(Day21.part2(&String::from("root: 5\n")).is_err());
+
+    let s = "root: aaaa + bbbb\naaaa: root * bbbb\nbbbb: 5\n";
+    assert_eq!(
+        check_jobs(s, &parse_all_jobs(s).unwrap()),
+        Err(ParseError::new(2, 7, "root", "monkey waits on itself"))
+    );
+    let s = "root: aaaa + aaaa\naaaa: 5\n";
+    assert!(check_jobs(s, &parse_all_jobs(s).unwrap()).is_ok());
+}
+
 #[test]
 fn test_get_money() {
     let s = "root: pppw + sjmn
@@ -154,8 +318,19 @@ lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32
 ";
-    let joblist = parse_all_jobs(s);
-    assert_eq!(joblist.get_money("root"), 152);
+    let joblist = parse_all_jobs(s).unwrap();
+    assert_eq!(joblist.get_money("root"), Ok(152));
+
+    let joblist = parse_all_jobs("root: aaaa / bbbb\naaaa: 5\nbbbb: 0\n").unwrap();
+    assert_eq!(
+        joblist.get_money("root"),
+        Err("root divides by zero".into())
+    );
+    let joblist = parse_all_jobs("root: aaaa + bbbb\naaaa: 9223372036854775800\nbbbb: 8\n");
+    assert_eq!(
+        joblist.unwrap().get_money("root"),
+        Err("root overflows".into())
+    );
 }
 
 #[test]
@@ -176,7 +351,7 @@ lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32
 ";
-    let joblist = parse_all_jobs(s);
+    let joblist = parse_all_jobs(s).unwrap();
     assert_eq!(joblist.loss(301.0), 0.0);
 }
 
@@ -198,8 +373,8 @@ lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32
 ";
-    let joblist = parse_all_jobs(s);
+    let joblist = parse_all_jobs(s).unwrap();
 
     let min = find_minimum(|x| joblist.loss(x), 0.0001);
-    assert!(min.abs_sub(301.0) < 0.000000001);
+    assert!((min - 301.0).max(0.0) < 0.000000001);
 }
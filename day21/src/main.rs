@@ -1,15 +1,6 @@
-use day21::{find_minimum, parse_all_jobs};
+use day21::Day21;
+use std::process::ExitCode;
 
-fn main() {
-    let input = std::fs::read_to_string("input.txt").expect("input.txt");
-    let joblist = parse_all_jobs(&input);
-    println!("part 1: {}", joblist.get_money("root"));
-
-    let min = find_minimum(|x| joblist.loss(x), 0.1);
-    println!("part 2: {}", min);
-
-    println!(
-        "part 2 guess 3342154812537: loss={}",
-        joblist.loss(3342154812537.0)
-    );
+fn main() -> ExitCode {
+    common::cli::exit(common::cli::run(Day21, 21))
 }
@@ -24,24 +24,24 @@ pub enum Op {
 #[derive(Debug, PartialEq, Eq)]
 pub struct Job<'a>(pub &'a str, pub Expr<'a>);
 
-pub fn parse_job(s: &str) -> IResult<&str, Job> {
+pub fn parse_job(s: &str) -> IResult<&str, Job<'_>> {
     let parser = separated_pair(alpha1, tag(":"), parse_expr);
     let mut parser = map(parser, |(name, expr)| Job(name, expr));
     parser(s)
 }
 
-fn parse_expr(s: &str) -> IResult<&str, Expr> {
+fn parse_expr(s: &str) -> IResult<&str, Expr<'_>> {
     let mut parser = alt((delimited(multispace0, parse_num, multispace0), parse_binop));
     parser(s)
 }
 
-fn parse_num(s: &str) -> IResult<&str, Expr> {
+fn parse_num(s: &str) -> IResult<&str, Expr<'_>> {
     let parser = map_res(digit1, |s: &str| s.parse::<i64>());
     let mut parser = map(parser, Expr::Num);
     parser(s)
 }
 
-fn parse_binop(s: &str) -> IResult<&str, Expr> {
+fn parse_binop(s: &str) -> IResult<&str, Expr<'_>> {
     let parser = tuple((
         delimited(multispace0, alpha1, multispace0),
         delimited(multispace0, parse_op, multispace0),
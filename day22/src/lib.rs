@@ -0,0 +1,458 @@
+use common::observe::{Inspect, Observer};
+use common::{ParseError, Solution};
+use grid::image::Palette;
+use grid::Grid;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dir {
+    North = 3,
+    East = 0,
+    South = 1,
+    West = 2,
+}
+impl Dir {
+    fn clock(self) -> Self {
+        match self {
+            Dir::North => Dir::East,
+            Dir::East => Dir::South,
+            Dir::South => Dir::West,
+            Dir::West => Dir::North,
+        }
+    }
+
+    fn counterclock(self) -> Self {
+        match self {
+            Dir::North => Dir::West,
+            Dir::East => Dir::North,
+            Dir::South => Dir::East,
+            Dir::West => Dir::South,
+        }
+    }
+
+    fn arrow(self) -> char {
+        match self {
+            Dir::North => '^',
+            Dir::East => '>',
+            Dir::South => 'V',
+            Dir::West => '<',
+        }
+    }
+
+    fn step(self) -> grid::Pos {
+        match self {
+            Dir::North => grid::Pos::UP,
+            Dir::East => grid::Pos::RIGHT,
+            Dir::South => grid::Pos::DOWN,
+            Dir::West => grid::Pos::LEFT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Pos {
+    x: i64,
+    y: i64,
+    dir: Dir,
+}
+impl Pos {
+    fn password(&self) -> i32 {
+        1000 * (self.y as i32 + 1) + 4 * (self.x as i32 + 1) + self.dir as i32
+    }
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    map: Grid<char>,
+    moves: Vec<Action>,
+}
+
+impl Problem {
+    fn initial_pos(&self) -> Option<Pos> {
+        let x = self.map.rows().next()?.iter().position(|&ch| ch == '.')?;
+        Some(Pos {
+            x: x as i64,
+            y: 0,
+            dir: Dir::East,
+        })
+    }
+
+    fn forward1(&self, Pos { x, y, dir }: Pos) -> Pos {
+        let mut p = grid::Pos::new(x, y);
+        loop {
+            p = self.map.wrap(p + dir.step());
+
+            match self.map[p] {
+                '#' => {
+                    // Hit a wall: stop moving
+                    return Pos { x, y, dir };
+                }
+                '.' => {
+                    // Landed in vacant spot
+                    return Pos {
+                        x: p.x,
+                        y: p.y,
+                        dir,
+                    };
+                }
+                _ => {
+                    // Out of bounds.  Keep moving.
+                }
+            }
+        }
+    }
+
+    fn apply_move(&self, p: Pos, a: Action) -> Pos {
+        match a {
+            Action::Forward(n) => (0..n).fold(p, |acc, _| self.forward1(acc)),
+
+            Action::Clock => Pos {
+                dir: p.dir.clock(),
+                ..p
+            },
+
+            Action::Counterclock => Pos {
+                dir: p.dir.counterclock(),
+                ..p
+            },
+        }
+    }
+}
+
+/// Where the path has got to on the board.
+#[derive(Debug)]
+pub struct Walk<'a> {
+    problem: &'a Problem,
+    pos: Pos,
+}
+
+impl Walk<'_> {
+    /// The column and row, counting from 0.
+    pub fn position(&self) -> grid::Pos {
+        grid::Pos::new(self.pos.x, self.pos.y)
+    }
+
+    /// The password for stopping here.
+    pub fn password(&self) -> i32 {
+        self.pos.password()
+    }
+}
+
+// Draws the board with an arrow where the path has got to.
+impl fmt::Display for Walk<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = self.problem.map.clone();
+        map[self.position()] = self.pos.dir.arrow();
+        write!(f, "{}", map.render(|&ch| ch))
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Action {
+    Forward(usize),
+    Clock,
+    Counterclock,
+}
+
+pub fn parse_input(s: &str) -> Result<Problem, ParseError> {
+    let (map, moves) = s.split_once("\n\n").ok_or_else(|| {
+        ParseError::at(s, &s[s.len()..], "expected a blank line and then the path")
+    })?;
+    let map = parse_map(map).map_err(|e| e.within(s, map))?;
+    let moves = parse_moves(moves).map_err(|e| e.within(s, moves))?;
+
+    Ok(Problem { map, moves })
+}
+
+fn parse_moves(s: &str) -> Result<Vec<Action>, ParseError> {
+    let mut moves = Vec::new();
+    let mut n = 0;
+    let path = s.trim();
+    for (i, ch) in path.char_indices() {
+        match ch {
+            '0'..='9' => n = n * 10 + (ch as usize - '0' as usize),
+            'L' => {
+                moves.push(Action::Forward(n));
+                moves.push(Action::Counterclock);
+                n = 0;
+            }
+            'R' => {
+                moves.push(Action::Forward(n));
+                moves.push(Action::Clock);
+                n = 0;
+            }
+            _ => {
+                let bad = &path[i..i + ch.len_utf8()];
+                return Err(ParseError::at(s, bad, "expected a number, 'L' or 'R'"));
+            }
+        }
+    }
+    if n != 0 {
+        moves.push(Action::Forward(n));
+    }
+    Ok(moves)
+}
+
+fn parse_map(s: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse_padded(s, ' ', |ch| matches!(ch, ' ' | '.' | '#').then_some(ch))
+}
+
+pub fn part_1(s: &str) -> i32 {
+    let pos = get_final_pos(s);
+    pos.password()
+}
+
+/** Given the problem, returns final position. */
+fn get_final_pos(s: &str) -> Pos {
+    final_pos(&parse_input(s).unwrap()).unwrap()
+}
+
+/** Follows the moves from the initial position, if there is one. */
+fn final_pos(problem: &Problem) -> Option<Pos> {
+    final_pos_with(problem, &mut ())
+}
+
+/** As final_pos, showing the observer each tile moved and each turn. */
+fn final_pos_with<'a>(problem: &'a Problem, observer: &mut impl Observer<Walk<'a>>) -> Option<Pos> {
+    let mut walk = Walk {
+        problem,
+        pos: problem.initial_pos()?,
+    };
+    let mut step = 0;
+    for &a in &problem.moves {
+        let (times, a) = match a {
+            Action::Forward(n) => (n, Action::Forward(1)),
+            turn => (1, turn),
+        };
+        for _ in 0..times {
+            walk.pos = problem.apply_move(walk.pos, a);
+            step += 1;
+            observer.observe(step, &walk);
+        }
+    }
+    Some(walk.pos)
+}
+
+/** Follows the path, showing the observer each tile moved and each turn.
+ * Returns the password, or None if there's no open tile to start from. */
+pub fn walk_with<'a>(problem: &'a Problem, observer: &mut impl Observer<Walk<'a>>) -> Option<i32> {
+    final_pos_with(problem, observer).map(|pos| pos.password())
+}
+
+#[derive(Debug, Default)]
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Problem;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self, problem: &Self::Input) -> Result<i32, common::Error> {
+        let pos = final_pos(problem).ok_or("no open tile to start from")?;
+        Ok(pos.password())
+    }
+
+    // Folding the map into a cube hasn't been done yet.
+    fn part2(&self, _problem: &Self::Input) -> Result<i32, common::Error> {
+        Err("not implemented".into())
+    }
+}
+
+/** The map with the path drawn over it in arrows, or None if there's no
+ * open tile to start from.  The observer is shown each step, as for
+ * walk_with. */
+pub fn trail_with<'a>(
+    problem: &'a Problem,
+    observer: &mut impl Observer<Walk<'a>>,
+) -> Option<String> {
+    let Pos { x, y, dir } = problem.initial_pos()?;
+    let mut map = problem.map.clone();
+    map[grid::Pos::new(x, y)] = dir.arrow();
+    let mut draw = Inspect(|_, walk: &Walk| map[walk.position()] = walk.pos.dir.arrow());
+    walk_with(problem, &mut (&mut draw, observer));
+    Some(map.render(|&ch| ch))
+}
+
+/** Open tiles, walls and the path, with the void off the board left dark. */
+pub fn palette() -> Palette {
+    let path = [255, 120, 40];
+    Palette::new([10, 10, 20])
+        .with('.', [200, 200, 190])
+        .with('#', [70, 70, 80])
+        .with('^', path)
+        .with('>', path)
+        .with('V', path)
+        .with('<', path)
+}
+
+/** Given the problem, shows what the path looks like.  For debugging purposes. */
+pub fn visualize(s: &str) {
+    let problem = parse_input(s).unwrap();
+    print!("{}", trail_with(&problem, &mut ()).unwrap_or_default());
+}
+
+#[test]
+fn test_part1() {
+    let input = "\
+        ...#
+        .#..
+        #...
+        ....
+...#.......#
+........#...
+..#....#....
+..........#.
+        ...#....
+        .....#..
+        .#......
+        ......#.
+
+10R5L5R10L4R5L5
+";
+    assert_eq!(part_1(input), 6032);
+}
+
+#[test]
+fn test_walk_with() {
+    use common::observe::{Counter, Invariant};
+
+    let problem = parse_input("   ...\n   .#.\n\n2R1").unwrap();
+    let mut frames = Vec::new();
+    let mut record = Inspect(|_, walk: &Walk| frames.push(walk.to_string()));
+    let mut open = Invariant::new("to stand on an open tile", |walk: &Walk| {
+        problem.map[walk.position()] == '.'
+    });
+    let mut counter = Counter::default();
+    assert_eq!(
+        walk_with(&problem, &mut (&mut record, (&mut open, &mut counter))),
+        Some(2025)
+    );
+    assert_eq!(counter.steps, 4);
+    assert_eq!(frames[0], "   .>.\n   .#.\n");
+    assert_eq!(frames[2], "   ..V\n   .#.\n");
+    assert_eq!(frames[3], "   ...\n   .#V\n");
+}
+
+#[test]
+fn test_trail() {
+    let problem = parse_input("   ...\n   .#.\n\n2R1").unwrap();
+    assert_eq!(
+        trail_with(&problem, &mut ()).as_deref(),
+        Some("   >>V\n   .#V\n")
+    );
+}
+
+#[test]
+fn test_parse_input_errors() {
+    assert_eq!(
+        parse_input("  ..#\n  ...\n10R5").err(),
+        Some(ParseError::new(
+            3,
+            5,
+            "",
+            "expected a blank line and then the path"
+        ))
+    );
+    assert_eq!(
+        parse_input("  ..#\n  .x.\n\n10R5").err(),
+        Some(ParseError::new(2, 4, "x", "unexpected character"))
+    );
+    assert_eq!(
+        parse_input("  ..#\n  ...\n\n10R5U2\n").err(),
+        Some(ParseError::new(4, 5, "U", "expected a number, 'L' or 'R'"))
+    );
+}
+
+#[test]
+fn test_get_final_pos() {
+    let pos = get_final_pos(
+        "   ...
+
+1",
+    );
+    assert_eq!(
+        pos,
+        Pos {
+            x: 4,
+            y: 0,
+            dir: Dir::East
+        }
+    );
+
+    let pos = get_final_pos(
+        "   ...
+
+2",
+    );
+    assert_eq!(
+        pos,
+        Pos {
+            x: 5,
+            y: 0,
+            dir: Dir::East
+        }
+    );
+
+    let pos = get_final_pos(
+        "   ...
+
+3",
+    );
+    assert_eq!(
+        pos,
+        Pos {
+            x: 3,
+            y: 0,
+            dir: Dir::East
+        }
+    );
+}
+
+#[test]
+fn test_get_final_pos_left() {
+    let pos = get_final_pos(
+        "   ...
+
+RR1",
+    );
+    assert_eq!(
+        pos,
+        Pos {
+            x: 5,
+            y: 0,
+            dir: Dir::West
+        }
+    );
+
+    let pos = get_final_pos(
+        "   ...
+
+RR2",
+    );
+    assert_eq!(
+        pos,
+        Pos {
+            x: 4,
+            y: 0,
+            dir: Dir::West
+        }
+    );
+
+    let pos = get_final_pos(
+        "   ...
+
+RR3",
+    );
+    assert_eq!(
+        pos,
+        Pos {
+            x: 3,
+            y: 0,
+            dir: Dir::West
+        }
+    );
+}
@@ -0,0 +1,254 @@
+// Checking a packing list for the things the puzzle takes for granted:
+// that every line is a rucksack with two equal compartments, that exactly
+// one item is in both, that each group has exactly one badge, and that the
+// groups come out even.  Each anomaly found names its line.
+//
+// Read strictly, as by default, the first anomaly is an error.  Read
+// leniently, lines that aren't rucksacks are skipped, the rest are
+// answered for as best they can be, and the anomalies are warnings.
+
+use crate::{badges, line_error, Items, Rucksack, RucksackError};
+use common::ParseError;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anomaly {
+    /// A character that isn't an item, at a 1-based column.
+    InvalidItem {
+        line: usize,
+        column: usize,
+        item: char,
+    },
+    UnevenCompartments {
+        line: usize,
+        len: usize,
+    },
+    /// Not exactly one item in both compartments.
+    SharedItems {
+        line: usize,
+        shared: Items,
+    },
+    /// Not exactly one item in all of a group's rucksacks.  The line is
+    /// the group's first.
+    Badge {
+        line: usize,
+        shared: Items,
+    },
+    /// A rucksack after the last whole group.
+    Leftover {
+        line: usize,
+    },
+}
+
+impl Anomaly {
+    pub fn line(&self) -> usize {
+        match *self {
+            Anomaly::InvalidItem { line, .. }
+            | Anomaly::UnevenCompartments { line, .. }
+            | Anomaly::SharedItems { line, .. }
+            | Anomaly::Badge { line, .. }
+            | Anomaly::Leftover { line } => line,
+        }
+    }
+
+    /// The error parse_rucksacks gives for the line, if it isn't a
+    /// rucksack at all.
+    pub fn parse_error(&self, input: &str) -> Option<ParseError> {
+        let text = input.lines().nth(self.line() - 1).unwrap_or("");
+        match *self {
+            Anomaly::InvalidItem { line, column, item } => Some(line_error(
+                line,
+                text,
+                RucksackError::InvalidItem { column, item },
+            )),
+            Anomaly::UnevenCompartments { line, len } => Some(line_error(
+                line,
+                text,
+                RucksackError::UnevenCompartments { len },
+            )),
+            _ => None,
+        }
+    }
+}
+
+// "no items" or "2 items, bA".
+fn describe(items: &Items) -> String {
+    match items.len() {
+        0 => String::from("no items"),
+        n => format!("{} items, {}", n, items.iter().collect::<String>()),
+    }
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Anomaly::InvalidItem { line, column, item } => {
+                write!(
+                    f,
+                    "line {}, column {}: {:?} is not an item",
+                    line, column, item
+                )
+            }
+            Anomaly::UnevenCompartments { line, len } => write!(
+                f,
+                "line {}: {} items don't split into two compartments",
+                line, len
+            ),
+            Anomaly::SharedItems { line, shared } => write!(
+                f,
+                "line {}: {} in both compartments instead of one",
+                line,
+                describe(shared)
+            ),
+            Anomaly::Badge { line, shared } => write!(
+                f,
+                "line {}: {} shared by the group starting here instead of one",
+                line,
+                describe(shared)
+            ),
+            Anomaly::Leftover { line } => {
+                write!(f, "line {}: not in a whole group", line)
+            }
+        }
+    }
+}
+
+impl Error for Anomaly {}
+
+/// The rucksacks on a packing list, each with its 1-based line, and the
+/// lines that weren't rucksacks.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Packing {
+    pub rucksacks: Vec<(usize, Rucksack)>,
+    pub malformed: Vec<Anomaly>,
+}
+
+impl Packing {
+    /// Reads every line it can, keeping an anomaly for each it can't.
+    pub fn read(input: &str) -> Self {
+        let mut packing = Packing::default();
+        for (lineindex, text) in input.lines().enumerate() {
+            let line = lineindex + 1;
+            match text.parse() {
+                Ok(rucksack) => packing.rucksacks.push((line, rucksack)),
+                Err(RucksackError::InvalidItem { column, item }) => packing
+                    .malformed
+                    .push(Anomaly::InvalidItem { line, column, item }),
+                Err(RucksackError::UnevenCompartments { len }) => packing
+                    .malformed
+                    .push(Anomaly::UnevenCompartments { line, len }),
+            }
+        }
+        packing
+    }
+
+    /// The rucksacks that were read, without their lines.
+    pub fn rucksacks(&self) -> Vec<Rucksack> {
+        self.rucksacks
+            .iter()
+            .map(|&(_, rucksack)| rucksack)
+            .collect()
+    }
+
+    /// What's wrong with the rucksacks on their own, as for part 1.
+    pub fn rucksack_anomalies(&self) -> Vec<Anomaly> {
+        let shared = self
+            .rucksacks
+            .iter()
+            .filter(|(_, rucksack)| rucksack.shared().len() != 1)
+            .map(|&(line, rucksack)| Anomaly::SharedItems {
+                line,
+                shared: rucksack.shared(),
+            });
+        by_line(self.malformed.iter().cloned().chain(shared))
+    }
+
+    /// What's wrong with the rucksacks in groups of `size`, as for part 2.
+    /// Skipped lines don't count towards the groups.
+    pub fn group_anomalies(&self, size: usize) -> Vec<Anomaly> {
+        let groups = self.rucksacks.chunks_exact(size.max(1));
+        let leftover = groups
+            .remainder()
+            .iter()
+            .map(|&(line, _)| Anomaly::Leftover { line });
+        let badges = groups
+            .zip(badges(&self.rucksacks(), size.max(1)))
+            .filter(|(_, shared)| shared.len() != 1)
+            .map(|(group, shared)| Anomaly::Badge {
+                line: group[0].0,
+                shared,
+            });
+        by_line(self.malformed.iter().cloned().chain(badges).chain(leftover))
+    }
+
+    /// Everything that's wrong, in order.
+    pub fn anomalies(&self, size: usize) -> Vec<Anomaly> {
+        let groups = self.group_anomalies(size).into_iter();
+        let mut anomalies = by_line(self.rucksack_anomalies().into_iter().chain(groups));
+        anomalies.dedup();
+        anomalies
+    }
+}
+
+fn by_line(anomalies: impl Iterator<Item = Anomaly>) -> Vec<Anomaly> {
+    let mut anomalies: Vec<Anomaly> = anomalies.collect();
+    anomalies.sort_by_key(Anomaly::line);
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+abcab
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+abde
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+ab-d
+";
+
+    #[test]
+    fn test_read() {
+        let packing = Packing::read(LIST);
+        assert_eq!(packing.rucksacks.len(), 7);
+        assert_eq!(
+            packing.malformed,
+            [
+                Anomaly::UnevenCompartments { line: 3, len: 5 },
+                Anomaly::InvalidItem {
+                    line: 9,
+                    column: 3,
+                    item: '-'
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_anomalies() {
+        let packing = Packing::read(LIST);
+        let lines = |anomalies: Vec<Anomaly>| -> Vec<usize> {
+            anomalies.iter().map(Anomaly::line).collect()
+        };
+        // abde has nothing in both compartments.
+        assert_eq!(lines(packing.rucksack_anomalies()), [3, 6, 9]);
+        // The second group, from line 5, has abde in it, and so no badge;
+        // line 8 is left over.
+        assert_eq!(lines(packing.group_anomalies(3)), [3, 5, 8, 9]);
+        assert_eq!(lines(packing.anomalies(3)), [3, 5, 6, 8, 9]);
+        assert_eq!(
+            packing.anomalies(3)[1].to_string(),
+            "line 5: no items shared by the group starting here instead of one"
+        );
+        let group = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+";
+        assert!(Packing::read(group).anomalies(3).is_empty());
+    }
+}
@@ -0,0 +1,437 @@
+use anomaly::Packing;
+use common::{ParseError, Solution};
+use std::error::Error;
+use std::fmt;
+use std::io::prelude::*;
+use std::ops::{BitAnd, BitOr};
+use std::str::FromStr;
+
+pub mod anomaly;
+
+fn priority(ch: char) -> Option<u32> {
+    match ch {
+        'a'..='z' => Some(ch as u32 - 'a' as u32 + 1),
+        'A'..='Z' => Some(ch as u32 - 'A' as u32 + 27),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_priority() {
+    assert_eq!(priority('a'), Some(1));
+    assert_eq!(priority('L'), Some(38));
+    assert_eq!(priority('!'), None);
+}
+
+// The item with a priority, from 1 to 52.
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => char::from(b'a' + (priority - 1) as u8),
+        _ => char::from(b'A' + (priority - 27) as u8),
+    }
+}
+
+/// A character that isn't an item: only letters are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidItem(pub char);
+
+impl fmt::Display for InvalidItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} is not an item", self.0)
+    }
+}
+
+impl Error for InvalidItem {}
+
+/// A set of items, one bit for each: bit 0 for a, with priority 1, up to
+/// bit 51 for Z.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Items(u64);
+
+impl Items {
+    pub const EMPTY: Items = Items(0);
+
+    pub fn insert(&mut self, ch: char) -> Result<(), InvalidItem> {
+        let priority = priority(ch).ok_or(InvalidItem(ch))?;
+        self.0 |= 1 << (priority - 1);
+        Ok(())
+    }
+
+    pub fn contains(&self, ch: char) -> bool {
+        priority(ch).is_some_and(|priority| self.0 & 1 << (priority - 1) != 0)
+    }
+
+    pub fn intersection(self, other: Items) -> Items {
+        Items(self.0 & other.0)
+    }
+
+    pub fn union(self, other: Items) -> Items {
+        Items(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items, in order of priority.
+    pub fn iter(&self) -> impl Iterator<Item = char> + '_ {
+        (1..=52)
+            .filter(|priority| self.0 & 1 << (priority - 1) != 0)
+            .map(item)
+    }
+
+    /// The item, if there's exactly one.
+    pub fn only(&self) -> Option<char> {
+        (self.len() == 1).then(|| item(self.0.trailing_zeros() + 1))
+    }
+
+    /// The sum of the items' priorities.
+    pub fn priority(&self) -> u32 {
+        self.iter().filter_map(priority).sum()
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+
+    fn bitand(self, other: Items) -> Items {
+        self.intersection(other)
+    }
+}
+
+impl BitOr for Items {
+    type Output = Items;
+
+    fn bitor(self, other: Items) -> Items {
+        self.union(other)
+    }
+}
+
+impl FromStr for Items {
+    type Err = InvalidItem;
+
+    fn from_str(s: &str) -> Result<Self, InvalidItem> {
+        let mut items = Items::EMPTY;
+        for ch in s.chars() {
+            items.insert(ch)?;
+        }
+        Ok(items)
+    }
+}
+
+#[test]
+fn test_items() -> Result<(), Box<dyn Error>> {
+    let lhs: Items = "vJrwpWtwJgWr".parse()?;
+    let rhs: Items = "hcsFMMfFFhFp".parse()?;
+    assert_eq!((lhs & rhs).only(), Some('p'));
+    assert_eq!((lhs & rhs).priority(), 16);
+    assert_eq!((lhs | rhs).len(), 14);
+    assert!(lhs.contains('J') && !lhs.contains('j') && !lhs.contains('!'));
+    assert_eq!("zaZ".parse::<Items>()?.iter().collect::<String>(), "azZ");
+    assert_eq!("ab".parse::<Items>()?.only(), None);
+    assert_eq!("a-b".parse::<Items>(), Err(InvalidItem('-')));
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RucksackError {
+    /// An item that isn't a letter, at a 1-based column.
+    InvalidItem { column: usize, item: char },
+    /// An odd number of items, which can't be split into two compartments.
+    UnevenCompartments { len: usize },
+}
+
+impl fmt::Display for RucksackError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RucksackError::InvalidItem { column, item } => {
+                write!(f, "column {}: {}", column, InvalidItem(*item))
+            }
+            RucksackError::UnevenCompartments { len } => {
+                write!(f, "{} items don't split into two compartments", len)
+            }
+        }
+    }
+}
+
+impl Error for RucksackError {}
+
+/// The items in each compartment of a rucksack.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rucksack {
+    pub left: Items,
+    pub right: Items,
+}
+
+impl Rucksack {
+    /// The items in either compartment.
+    pub fn items(&self) -> Items {
+        self.left | self.right
+    }
+
+    /// The items packed into both compartments.
+    pub fn shared(&self) -> Items {
+        self.left & self.right
+    }
+}
+
+impl FromStr for Rucksack {
+    type Err = RucksackError;
+
+    fn from_str(s: &str) -> Result<Self, RucksackError> {
+        let mut rucksack = Rucksack::default();
+        let len = s.chars().count();
+        for (i, ch) in s.chars().enumerate() {
+            let compartment = if i < len / 2 {
+                &mut rucksack.left
+            } else {
+                &mut rucksack.right
+            };
+            compartment
+                .insert(ch)
+                .map_err(|InvalidItem(item)| RucksackError::InvalidItem {
+                    column: i + 1,
+                    item,
+                })?;
+        }
+        if !len.is_multiple_of(2) {
+            return Err(RucksackError::UnevenCompartments { len });
+        }
+        Ok(rucksack)
+    }
+}
+
+// Reads one rucksack per line.  Every item must be a letter, so that it
+// has a priority, and both compartments must hold the same number of items.
+pub fn parse_rucksacks(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    let mut rucksacks = Vec::new();
+    for (lineindex, line) in input.lines().enumerate() {
+        let rucksack = line
+            .parse()
+            .map_err(|e| line_error(lineindex + 1, line, e))?;
+        rucksacks.push(rucksack);
+    }
+    Ok(rucksacks)
+}
+
+// Where on the numbered line it stopped being a rucksack.
+fn line_error(lineno: usize, line: &str, e: RucksackError) -> ParseError {
+    match e {
+        RucksackError::InvalidItem { column, item } => {
+            let start = line.char_indices().nth(column - 1).map_or(0, |(i, _)| i);
+            ParseError::at(line, &line[start..start + item.len_utf8()], "not an item")
+                .on_line(lineno)
+        }
+        RucksackError::UnevenCompartments { .. } => {
+            ParseError::new(lineno, 1, line, "compartments differ in size")
+        }
+    }
+}
+
+// Sums the priorities of the items found in both compartments.
+pub fn sum_priorities_part_1(rucksacks: &[Rucksack]) -> u32 {
+    rucksacks.iter().map(|sack| sack.shared().priority()).sum()
+}
+
+pub fn get_sum_priorities_part_1(mut input: impl Read) -> Result<u32, Box<dyn Error>> {
+    let mut s = String::new();
+    input.read_to_string(&mut s)?;
+    Ok(sum_priorities_part_1(&parse_rucksacks(&s)?))
+}
+
+#[test]
+fn test_sum_priorities_example() -> Result<(), Box<dyn Error>> {
+    let ex = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw"
+        .as_bytes();
+    assert_eq!(get_sum_priorities_part_1(ex)?, 157);
+    Ok(())
+}
+
+/// The items that every rucksack in each group of `size` has, group by
+/// group; that should be exactly one, the group's badge.  Rucksacks left
+/// over after the last whole group are ignored.
+pub fn badges(rucksacks: &[Rucksack], size: usize) -> Vec<Items> {
+    if size == 0 {
+        return Vec::new();
+    }
+    rucksacks
+        .chunks_exact(size)
+        .map(|group| {
+            group
+                .iter()
+                .map(Rucksack::items)
+                .reduce(Items::intersection)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[test]
+fn test_badges() -> Result<(), Box<dyn Error>> {
+    let rucksacks = parse_rucksacks("abcA\nxbyB\nbzzA\nwwww\n")?;
+    let threes = badges(&rucksacks, 3);
+    assert_eq!(threes.len(), 1);
+    assert_eq!(threes[0].iter().collect::<String>(), "b");
+    let pairs: Vec<Option<char>> = badges(&rucksacks, 2).iter().map(Items::only).collect();
+    assert_eq!(pairs, [Some('b'), None]);
+    assert!(badges(&rucksacks, 0).is_empty());
+    assert_eq!(badges(&rucksacks, 1)[3].only(), Some('w'));
+    Ok(())
+}
+
+// Sums the priorities of the badge shared by each group of elves.
+pub fn sum_priorities_part_2(rucksacks: &[Rucksack], group_size: usize) -> u32 {
+    badges(rucksacks, group_size)
+        .iter()
+        .map(Items::priority)
+        .sum()
+}
+
+pub fn get_sum_priorities_part_2(mut input: impl Read) -> Result<u32, Box<dyn Error>> {
+    let mut s = String::new();
+    input.read_to_string(&mut s)?;
+    Ok(sum_priorities_part_2(&parse_rucksacks(&s)?, 3))
+}
+
+#[test]
+fn test_sum_priorities_2() -> Result<(), Box<dyn Error>> {
+    let input = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw"
+        .as_bytes();
+    assert_eq!(get_sum_priorities_part_2(input)?, 70);
+    Ok(())
+}
+
+#[test]
+fn test_parse_rucksacks_errors() {
+    let e = parse_rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\nab-d\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "-"));
+
+    let e = parse_rucksacks("abc\n").unwrap_err();
+    assert_eq!((e.line, e.column), (1, 1));
+
+    let e = "abé".parse::<Rucksack>().unwrap_err();
+    assert_eq!(
+        e,
+        RucksackError::InvalidItem {
+            column: 3,
+            item: 'é'
+        }
+    );
+    let e = parse_rucksacks("abé\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (1, 3, "é"));
+}
+
+// The elves are in groups of three, unless told otherwise with --group.
+// A line that isn't a rucksack fails parsing, and any other anomaly in the
+// packing list fails the part it matters to, unless the day is lenient;
+// see anomaly.
+#[derive(Debug)]
+pub struct Day3 {
+    pub group: usize,
+    pub lenient: bool,
+}
+
+impl Default for Day3 {
+    fn default() -> Self {
+        Day3 {
+            group: 3,
+            lenient: false,
+        }
+    }
+}
+
+impl Day3 {
+    fn check(&self, anomalies: Vec<anomaly::Anomaly>) -> Result<(), common::Error> {
+        match anomalies.into_iter().next() {
+            Some(anomaly) if !self.lenient => Err(anomaly.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Solution for Day3 {
+    type Input = Packing;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        let packing = Packing::read(input);
+        match packing.malformed.iter().find_map(|a| a.parse_error(input)) {
+            Some(e) => Err(e.into()),
+            None => Ok(packing),
+        }
+    }
+
+    // When lenient, keeps every rucksack it can, and leaves the rest for
+    // warnings.
+    fn parse_input(&self, input: &str) -> Result<Self::Input, common::Error> {
+        if self.lenient {
+            Ok(Packing::read(input))
+        } else {
+            Day3::parse(input)
+        }
+    }
+
+    fn part1(&self, packing: &Self::Input) -> Result<u32, common::Error> {
+        self.check(packing.rucksack_anomalies())?;
+        Ok(sum_priorities_part_1(&packing.rucksacks()))
+    }
+
+    fn part2(&self, packing: &Self::Input) -> Result<u32, common::Error> {
+        self.check(packing.group_anomalies(self.group))?;
+        Ok(sum_priorities_part_2(&packing.rucksacks(), self.group))
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), common::Error> {
+        match name {
+            "group" => match value.parse()? {
+                0 => return Err("groups need at least one elf".into()),
+                group => self.group = group,
+            },
+            "lenient" => self.lenient = value.parse()?,
+            _ => return Err(common::unknown_param(name, &["group", "lenient"])),
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_day3_lenient() -> Result<(), Box<dyn Error>> {
+    // The second rucksack has a and b in both compartments, and the third
+    // isn't one at all.
+    let input = "vJrwpWtwJgWrhcsFMMfFFhFp\nabeabd\nab-d\n";
+    let mut day = Day3::default();
+    let e = day.parse_input(input).unwrap_err();
+    assert_eq!(e.to_string(), "line 3, column 3: not an item: \"-\"");
+
+    let packing = Day3::parse("vJrwpWtwJgWrhcsFMMfFFhFp\nabeabd\n")?;
+    let e = day.part1(&packing).unwrap_err();
+    assert_eq!(
+        e.to_string(),
+        "line 2: 2 items, ab in both compartments instead of one"
+    );
+
+    day.set_param("lenient", "true")?;
+    let packing = day.parse_input(input)?;
+    assert_eq!(packing.malformed.len(), 1);
+    assert_eq!(day.part1(&packing)?, 16 + 1 + 2);
+    // One group of two, and no badge.
+    day.set_param("group", "2")?;
+    assert_eq!(day.part2(&packing)?, 0);
+    assert!(day.set_param("group", "0").is_err());
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use common::{ParseError, Solution};
+
+pub mod roster;
+
+/// The sections from start to end, inclusive.  One made to end before it
+/// starts, like 4-2, has no sections; the input can't hold one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Assignment {
+    start: u32,
+    end: u32,
+}
+
+impl Assignment {
+    pub fn new(start: u32, end: u32) -> Assignment {
+        Assignment { start, end }
+    }
+
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// The number of sections.  As wide as 0-4294967295 still fits.
+    pub fn len(&self) -> u64 {
+        if self.is_empty() {
+            0
+        } else {
+            (self.end - self.start) as u64 + 1
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start > self.end
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        self.start <= section && section <= self.end
+    }
+
+    /// Whether every section of `other` is in this one.  No sections at
+    /// all are in anything.
+    pub fn fully_encloses(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && self.end >= other.end)
+    }
+
+    /// Whether some section is in both, which it can't be if either has
+    /// none.
+    // https://stackoverflow.com/questions/3269434/whats-the-most-efficient-way-to-test-if-two-ranges-overlap
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.is_empty() && !other.is_empty() && self.start <= other.end && self.end >= other.start
+    }
+
+    /// The sections in both, if there are any.
+    pub fn intersection(&self, other: &Self) -> Option<Assignment> {
+        let both = Assignment::new(self.start.max(other.start), self.end.min(other.end));
+        (!both.is_empty()).then_some(both)
+    }
+
+    /// The sections in either, if they make one range: the two overlap or
+    /// one ends just before the other starts.
+    pub fn merge(&self, other: &Self) -> Option<Assignment> {
+        if self.is_empty() {
+            return Some(*other);
+        }
+        if other.is_empty() {
+            return Some(*self);
+        }
+        let touches = |a: &Self, b: &Self| a.start as u64 <= b.end as u64 + 1;
+        (touches(self, other) && touches(other, self))
+            .then(|| Assignment::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+
+    /// The sections not in `other`: none, one or two ranges, in order.
+    pub fn difference(&self, other: &Self) -> Vec<Assignment> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        if !self.overlaps(other) || other.is_empty() {
+            return vec![*self];
+        }
+        let mut pieces = Vec::new();
+        if self.start < other.start {
+            pieces.push(Assignment::new(self.start, other.start - 1));
+        }
+        if other.end < self.end {
+            pieces.push(Assignment::new(other.end + 1, self.end));
+        }
+        pieces
+    }
+}
+
+impl std::str::FromStr for Assignment {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chunks = s.split('-');
+        if let (Some(start), Some(end), None) = (chunks.next(), chunks.next(), chunks.next()) {
+            let number = |n: &str| {
+                n.parse::<u32>()
+                    .map_err(|_| ParseError::at(s, n, "expected a section number"))
+            };
+            let assignment = Assignment::new(number(start)?, number(end)?);
+            if assignment.is_empty() {
+                return Err(ParseError::at(s, s, "range ends before it starts"));
+            }
+            return Ok(assignment);
+        }
+
+        Err(ParseError::at(s, s, "expected a range like 2-4"))
+    }
+}
+
+#[test]
+fn test_fully_encloses() {
+    assert!(!Assignment::new(1, 2).fully_encloses(&Assignment::new(3, 4)));
+
+    assert!(Assignment::new(1, 2).fully_encloses(&Assignment::new(2, 2)));
+
+    assert!(Assignment::new(2, 8).fully_encloses(&Assignment::new(3, 7)));
+
+    // An empty range is in every other, and nothing is in it.
+    assert!(Assignment::new(2, 4).fully_encloses(&Assignment::new(4, 2)));
+    assert!(Assignment::new(7, 8).fully_encloses(&Assignment::new(4, 2)));
+    assert!(!Assignment::new(4, 2).fully_encloses(&Assignment::new(2, 4)));
+}
+
+#[test]
+fn test_overlaps() {
+    assert!(Assignment::new(2, 4).overlaps(&Assignment::new(4, 6)));
+    assert!(!Assignment::new(2, 4).overlaps(&Assignment::new(5, 6)));
+
+    // Nothing overlaps an empty range, even one that seems to span it.
+    assert!(!Assignment::new(4, 2).overlaps(&Assignment::new(2, 4)));
+    assert!(!Assignment::new(2, 4).overlaps(&Assignment::new(4, 2)));
+}
+
+#[test]
+fn test_algebra() {
+    let a = Assignment::new(2, 6);
+    assert_eq!(a.len(), 5);
+    assert_eq!(Assignment::new(4, 2).len(), 0);
+    assert_eq!(Assignment::new(0, u32::MAX).len(), 1 << 32);
+
+    assert_eq!(
+        a.intersection(&Assignment::new(4, 8)),
+        Some(Assignment::new(4, 6))
+    );
+    assert_eq!(a.intersection(&Assignment::new(7, 8)), None);
+
+    assert_eq!(a.merge(&Assignment::new(7, 8)), Some(Assignment::new(2, 8)));
+    assert_eq!(a.merge(&Assignment::new(0, 1)), Some(Assignment::new(0, 6)));
+    assert_eq!(a.merge(&Assignment::new(8, 9)), None);
+    assert_eq!(
+        Assignment::new(0, u32::MAX).merge(&Assignment::new(u32::MAX, u32::MAX)),
+        Some(Assignment::new(0, u32::MAX))
+    );
+
+    assert_eq!(
+        a.difference(&Assignment::new(3, 4)),
+        [Assignment::new(2, 2), Assignment::new(5, 6)]
+    );
+    assert_eq!(
+        a.difference(&Assignment::new(0, 3)),
+        [Assignment::new(4, 6)]
+    );
+    assert_eq!(a.difference(&Assignment::new(1, 9)), []);
+    assert_eq!(a.difference(&Assignment::new(7, 9)), [a]);
+}
+
+#[test]
+fn test_parse() {
+    assert!("hello.".parse::<Assignment>().is_err());
+    assert!("4-2".parse::<Assignment>().is_err());
+    assert!(matches!(
+        "2-4".parse::<Assignment>(),
+        Ok(Assignment { start: 2, end: 4 })
+    ));
+    assert!(matches!(
+        "12-17".parse::<Assignment>(),
+        Ok(Assignment { start: 12, end: 17 })
+    ));
+}
+
+#[test]
+fn test_parse_pairs_errors() {
+    let e = parse_pairs("2-4,6-8\n2-3,4-x\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 7, "x"));
+
+    let e = parse_pairs("2-4,6-8\n2-3\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "2-3"));
+}
+
+// Parses one pair of assignments per line, e.g. "2-4,6-8".
+pub fn parse_pairs(input: &str) -> Result<Vec<(Assignment, Assignment)>, ParseError> {
+    let mut pairs = Vec::new();
+    for line in input.lines().filter(|line| !line.is_empty()) {
+        let mut chunks = line.split(',');
+        if let (Some(x), Some(y), None) = (chunks.next(), chunks.next(), chunks.next()) {
+            let assignment = |s: &str| s.parse::<Assignment>().map_err(|e| e.within(input, s));
+            pairs.push((assignment(x)?, assignment(y)?));
+        } else {
+            return Err(ParseError::at(input, line, "expected a pair of ranges"));
+        }
+    }
+    Ok(pairs)
+}
+
+pub fn part_1(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs
+        .iter()
+        .filter(|(x, y)| x.fully_encloses(y) || y.fully_encloses(x))
+        .count()
+}
+
+pub fn part_2(pairs: &[(Assignment, Assignment)]) -> usize {
+    pairs.iter().filter(|(x, y)| x.overlaps(y)).count()
+}
+
+#[derive(Debug, Default)]
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<(Assignment, Assignment)>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_pairs(input)?)
+    }
+
+    fn part1(&self, pairs: &Self::Input) -> Result<usize, common::Error> {
+        Ok(part_1(pairs))
+    }
+
+    fn part2(&self, pairs: &Self::Input) -> Result<usize, common::Error> {
+        Ok(part_2(pairs))
+    }
+}
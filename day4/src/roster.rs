@@ -0,0 +1,237 @@
+// Questions about the whole roster of assignments rather than one pair:
+// how many sections anyone covers, which ones several elves cover, and
+// which ones nobody does.
+//
+//     let roster: Vec<Assignment> = pairs.iter().flat_map(|&(x, y)| [x, y]).collect();
+//     let covered = AssignmentSet::from_iter(roster.iter().copied());
+//     println!("{} sections, {} gaps", covered.len(), covered.gaps().len());
+
+use crate::Assignment;
+
+/// Sections as a list of ranges, kept sorted, with none empty and none
+/// overlapping or touching another, so that each set has one way to be
+/// written.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AssignmentSet {
+    ranges: Vec<Assignment>,
+}
+
+impl AssignmentSet {
+    pub fn new() -> Self {
+        AssignmentSet::default()
+    }
+
+    pub fn ranges(&self) -> &[Assignment] {
+        &self.ranges
+    }
+
+    /// The number of sections covered.
+    pub fn len(&self) -> u64 {
+        self.ranges.iter().map(Assignment::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, section: u32) -> bool {
+        // The last range starting at or before the section.
+        let i = self.ranges.partition_point(|range| range.start <= section);
+        i > 0 && self.ranges[i - 1].contains(section)
+    }
+
+    pub fn insert(&mut self, assignment: Assignment) {
+        self.ranges.push(assignment);
+        self.ranges = normalize(std::mem::take(&mut self.ranges));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let ranges = self.ranges.iter().chain(&other.ranges).copied().collect();
+        AssignmentSet {
+            ranges: normalize(ranges),
+        }
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            ranges.extend(a.intersection(&b));
+            // Whichever ends first can't meet anything further on.
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        AssignmentSet { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for range in &self.ranges {
+            let mut pieces = vec![*range];
+            for taken in other.ranges.iter().filter(|taken| taken.overlaps(range)) {
+                pieces = pieces
+                    .iter()
+                    .flat_map(|piece| piece.difference(taken))
+                    .collect();
+            }
+            ranges.extend(pieces);
+        }
+        AssignmentSet { ranges }
+    }
+
+    /// The sections nobody covers between the first section covered and
+    /// the last.
+    pub fn gaps(&self) -> Self {
+        let ranges = self
+            .ranges
+            .windows(2)
+            .map(|pair| Assignment::new(pair[0].end + 1, pair[1].start - 1))
+            .collect();
+        AssignmentSet { ranges }
+    }
+
+    /// The sections that more than `k` of the assignments cover.  With k
+    /// 0 that's every section covered, and with k 1 the ones that elves
+    /// share.
+    pub fn covered_by_more_than<'a>(
+        assignments: impl IntoIterator<Item = &'a Assignment>,
+        k: usize,
+    ) -> Self {
+        // Each assignment adds an elf at its start and takes one away just
+        // after its end; u64, since that can be past u32::MAX.
+        let mut changes: Vec<(u64, isize)> = assignments
+            .into_iter()
+            .filter(|assignment| !assignment.is_empty())
+            .flat_map(|assignment| {
+                [
+                    (assignment.start as u64, 1),
+                    (assignment.end as u64 + 1, -1),
+                ]
+            })
+            .collect();
+        changes.sort_unstable();
+
+        let mut ranges = Vec::new();
+        let mut elves = 0;
+        let mut start = None;
+        for (i, &(section, change)) in changes.iter().enumerate() {
+            elves += change;
+            // Only look once every change at this section is in.
+            if changes.get(i + 1).is_some_and(|&(next, _)| next == section) {
+                continue;
+            }
+            match start {
+                None if elves > k as isize => start = Some(section),
+                Some(from) if elves <= k as isize => {
+                    ranges.push(Assignment::new(from as u32, (section - 1) as u32));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        AssignmentSet { ranges }
+    }
+}
+
+impl FromIterator<Assignment> for AssignmentSet {
+    fn from_iter<I: IntoIterator<Item = Assignment>>(iter: I) -> Self {
+        AssignmentSet {
+            ranges: normalize(iter.into_iter().collect()),
+        }
+    }
+}
+
+// Sorts the ranges and merges any that overlap or touch.
+fn normalize(mut ranges: Vec<Assignment>) -> Vec<Assignment> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_unstable_by_key(|range| range.start);
+    let mut merged: Vec<Assignment> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last().and_then(|last| last.merge(&range)) {
+            Some(both) => *merged.last_mut().unwrap() = both,
+            None => merged.push(range),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pairs;
+
+    const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    fn set(ranges: &[(u32, u32)]) -> AssignmentSet {
+        ranges
+            .iter()
+            .map(|&(start, end)| Assignment::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_normalize() {
+        let covered = set(&[(6, 8), (2, 3), (4, 4), (10, 9), (11, 12)]);
+        // 2-3 and 4-4 touch, so merge; 10-9 is empty.
+        let ranges = [
+            Assignment::new(2, 4),
+            Assignment::new(6, 8),
+            Assignment::new(11, 12),
+        ];
+        assert_eq!(covered.ranges(), ranges);
+        assert_eq!(covered.len(), 8);
+        assert!(covered.contains(7));
+        assert!(!covered.contains(5));
+        assert!(!covered.contains(1));
+
+        let mut inserted = covered.clone();
+        inserted.insert(Assignment::new(5, 10));
+        assert_eq!(inserted, set(&[(2, 12)]));
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        let a = set(&[(1, 5), (10, 15)]);
+        let b = set(&[(4, 11), (14, 20)]);
+        assert_eq!(a.union(&b), set(&[(1, 20)]));
+        assert_eq!(a.intersection(&b), set(&[(4, 5), (10, 11), (14, 15)]));
+        assert_eq!(a.difference(&b), set(&[(1, 3), (12, 13)]));
+        assert_eq!(b.difference(&a), set(&[(6, 9), (16, 20)]));
+        assert_eq!(a.gaps(), set(&[(6, 9)]));
+        assert!(AssignmentSet::new().gaps().is_empty());
+    }
+
+    #[test]
+    fn test_roster() {
+        let pairs = parse_pairs(EXAMPLE).unwrap();
+        let roster: Vec<Assignment> = pairs.iter().flat_map(|&(x, y)| [x, y]).collect();
+        let covered: AssignmentSet = roster.iter().copied().collect();
+        assert_eq!(covered, set(&[(2, 9)]));
+        assert_eq!(AssignmentSet::covered_by_more_than(&roster, 0), covered);
+        // 6 is in 6-8, 5-7, 2-8, 3-7, 6-6, 4-6, 2-6 and 4-8.
+        assert_eq!(
+            AssignmentSet::covered_by_more_than(&roster, 7),
+            set(&[(6, 6)])
+        );
+        assert!(AssignmentSet::covered_by_more_than(&roster, 8).is_empty());
+
+        let edges = [
+            Assignment::new(u32::MAX - 1, u32::MAX),
+            Assignment::new(u32::MAX, u32::MAX),
+        ];
+        assert_eq!(
+            AssignmentSet::covered_by_more_than(&edges, 1),
+            set(&[(u32::MAX, u32::MAX)])
+        );
+    }
+}
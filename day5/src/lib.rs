@@ -0,0 +1,214 @@
+use common::{ParseError, Solution};
+use std::error::Error;
+use std::str::FromStr;
+
+#[derive(Debug, Clone)]
+pub struct State {
+    columns: Vec<Vec<char>>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Move {
+    how_many: usize,
+    from: usize,
+    to: usize,
+}
+
+impl FromStr for Move {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let chunks: Vec<&str> = s.split_whitespace().collect();
+        // Stacks are numbered from 1.
+        let stack = |n: &str| match n.parse::<usize>() {
+            Ok(n) if n > 0 => Ok(n - 1),
+            _ => Err(ParseError::at(s, n, "expected a stack number")),
+        };
+        match chunks[..] {
+            ["move", how_many, "from", from, "to", to] => Ok(Move {
+                how_many: how_many
+                    .parse()
+                    .map_err(|_| ParseError::at(s, how_many, "expected a crate count"))?,
+                from: stack(from)?,
+                to: stack(to)?,
+            }),
+            _ => Err(ParseError::at(s, s, "expected \"move N from A to B\"")),
+        }
+    }
+}
+
+#[test]
+fn parse_move() {
+    assert_eq!(
+        "move 3 from 8 to 9".parse::<Move>(),
+        Ok(Move {
+            how_many: 3,
+            from: 7,
+            to: 8
+        })
+    );
+    assert_eq!("move 3 from 0 to 9".parse::<Move>().unwrap_err().text, "0");
+}
+
+#[test]
+fn parse_input_errors() {
+    let drawing = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+    let e = parse_input(&format!(
+        "{}move 1 from 2 to 1\nmove x from 1 to 3\n",
+        drawing
+    ))
+    .unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (7, 6, "x"));
+
+    let e = parse_input(&format!("{}move 1 from 4 to 1\n", drawing)).unwrap_err();
+    assert_eq!(e.line, 6);
+
+    assert!(parse_input("move 1 from 2 to 1\n").is_err());
+
+    // Stack 2 has three crates, then two after the first move.
+    let e = parse_input(&format!(
+        "{}move 1 from 2 to 1\nmove 3 from 2 to 2\n",
+        drawing
+    ))
+    .unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (7, 6, "3"));
+    assert_eq!(e.message, "stack 2 has only 2 crates");
+}
+
+fn apply_move_part_1(
+    state: &mut State,
+    &Move { how_many, from, to }: &Move,
+) -> Result<(), Box<dyn Error>> {
+    for _ in 0..how_many {
+        let elt = state.columns[from]
+            .pop()
+            .ok_or(format!("not enough columns in {}", from))?;
+        state.columns[to].push(elt);
+    }
+    Ok(())
+}
+
+fn apply_move_part_2(
+    state: &mut State,
+    &Move { how_many, from, to }: &Move,
+) -> Result<(), Box<dyn Error>> {
+    let mut to_move = Vec::new();
+    for _ in 0..how_many {
+        to_move.push(
+            state.columns[from]
+                .pop()
+                .ok_or(format!("not enough columns in {}", from))?,
+        );
+    }
+    to_move.reverse();
+    state.columns[to].extend(to_move);
+
+    Ok(())
+}
+
+// Parses the drawing of the starting stacks, whose last line numbers them.
+fn parse_columns(drawing: &str) -> Result<State, ParseError> {
+    let saved_lines: Vec<&str> = drawing.lines().collect();
+    let column_number_line = saved_lines
+        .last()
+        .ok_or_else(|| ParseError::new(1, 1, "", "expected a drawing of the stacks"))?;
+    let mut indices: Vec<usize> = Vec::new();
+    for (index, char) in column_number_line.chars().enumerate() {
+        if char.is_numeric() {
+            indices.push(index);
+        }
+    }
+
+    let mut columns: Vec<Vec<char>> = vec![vec![]; indices.len()];
+
+    for line in saved_lines[..(saved_lines.len() - 1)].iter().rev() {
+        let chars: Vec<char> = line.chars().collect();
+        for (column, &index) in indices.iter().enumerate() {
+            if let Some(ch) = chars.get(index) {
+                if ch.is_ascii_alphabetic() {
+                    columns[column].push(*ch);
+                }
+            }
+        }
+    }
+
+    Ok(State { columns })
+}
+
+fn top_crates(state: &State) -> Result<String, Box<dyn Error>> {
+    let mut result = String::new();
+    for column in &state.columns {
+        result.push(*column.last().ok_or("unexpected empty!")?);
+    }
+    Ok(result)
+}
+
+// Parses the starting stacks, then one move per line.  Every move must
+// have enough crates to take, so that both parts can make them all.
+pub fn parse_input(input: &str) -> Result<(State, Vec<Move>), ParseError> {
+    let (drawing, procedure) = input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::at(input, &input[input.len()..], "expected a blank line"))?;
+    let state = parse_columns(drawing)?;
+    // How many crates each stack has as the moves go.
+    let mut heights: Vec<usize> = state.columns.iter().map(Vec::len).collect();
+    let mut moves = Vec::new();
+    for line in procedure.lines().filter(|line| !line.trim().is_empty()) {
+        let next_move = line.parse::<Move>().map_err(|e| e.within(input, line))?;
+        if next_move.from >= state.columns.len() || next_move.to >= state.columns.len() {
+            return Err(ParseError::at(input, line, "no such stack"));
+        }
+        if heights[next_move.from] < next_move.how_many {
+            let count = line.split_whitespace().nth(1).unwrap_or(line);
+            return Err(ParseError::at(
+                input,
+                count,
+                format!(
+                    "stack {} has only {} crates",
+                    next_move.from + 1,
+                    heights[next_move.from]
+                ),
+            ));
+        }
+        heights[next_move.from] -= next_move.how_many;
+        heights[next_move.to] += next_move.how_many;
+        moves.push(next_move);
+    }
+    Ok((state, moves))
+}
+
+pub fn part_1((state, moves): &(State, Vec<Move>)) -> Result<String, Box<dyn Error>> {
+    let mut state = state.clone();
+    for next_move in moves {
+        apply_move_part_1(&mut state, next_move)?;
+    }
+    top_crates(&state)
+}
+
+pub fn part_2((state, moves): &(State, Vec<Move>)) -> Result<String, Box<dyn Error>> {
+    let mut state = state.clone();
+    for next_move in moves {
+        apply_move_part_2(&mut state, next_move)?;
+    }
+    top_crates(&state)
+}
+
+#[derive(Debug, Default)]
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (State, Vec<Move>);
+    type Part1 = String;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Result<String, common::Error> {
+        part_1(input)
+    }
+
+    fn part2(&self, input: &Self::Input) -> Result<String, common::Error> {
+        part_2(input)
+    }
+}
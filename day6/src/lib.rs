@@ -0,0 +1,60 @@
+use common::Solution;
+
+pub fn find_marker_end(s: &str, len: usize) -> Option<usize> {
+    for (index, window) in s.as_bytes().windows(len).enumerate() {
+        if is_all_different(window) {
+            return Some(index + len);
+        }
+    }
+    None
+}
+
+fn is_all_different(chars: &[u8]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    for &ch in chars {
+        if seen.contains(&ch) {
+            return false;
+        }
+        seen.insert(ch);
+    }
+    true
+}
+
+#[derive(Debug, Default)]
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(&self, datastream: &Self::Input) -> Result<usize, common::Error> {
+        Ok(find_marker_end(datastream, 4).ok_or("no start-of-packet marker")?)
+    }
+
+    fn part2(&self, datastream: &Self::Input) -> Result<usize, common::Error> {
+        Ok(find_marker_end(datastream, 14).ok_or("no start-of-message marker")?)
+    }
+}
+
+#[test]
+fn test_start_of_packet() {
+    assert_eq!(
+        find_marker_end("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4),
+        Some(7)
+    );
+    assert_eq!(find_marker_end("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+    assert_eq!(find_marker_end("nppdvjthqldpwncqszvftbrmjlhg", 4), Some(6));
+    assert_eq!(
+        find_marker_end("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg", 4),
+        Some(10)
+    );
+    assert_eq!(
+        find_marker_end("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 4),
+        Some(11)
+    );
+}
@@ -0,0 +1,325 @@
+use common::{ParseError, Solution};
+use std::iter::Peekable;
+
+fn directory_from_str(s: &str) -> Result<Entry, ParseError> {
+    let mut chunks = s.split_whitespace();
+    match (chunks.next(), chunks.next(), chunks.next()) {
+        (Some("dir"), Some(name), None) => Ok(Entry::Directory { name: name.into() }),
+        _ => Err(ParseError::at(s, s, "expected \"dir NAME\"")),
+    }
+}
+
+fn file_from_str(s: &str) -> Result<Entry, ParseError> {
+    let mut chunks = s.split_whitespace();
+    match (chunks.next(), chunks.next(), chunks.next()) {
+        (Some(size), Some(name), None) => {
+            let size: usize = size
+                .parse::<usize>()
+                .map_err(|_| ParseError::at(s, size, "expected a file size"))?;
+            Ok(Entry::File {
+                size,
+                name: name.into(),
+            })
+        }
+        _ => Err(ParseError::at(s, s, "expected \"SIZE NAME\"")),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Entry {
+    Directory { name: String },
+    File { size: usize, name: String },
+}
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Listing { entries: Vec<Entry> },
+    ChangeDir { name: String },
+}
+
+// Reads the terminal output as commands, each with the line it was typed on.
+struct CommandStream<'a> {
+    input: &'a str,
+    inner: Peekable<std::str::Lines<'a>>,
+}
+
+impl<'a> CommandStream<'a> {
+    fn new(input: &'a str) -> Self {
+        CommandStream {
+            input,
+            inner: input.lines().peekable(),
+        }
+    }
+
+    fn error(&self, fragment: &str, message: &str) -> ParseError {
+        ParseError::at(self.input, fragment, message)
+    }
+}
+
+impl<'a> Iterator for CommandStream<'a> {
+    type Item = Result<(&'a str, Command), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.inner.find(|line| !line.trim().is_empty())?;
+        if !line.starts_with("$ ") {
+            return Some(Err(self.error(line, "expected a command")));
+        }
+        let chunks: Vec<&str> = line.split_whitespace().collect();
+        let command = match &chunks[..] {
+            [_, "cd", name] => Command::ChangeDir {
+                name: name.to_string(),
+            },
+            [_, "ls"] => {
+                let mut entries = Vec::new();
+                while let Some(&line) = self.inner.peek() {
+                    if line.starts_with('$') {
+                        break;
+                    }
+                    if !line.trim().is_empty() {
+                        let entry = if line.starts_with("dir") {
+                            directory_from_str(line)
+                        } else {
+                            file_from_str(line)
+                        };
+                        entries.push(entry.map_err(|e| e.within(self.input, line)));
+                    }
+                    self.inner.next();
+                }
+                match entries.into_iter().collect() {
+                    Ok(entries) => Command::Listing { entries },
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+            _ => return Some(Err(self.error(line, "expected cd or ls"))),
+        };
+        Some(Ok((line, command)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Content {
+    Directory { name: String, index: usize },
+    File { size: usize, name: String },
+}
+
+#[derive(Debug, PartialEq)]
+pub struct FileSystem {
+    breadcrumb: Vec<usize>,
+    contents: Vec<Vec<Content>>,
+}
+
+impl FileSystem {
+    fn new() -> Self {
+        FileSystem {
+            breadcrumb: vec![0],
+            contents: vec![vec![]],
+        }
+    }
+
+    fn list_contents(&self, index: usize) -> &[Content] {
+        &self.contents[index][..]
+    }
+
+    fn move_up(&mut self) {
+        self.breadcrumb.pop();
+        if self.breadcrumb.is_empty() {
+            self.breadcrumb.push(0);
+        }
+    }
+
+    fn move_in(&mut self, name: &str) -> Result<(), String> {
+        let slot = self
+            .contents
+            .get(self.breadcrumb.last().copied().expect("empty"))
+            .expect("out of range");
+        let index = slot
+            .iter()
+            .find_map(|elt| match elt {
+                Content::File { .. } => None,
+                Content::Directory {
+                    name: dir_name,
+                    index,
+                } => {
+                    if dir_name == name {
+                        Some(index)
+                    } else {
+                        None
+                    }
+                }
+            })
+            .ok_or_else(|| format!("no directory named {:?} here", name))?;
+        self.breadcrumb.push(*index);
+        Ok(())
+    }
+
+    fn move_root(&mut self) {
+        self.breadcrumb.clear();
+        self.breadcrumb.push(0);
+    }
+
+    fn add_entry_as_content(&mut self, entry: &Entry) {
+        let next_index = self.contents.len();
+        let slot = self
+            .contents
+            .get_mut(self.breadcrumb.last().copied().expect("empty"))
+            .expect("out of range");
+        match entry {
+            Entry::File { name, size } => {
+                slot.push(Content::File {
+                    name: name.clone(),
+                    size: *size,
+                });
+            }
+            Entry::Directory { name } => {
+                slot.push(Content::Directory {
+                    name: name.clone(),
+                    index: next_index,
+                });
+                self.contents.push(vec![]);
+            }
+        }
+    }
+}
+
+fn total_size(fs: &FileSystem, index: usize) -> usize {
+    let mut result = 0;
+    for content in fs.list_contents(index) {
+        result += match content {
+            Content::Directory { index, .. } => total_size(fs, *index),
+            Content::File { size, .. } => *size,
+        };
+    }
+    result
+}
+
+fn dispatch(fs: &mut FileSystem, command: Command) -> Result<(), String> {
+    match command {
+        Command::Listing { entries } => {
+            for entry in entries {
+                fs.add_entry_as_content(&entry);
+            }
+        }
+        Command::ChangeDir { name } => {
+            if name == "/" {
+                fs.move_root();
+            } else if name == ".." {
+                fs.move_up();
+            } else {
+                fs.move_in(&name)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn parse_file_system(input: &str) -> Result<FileSystem, ParseError> {
+    let mut fs = FileSystem::new();
+    for command in CommandStream::new(input) {
+        let (line, command) = command?;
+        dispatch(&mut fs, command).map_err(|e| ParseError::at(input, line, e))?;
+    }
+    Ok(fs)
+}
+
+pub fn part_1(fs: &FileSystem) -> usize {
+    (0..(fs.contents.len()))
+        .filter_map(|index| {
+            let size = total_size(fs, index);
+            if size <= 100000 {
+                Some(size)
+            } else {
+                None
+            }
+        })
+        .sum::<usize>()
+}
+
+pub fn part_2(fs: &FileSystem) -> Option<usize> {
+    let unused = 70000000usize.saturating_sub(total_size(fs, 0));
+    // If there's room already, any directory will do.
+    let target = 30000000usize.saturating_sub(unused);
+    (0..(fs.contents.len()))
+        .filter_map(|index| {
+            let size = total_size(fs, index);
+            if size >= target {
+                Some(size)
+            } else {
+                None
+            }
+        })
+        .min()
+}
+
+#[derive(Debug, Default)]
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = FileSystem;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_file_system(input)?)
+    }
+
+    fn part1(&self, fs: &Self::Input) -> Result<usize, common::Error> {
+        Ok(part_1(fs))
+    }
+
+    fn part2(&self, fs: &Self::Input) -> Result<usize, common::Error> {
+        Ok(part_2(fs).ok_or("no directory is large enough")?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn test_example() {
+        let fs = parse_file_system(EXAMPLE).unwrap();
+        assert_eq!(part_1(&fs), 95437);
+        assert_eq!(part_2(&fs), Some(24933642));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = parse_file_system("$ cd /\n$ ls\nabc b.txt\n").unwrap_err();
+        assert_eq!((e.line, e.column, e.text.as_str()), (3, 1, "abc"));
+
+        let e = parse_file_system("$ cd /\n$ cd nowhere\n").unwrap_err();
+        assert_eq!((e.line, e.column), (2, 1));
+
+        let e = parse_file_system("$ cd /\n$ rm -rf\n").unwrap_err();
+        assert_eq!(e.line, 2);
+
+        let e = parse_file_system("cd /\n").unwrap_err();
+        assert_eq!(e.line, 1);
+    }
+}
@@ -0,0 +1,158 @@
+use common::{parallel, ParseError, Solution};
+use grid::image::Palette;
+use grid::{Grid, Pos};
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug)]
+pub struct HeightMap(Grid<u8>);
+
+impl FromStr for HeightMap {
+    type Err = ParseError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(HeightMap(Grid::parse(s, |ch| {
+            ch.to_digit(10).map(|x| x as u8)
+        })?))
+    }
+}
+
+// The heights, one digit per tree, as in the input.
+impl fmt::Display for HeightMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .render(|&h| char::from_digit(h as u32, 10).unwrap_or('?'))
+        )
+    }
+}
+
+// A heatmap of the heights, from dark green for the shortest trees to pale
+// green for the tallest.
+pub fn palette() -> Palette {
+    Palette::new([0, 0, 0]).gradient('0'..='9', [10, 40, 10], [190, 240, 160])
+}
+
+impl HeightMap {
+    fn coords(&self) -> impl Iterator<Item = Pos> {
+        self.0.positions()
+    }
+
+    fn get(&self, p: Pos) -> Option<u8> {
+        self.0.get(p).copied()
+    }
+
+    // The heights of the trees from p (exclusive) to the edge, in each
+    // direction.
+    fn walks_to_edge(&self, p: Pos) -> [impl Iterator<Item = u8> + '_; 4] {
+        Pos::ORTHOGONAL.map(|step| self.0.ray(p, step).map(|(_, &h)| h))
+    }
+}
+
+fn is_visible(hmap: &HeightMap, p: Pos) -> bool {
+    if let Some(h) = hmap.get(p) {
+        for mut slice in hmap.walks_to_edge(p) {
+            if !slice.any(|other| other >= h) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+pub fn part_1(hmap: &HeightMap) -> usize {
+    hmap.coords().filter(|&p| is_visible(hmap, p)).count()
+}
+
+#[test]
+fn test_part_1() {
+    let example_map = HeightMap(
+        Grid::from_rows(vec![
+            vec![3, 0, 3, 7, 3],
+            vec![2, 5, 5, 1, 2],
+            vec![6, 5, 3, 3, 2],
+            vec![3, 3, 5, 4, 9],
+            vec![3, 5, 3, 9, 0],
+        ])
+        .unwrap(),
+    );
+
+    assert_eq!(part_1(&example_map), 21);
+}
+
+fn scenic_score(hmap: &HeightMap, p: Pos) -> usize {
+    if let Some(h) = hmap.get(p) {
+        hmap.walks_to_edge(p)
+            .map(|slice| {
+                let mut count = 0;
+                for other in slice {
+                    count += 1;
+                    if other >= h {
+                        break;
+                    }
+                }
+                count
+            })
+            .into_iter()
+            .product()
+    } else {
+        1
+    }
+}
+
+#[test]
+fn test_scenic_score() -> Result<(), Box<dyn std::error::Error>> {
+    let hmap: HeightMap = "30373
+25512
+65332
+33549
+35390"
+        .parse()?;
+    assert_eq!(scenic_score(&hmap, Pos::new(2, 3)), 8);
+    Ok(())
+}
+
+#[test]
+fn test_display() -> Result<(), Box<dyn std::error::Error>> {
+    let hmap: HeightMap = "303\n255\n".parse()?;
+    assert_eq!(hmap.to_string(), "303\n255\n");
+    Ok(())
+}
+
+#[test]
+fn test_parse_errors() {
+    let e = "303\n2x5\n".parse::<HeightMap>().unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 2, "x"));
+
+    let e = "303\n25\n".parse::<HeightMap>().unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "25"));
+}
+
+pub fn part_2(hmap: &HeightMap) -> Option<usize> {
+    let coords: Vec<Pos> = hmap.coords().collect();
+    parallel::map(&coords, |&p| scenic_score(hmap, p))
+        .into_iter()
+        .max()
+}
+
+#[derive(Debug, Default)]
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = HeightMap;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(input.parse()?)
+    }
+
+    fn part1(&self, hmap: &Self::Input) -> Result<usize, common::Error> {
+        Ok(part_1(hmap))
+    }
+
+    fn part2(&self, hmap: &Self::Input) -> Result<usize, common::Error> {
+        Ok(part_2(hmap).ok_or("empty")?)
+    }
+}
@@ -0,0 +1,387 @@
+use common::observe::Observer;
+use common::{ParseError, Solution};
+use std::collections::HashSet;
+#[cfg(test)]
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Movement {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+// x grows to the right and y grows upward.
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Pos {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Pos {
+    fn new(row: isize, col: isize) -> Self {
+        Pos { x: row, y: col }
+    }
+
+    fn is_adjacent_to(&self, other: &Self) -> bool {
+        self.x.abs_diff(other.x) <= 1 && self.y.abs_diff(other.y) <= 1
+    }
+
+    fn apply_movement(&self, movement: &Movement) -> Self {
+        match movement {
+            Movement::Left => Self {
+                x: self.x - 1,
+                ..*self
+            },
+            Movement::Right => Self {
+                x: self.x + 1,
+                ..*self
+            },
+            Movement::Up => Self {
+                y: self.y + 1,
+                ..*self
+            },
+            Movement::Down => Self {
+                y: self.y - 1,
+                ..*self
+            },
+        }
+    }
+}
+
+#[test]
+fn test_is_adjacent_to() {
+    assert!(Pos::new(3, 4).is_adjacent_to(&Pos::new(3, 4)));
+    assert!(Pos::new(3, 4).is_adjacent_to(&Pos::new(3, 5)));
+
+    assert!(!Pos::new(3, 4).is_adjacent_to(&Pos::new(3, 6)));
+}
+
+#[test]
+fn test_apply_movement() {
+    assert_eq!(
+        Pos::new(0, 0).apply_movement(&Movement::Left),
+        Pos::new(-1, 0)
+    );
+
+    assert_eq!(
+        Pos::new(0, 0).apply_movement(&Movement::Right),
+        Pos::new(1, 0)
+    );
+    assert_eq!(Pos::new(0, 0).apply_movement(&Movement::Up), Pos::new(0, 1));
+    assert_eq!(
+        Pos::new(0, 0).apply_movement(&Movement::Down),
+        Pos::new(0, -1)
+    );
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BoardState {
+    knots: Vec<Pos>,
+}
+
+// Simulate the pulling motion of the head to move the tail.
+fn pull_tail(head: &Pos, tail: &Pos) -> Pos {
+    let (mut delta_x, mut delta_y) = (head.x - tail.x, head.y - tail.y);
+    if delta_x.abs() > 1 {
+	delta_x /= delta_x.abs();
+    }
+    if delta_y.abs() > 1 {
+	delta_y /= delta_y.abs();
+    }
+    Pos { x: delta_x + tail.x,
+	  y: delta_y + tail.y }
+}
+
+impl BoardState {
+    fn new(n: usize) -> Self {
+        Self {
+            knots: (0..n).map(|_| Pos::new(0, 0)).collect::<Vec<Pos>>(),
+        }
+    }
+
+    fn apply_movement(&mut self, movement: &Movement) {
+        if self.knots.is_empty() {
+            return;
+        }
+
+        self.knots[0] = self.knots[0].apply_movement(movement);
+
+        for i in 1..self.knots.len() {
+            if !self.knots[i].is_adjacent_to(&self.knots[i - 1]) {
+                self.knots[i] = pull_tail(&self.knots[i-1], &self.knots[i]);
+            }
+        }
+    }
+
+    // The knots, head first.
+    pub fn knots(&self) -> &[Pos] {
+        &self.knots
+    }
+}
+
+// Draws the rope as in the puzzle: H for the head, then each knot's number,
+// or T for the tail of a two-knot rope, and s for the start.  Where knots
+// overlap, the one nearer the head is drawn.
+impl fmt::Display for BoardState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let start = Pos::new(0, 0);
+        let all = || self.knots.iter().chain([&start]);
+        let (min_x, max_x) = (all().map(|p| p.x).min(), all().map(|p| p.x).max());
+        let (min_y, max_y) = (all().map(|p| p.y).min(), all().map(|p| p.y).max());
+        let (Some(min_x), Some(max_x), Some(min_y), Some(max_y)) = (min_x, max_x, min_y, max_y)
+        else {
+            return Ok(());
+        };
+        for y in (min_y..=max_y).rev() {
+            for x in min_x..=max_x {
+                let p = Pos::new(x, y);
+                let ch = match self.knots.iter().position(|knot| *knot == p) {
+                    Some(0) => 'H',
+                    Some(1) if self.knots.len() == 2 => 'T',
+                    Some(i) => char::from_digit(i as u32 % 10, 10).unwrap_or('#'),
+                    None if p == start => 's',
+                    None => '.',
+                };
+                write!(f, "{}", ch)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_apply_movement_board_right() {
+    let mut board = BoardState::new(2);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Right);
+    assert_eq!(
+        board,
+        BoardState {
+            knots: vec![Pos { x: 4, y: 0 }, Pos { x: 3, y: 0 }],
+        }
+    );
+}
+
+#[test]
+fn test_apply_movement_board_right_three_knots() {
+    let mut board = BoardState::new(3);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Right);
+    assert_eq!(
+        board,
+        BoardState {
+            knots: vec![Pos { x: 4, y: 0 }, Pos { x: 3, y: 0 }, Pos { x: 2, y: 0 }],
+        }
+    );
+}
+
+#[test]
+fn test_apply_movement_board_diagonal() {
+    let mut board = BoardState::new(2);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Up);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Up);
+    assert_eq!(
+        board,
+        BoardState {
+            knots: vec![Pos { x: 2, y: 2 }, Pos { x: 1, y: 1 }],
+        }
+    );
+}
+
+pub fn watch_the_tail(movements: &[Movement], knot_size: usize) -> usize {
+    watch_the_tail_with(movements, knot_size, &mut ())
+}
+
+// As watch_the_tail, showing the observer the rope after each step.
+pub fn watch_the_tail_with(
+    movements: &[Movement],
+    knot_size: usize,
+    observer: &mut impl Observer<BoardState>,
+) -> usize {
+    let mut tail_visited = HashSet::new();
+    let mut board = BoardState::new(knot_size);
+    tail_visited.insert(board.knots.last().unwrap().clone());
+    for (step, m) in movements.iter().enumerate() {
+        board.apply_movement(m);
+        tail_visited.insert(board.knots.last().unwrap().clone());
+        observer.observe(step + 1, &board);
+    }
+    tail_visited.len()
+}
+
+#[test]
+fn test_knots_stay_together() -> Result<(), Box<dyn Error>> {
+    use common::observe::{Counter, Invariant};
+
+    let mut together = Invariant::new("every knot next to the one before", |board: &BoardState| {
+        board
+            .knots()
+            .windows(2)
+            .all(|pair| pair[0].is_adjacent_to(&pair[1]))
+    });
+    let mut counter = Counter::default();
+    let movements = parse_movements("R 4\nU 4\nL 3\nD 1\nR 4\nD 1\nL 5\nR 2")?;
+    watch_the_tail_with(&movements, 10, &mut (&mut together, &mut counter));
+    assert_eq!(counter.steps, 24);
+    Ok(())
+}
+
+#[test]
+fn test_display_board() {
+    let mut board = BoardState::new(2);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Right);
+    board.apply_movement(&Movement::Up);
+    assert_eq!(board.to_string(), "..H\nsT.\n");
+}
+
+pub fn parse_movements(s: &str) -> Result<Vec<Movement>, ParseError> {
+    let mut movements = Vec::new();
+    for line in s.lines().filter(|line| !line.trim().is_empty()) {
+        if let [direction, count] = line.split_whitespace().collect::<Vec<&str>>()[..] {
+            let cmd = match direction {
+                "L" => Ok(Movement::Left),
+                "R" => Ok(Movement::Right),
+                "U" => Ok(Movement::Up),
+                "D" => Ok(Movement::Down),
+                _ => Err(ParseError::at(s, direction, "expected L, R, U or D")),
+            }?;
+            let count: u32 = count
+                .parse()
+                .map_err(|_| ParseError::at(s, count, "expected a step count"))?;
+            for _ in 0..count {
+                movements.push(cmd.clone());
+            }
+        } else {
+            return Err(ParseError::at(
+                s,
+                line,
+                "expected a direction and a step count",
+            ));
+        }
+    }
+    Ok(movements)
+}
+
+#[test]
+fn test_parse_movements_errors() {
+    let e = parse_movements("R 4\nU x\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 3, "x"));
+
+    let e = parse_movements("R 4\nQ 1\n").unwrap_err();
+    assert_eq!((e.line, e.column, e.text.as_str()), (2, 1, "Q"));
+
+    let e = parse_movements("R 4\nR 4 4\n").unwrap_err();
+    assert_eq!((e.line, e.column), (2, 1));
+}
+
+#[derive(Debug, Default)]
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<Movement>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, common::Error> {
+        Ok(parse_movements(input)?)
+    }
+
+    fn part1(&self, movements: &Self::Input) -> Result<usize, common::Error> {
+        Ok(watch_the_tail(movements, 2))
+    }
+
+    fn part2(&self, movements: &Self::Input) -> Result<usize, common::Error> {
+        Ok(watch_the_tail(movements, 10))
+    }
+}
+
+#[test]
+fn test_watch_the_tail() -> Result<(), Box<dyn Error>> {
+    assert_eq!(watch_the_tail(&parse_movements("R 1",)?, 3), 1);
+    assert_eq!(watch_the_tail(&parse_movements("R 2",)?, 3), 1);
+    assert_eq!(watch_the_tail(&parse_movements("R 3",)?, 3), 2);
+    assert_eq!(
+        watch_the_tail(&parse_movements("R 3\nL 1\nR 1\nL 1\n",)?, 3),
+        2
+    );
+    Ok(())
+}
+
+#[test]
+fn test_part_1_small_example() -> Result<(), Box<dyn Error>> {
+    let input = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+    assert_eq!(watch_the_tail(&parse_movements(input)?, 2), 13);
+
+    Ok(())
+}
+
+#[test]
+fn test_part_2_small_example() -> Result<(), Box<dyn Error>> {
+    let input = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+    assert_eq!(watch_the_tail(&parse_movements(input)?, 10), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_part_2_large_example_beginning_1() -> Result<(), Box<dyn Error>> {
+    let input = "
+R 5
+";
+    assert_eq!(watch_the_tail(&parse_movements(input,)?, 10,), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_part_2_large_example_beginning_2() -> Result<(), Box<dyn Error>> {
+    let input = "
+R 5
+U 6
+";
+    assert_eq!(watch_the_tail(&parse_movements(input,)?, 10,), 1);
+
+    Ok(())
+}
+
+#[test]
+fn test_part_2_large_example() -> Result<(), Box<dyn Error>> {
+    let input = "
+R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+";
+    assert_eq!(watch_the_tail(&parse_movements(input,)?, 10,), 36);
+
+    Ok(())
+}
@@ -0,0 +1,316 @@
+use crate::Pos;
+use common::ParseError;
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid with a value in every cell, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Builds a grid from its rows, or returns `None` if they aren't all
+    /// the same width.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let height = rows.len();
+        Some(Grid {
+            cells: rows.into_iter().flatten().collect(),
+            width,
+            height,
+        })
+    }
+
+    /// Reads a grid with one cell per character, rejecting characters that
+    /// `cell` doesn't accept and rows of different widths.  Blank lines are
+    /// skipped.
+    pub fn parse(s: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let rows = parse_rows(s, cell)?;
+        let width = rows.first().map_or(0, |(_, row)| row.len());
+        if let Some((line, _)) = rows.iter().find(|(_, row)| row.len() != width) {
+            return Err(ParseError::at(s, line, "rows differ in width"));
+        }
+        Ok(
+            Grid::from_rows(rows.into_iter().map(|(_, row)| row).collect())
+                .expect("rows have the same width"),
+        )
+    }
+
+    /// Like `parse`, but short rows are padded out with `fill` to the width
+    /// of the widest one.
+    pub fn parse_padded(
+        s: &str,
+        fill: T,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError>
+    where
+        T: Clone,
+    {
+        let mut rows: Vec<Vec<T>> = parse_rows(s, cell)?
+            .into_iter()
+            .map(|(_, row)| row)
+            .collect();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        for row in &mut rows {
+            row.resize(width, fill.clone());
+        }
+        Ok(Grid::from_rows(rows).expect("rows have been padded"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        self.index_of(p).is_some()
+    }
+
+    fn index_of(&self, p: Pos) -> Option<usize> {
+        let x = usize::try_from(p.x).ok().filter(|&x| x < self.width)?;
+        let y = usize::try_from(p.y).ok().filter(|&y| y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(self.index_of(p)?)
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        let index = self.index_of(p)?;
+        self.cells.get_mut(index)
+    }
+
+    /// Every position in the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Pos::new(x, y)))
+    }
+
+    /// Every cell in the grid with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() doesn't accept zero, but then there are no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The orthogonal neighbors of `p` that are inside the grid.
+    pub fn neighbors4(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        p.neighbors4().filter(|&n| self.contains(n))
+    }
+
+    /// The neighbors of `p`, diagonals included, that are inside the grid.
+    pub fn neighbors8(&self, p: Pos) -> impl Iterator<Item = Pos> + '_ {
+        p.neighbors8().filter(|&n| self.contains(n))
+    }
+
+    /// Walks from `from` (exclusive) in steps of `step` until it leaves the
+    /// grid.  `step` must not be zero.
+    pub fn ray(&self, from: Pos, step: Pos) -> Ray<'_, T> {
+        Ray {
+            grid: self,
+            pos: from,
+            step,
+        }
+    }
+
+    /// Wraps `p` around the edges, so that stepping off one side of the
+    /// grid comes back on the other.  The grid must not be empty.
+    pub fn wrap(&self, p: Pos) -> Pos {
+        Pos::new(
+            p.x.rem_euclid(self.width as i64),
+            p.y.rem_euclid(self.height as i64),
+        )
+    }
+
+    /// Draws the grid as text, one line per row.
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut s = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            s.extend(row.iter().map(&mut cell));
+            s.push('\n');
+        }
+        s
+    }
+}
+
+// The non-blank lines of `s`, each with its cells.
+fn parse_rows<T>(
+    s: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<Vec<(&str, Vec<T>)>, ParseError> {
+    let mut rows = Vec::new();
+    for (lineindex, line) in s.lines().enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let row = line
+            .chars()
+            .enumerate()
+            .map(|(column, ch)| {
+                cell(ch).ok_or_else(|| {
+                    ParseError::new(lineindex + 1, column + 1, ch, "unexpected character")
+                })
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+        rows.push((line, row));
+    }
+    Ok(rows)
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Pos) -> &T {
+        self.get(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, p: Pos) -> &mut T {
+        self.get_mut(p)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", p))
+    }
+}
+
+/// The cells met walking in a straight line across a grid.
+#[derive(Debug)]
+pub struct Ray<'a, T> {
+    grid: &'a Grid<T>,
+    pos: Pos,
+    step: Pos,
+}
+
+impl<'a, T> Iterator for Ray<'a, T> {
+    type Item = (Pos, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.pos += self.step;
+        let cell = self.grid.get(self.pos)?;
+        Some((self.pos, cell))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(s: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(s, |ch| ch.to_digit(10))
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = digits("\n123\n456\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(Pos::new(2, 0)), Some(&3));
+        assert_eq!(grid[Pos::new(0, 1)], 4);
+        assert_eq!(grid.get(Pos::new(3, 0)), None);
+        assert_eq!(grid.get(Pos::new(-1, 0)), None);
+        assert_eq!(grid.rows().collect::<Vec<_>>(), vec![[1, 2, 3], [4, 5, 6]]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            digits("123\n4x6"),
+            Err(ParseError::new(2, 2, "x", "unexpected character"))
+        );
+        assert_eq!(
+            digits("123\n45"),
+            Err(ParseError::new(2, 1, "45", "rows differ in width"))
+        );
+    }
+
+    #[test]
+    fn test_parse_padded() {
+        let grid = Grid::parse_padded("  .#\n.\n", ' ', Some).unwrap();
+        assert_eq!((grid.width(), grid.height()), (4, 2));
+        assert_eq!(grid.render(|&ch| ch), "  .#\n.   \n");
+    }
+
+    #[test]
+    fn test_empty() {
+        let grid = digits("").unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.positions().count(), 0);
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.render(|_| '?'), "");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(
+            grid.neighbors4(Pos::new(0, 0)).collect::<Vec<_>>(),
+            vec![Pos::new(1, 0), Pos::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(Pos::new(1, 0)).count(), 5);
+        assert_eq!(grid.neighbors8(Pos::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = digits("123\n456\n789").unwrap();
+        let values = |step| {
+            grid.ray(Pos::new(1, 1), step)
+                .map(|(_, &v)| v)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values(Pos::LEFT), vec![4]);
+        assert_eq!(values(Pos::UP), vec![2]);
+        assert_eq!(values(Pos::new(1, 1)), vec![9]);
+        assert_eq!(
+            grid.ray(Pos::new(-1, 0), Pos::RIGHT)
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            vec![Pos::new(0, 0), Pos::new(1, 0), Pos::new(2, 0)]
+        );
+    }
+
+    #[test]
+    fn test_wrap() {
+        let grid = Grid::new(4, 3, '.');
+        assert_eq!(grid.wrap(Pos::new(-1, 3)), Pos::new(3, 0));
+        assert_eq!(grid.wrap(Pos::new(4, -1)), Pos::new(0, 2));
+        assert_eq!(grid.wrap(Pos::new(2, 1)), Pos::new(2, 1));
+    }
+
+    #[test]
+    fn test_iter_and_index_mut() {
+        let mut grid = Grid::from_rows(vec![vec!['.', '#'], vec!['#', '.']]).unwrap();
+        grid[Pos::new(0, 0)] = '#';
+        assert_eq!(
+            grid.iter()
+                .filter(|&(_, &ch)| ch == '#')
+                .map(|(p, _)| p)
+                .collect::<Vec<_>>(),
+            vec![Pos::new(0, 0), Pos::new(1, 0), Pos::new(0, 1)]
+        );
+        assert_eq!(grid.render(|&ch| ch), "##\n#.\n");
+        assert!(Grid::from_rows(vec![vec![1], vec![]]).is_none());
+    }
+}
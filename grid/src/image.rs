@@ -0,0 +1,313 @@
+// Pictures of puzzle states, saved as PPM or PNG.  A state is drawn first
+// as text, one character per cell, as the days already do for debugging;
+// a palette then gives each character its color:
+//
+//     let palette = Palette::new(BLACK).with('#', WHITE);
+//     Image::from_text(&cave.to_string(), &palette).scaled(4).save("cave.png")?;
+//
+// ImageFrames does the same for each step of a simulation; see
+// common::observe:
+//
+//     let writer = ImageWriter::new(palette).scale(4);
+//     let mut frames = ImageFrames::with_writer("frames", writer).every(100);
+
+use common::observe::{FrameWriter, Frames};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+pub type Rgb = [u8; 3];
+
+pub const BLACK: Rgb = [0, 0, 0];
+pub const WHITE: Rgb = [255, 255, 255];
+
+/// The color of each character.  Characters without one of their own are
+/// drawn in the default color.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Palette {
+    colors: HashMap<char, Rgb>,
+    default: Rgb,
+}
+
+impl Palette {
+    pub fn new(default: Rgb) -> Self {
+        Palette {
+            colors: HashMap::new(),
+            default,
+        }
+    }
+
+    pub fn with(mut self, ch: char, color: Rgb) -> Self {
+        self.colors.insert(ch, color);
+        self
+    }
+
+    /// Shades the characters evenly from one color to another, as for a
+    /// heatmap of the digits 0 to 9.
+    pub fn gradient(mut self, chars: impl IntoIterator<Item = char>, from: Rgb, to: Rgb) -> Self {
+        let chars: Vec<char> = chars.into_iter().collect();
+        let last = chars.len().saturating_sub(1).max(1) as f64;
+        for (i, ch) in chars.into_iter().enumerate() {
+            let t = i as f64 / last;
+            let color = [0, 1, 2]
+                .map(|c| (from[c] as f64 + (to[c] as f64 - from[c] as f64) * t).round() as u8);
+            self.colors.insert(ch, color);
+        }
+        self
+    }
+
+    /// Overrides colors from a spec like "#=ffffff,o=c2b280", as given on
+    /// the command line.
+    pub fn apply(mut self, spec: &str) -> Result<Self, String> {
+        for entry in spec.split(',').filter(|entry| !entry.is_empty()) {
+            let mut chars = entry.chars();
+            let (ch, color) = match (chars.next(), chars.next()) {
+                (Some(ch), Some('=')) => (ch, chars.as_str()),
+                _ => return Err(format!("expected CHAR=RRGGBB, not {:?}", entry)),
+            };
+            self.colors.insert(ch, parse_color(color)?);
+        }
+        Ok(self)
+    }
+
+    pub fn color(&self, ch: char) -> Rgb {
+        self.colors.get(&ch).copied().unwrap_or(self.default)
+    }
+}
+
+/// Reads a color written as six hex digits, with or without a leading #.
+pub fn parse_color(s: &str) -> Result<Rgb, String> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    let channel = |i: usize| {
+        hex.get(i..i + 2)
+            .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+    };
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Ok([r, g, b]),
+        _ => Err(format!("expected a color like c2b280, not {:?}", s)),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    // Row by row, from the top left.
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// One pixel per character.  Short lines are padded with the default
+    /// color.
+    pub fn from_text(text: &str, palette: &Palette) -> Self {
+        let lines: Vec<Vec<char>> = text.lines().map(|line| line.chars().collect()).collect();
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+        let pixels = lines
+            .iter()
+            .flat_map(|line| {
+                (0..width).map(|x| line.get(x).map_or(palette.default, |&ch| palette.color(ch)))
+            })
+            .collect();
+        Image {
+            width,
+            height: lines.len(),
+            pixels,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width).then(|| self.pixels.get(y * self.width + x).copied())?
+    }
+
+    /// Each pixel blown up to a `scale` by `scale` square.
+    pub fn scaled(&self, scale: usize) -> Self {
+        let scale = scale.max(1);
+        let pixels = self
+            .pixels
+            .chunks(self.width.max(1))
+            .flat_map(|row| {
+                let row: Vec<Rgb> = row
+                    .iter()
+                    .flat_map(|&pixel| std::iter::repeat_n(pixel, scale))
+                    .collect();
+                std::iter::repeat_n(row, scale).flatten()
+            })
+            .collect();
+        Image {
+            width: self.width * scale,
+            height: self.height * scale,
+            pixels,
+        }
+    }
+
+    /// The image as a binary PPM, which most viewers can open.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.pixels.iter().flatten());
+        ppm
+    }
+
+    pub fn write_png(&self, out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        let data: Vec<u8> = self.pixels.iter().flatten().copied().collect();
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    /// Saves the image as PNG if the path ends in .png, and as PPM
+    /// otherwise.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if path.extension().is_some_and(|extension| extension == "png") {
+            let mut out = BufWriter::new(File::create(path)?);
+            self.write_png(&mut out)?;
+            out.flush()
+        } else {
+            fs::write(path, self.to_ppm())
+        }
+    }
+}
+
+/// Saves each state of a simulation as a numbered image, e.g.
+/// frames/000001.png, frames/000002.png and so on, to be strung together
+/// into an animation.
+pub type ImageFrames = Frames<ImageWriter>;
+
+/// Draws frames as PNG images, or PPM ones.
+#[derive(Debug, Clone)]
+pub struct ImageWriter {
+    palette: Palette,
+    scale: usize,
+    extension: &'static str,
+}
+
+impl ImageWriter {
+    pub fn new(palette: Palette) -> Self {
+        ImageWriter {
+            palette,
+            scale: 1,
+            extension: "png",
+        }
+    }
+
+    pub fn scale(mut self, scale: usize) -> Self {
+        self.scale = scale.max(1);
+        self
+    }
+
+    /// Writes PPM frames instead of PNG ones.
+    pub fn ppm(mut self) -> Self {
+        self.extension = "ppm";
+        self
+    }
+}
+
+impl FrameWriter for ImageWriter {
+    fn extension(&self) -> &str {
+        self.extension
+    }
+
+    fn write_frame(&self, path: &Path, frame: &str) -> io::Result<()> {
+        Image::from_text(frame, &self.palette)
+            .scaled(self.scale)
+            .save(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::observe::Observer;
+    use std::env;
+
+    const RED: Rgb = [255, 0, 0];
+
+    #[test]
+    fn test_from_text() {
+        let palette = Palette::new(BLACK).with('#', WHITE).with('o', RED);
+        let image = Image::from_text("#o\n#\n", &palette);
+        assert_eq!((image.width(), image.height()), (2, 2));
+        assert_eq!(image.pixel(0, 0), Some(WHITE));
+        assert_eq!(image.pixel(1, 0), Some(RED));
+        assert_eq!(image.pixel(1, 1), Some(BLACK));
+        assert_eq!(image.pixel(2, 0), None);
+        assert_eq!(image.pixel(0, 2), None);
+    }
+
+    #[test]
+    fn test_scaled() {
+        let palette = Palette::new(BLACK).with('#', WHITE);
+        let image = Image::from_text("#.\n", &palette).scaled(2);
+        assert_eq!((image.width(), image.height()), (4, 2));
+        assert_eq!(image.pixel(1, 1), Some(WHITE));
+        assert_eq!(image.pixel(2, 1), Some(BLACK));
+    }
+
+    #[test]
+    fn test_gradient() {
+        let palette = Palette::new(BLACK).gradient('0'..='2', BLACK, [200, 100, 0]);
+        assert_eq!(palette.color('0'), BLACK);
+        assert_eq!(palette.color('1'), [100, 50, 0]);
+        assert_eq!(palette.color('2'), [200, 100, 0]);
+        assert_eq!(palette.color('3'), BLACK);
+    }
+
+    #[test]
+    fn test_apply() {
+        let palette = Palette::new(BLACK).with('#', WHITE);
+        let palette = palette.apply("#=c2b280,.=#ff0000").unwrap();
+        assert_eq!(palette.color('#'), [0xc2, 0xb2, 0x80]);
+        assert_eq!(palette.color('.'), RED);
+        assert!(Palette::new(BLACK).apply("#ffffff").is_err());
+        assert!(Palette::new(BLACK).apply("#=fff").is_err());
+        assert!(Palette::new(BLACK).apply("#=gggggg").is_err());
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        let palette = Palette::new(BLACK).with('o', RED);
+        let ppm = Image::from_text("o.\n", &palette).to_ppm();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\xff\x00\x00\x00\x00\x00");
+    }
+
+    #[test]
+    fn test_write_png() {
+        let palette = Palette::new(BLACK).with('o', RED);
+        let mut png = Vec::new();
+        Image::from_text("o.\n.o\n", &palette)
+            .write_png(&mut png)
+            .unwrap();
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+    }
+
+    #[test]
+    fn test_image_frames() {
+        let dir = env::temp_dir().join(format!("aoc-image-frames-{}", std::process::id()));
+        let writer = ImageWriter::new(Palette::new(WHITE)).ppm().scale(3);
+        let mut frames = ImageFrames::with_writer(&dir, writer);
+        for (step, state) in ["#", "##"].iter().enumerate() {
+            frames.observe(step + 1, state);
+        }
+        assert_eq!(frames.finish().unwrap(), 2);
+        assert!(fs::read(dir.join("000001.ppm"))
+            .unwrap()
+            .starts_with(b"P6\n3 3\n"));
+        assert!(fs::read(dir.join("000002.ppm"))
+            .unwrap()
+            .starts_with(b"P6\n6 3\n"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
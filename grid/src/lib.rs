@@ -0,0 +1,13 @@
+// Two-dimensional grids shared by the day crates: a dense grid for maps
+// read straight from the puzzle input, and a sparse one for unbounded
+// worlds that fill up as the simulation runs.  image draws them as
+// pictures.
+
+mod dense;
+pub mod image;
+mod pos;
+mod sparse;
+
+pub use dense::Grid;
+pub use pos::Pos;
+pub use sparse::SparseGrid;
@@ -0,0 +1,109 @@
+use std::ops::{Add, AddAssign, Neg, Sub};
+
+/// A position on a grid, or the step between two positions.  As in the
+/// puzzle text, x grows to the right and y grows downward.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pos {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Pos {
+    pub const UP: Pos = Pos::new(0, -1);
+    pub const DOWN: Pos = Pos::new(0, 1);
+    pub const LEFT: Pos = Pos::new(-1, 0);
+    pub const RIGHT: Pos = Pos::new(1, 0);
+
+    /// The four orthogonal steps.
+    pub const ORTHOGONAL: [Pos; 4] = [Pos::LEFT, Pos::RIGHT, Pos::UP, Pos::DOWN];
+
+    /// The eight steps to every surrounding position, diagonals included.
+    pub const SURROUNDING: [Pos; 8] = [
+        Pos::new(-1, -1),
+        Pos::new(0, -1),
+        Pos::new(1, -1),
+        Pos::new(-1, 0),
+        Pos::new(1, 0),
+        Pos::new(-1, 1),
+        Pos::new(0, 1),
+        Pos::new(1, 1),
+    ];
+
+    pub const fn new(x: i64, y: i64) -> Self {
+        Pos { x, y }
+    }
+
+    /// The four positions sharing an edge with this one.
+    pub fn neighbors4(self) -> impl Iterator<Item = Pos> {
+        Pos::ORTHOGONAL.into_iter().map(move |step| self + step)
+    }
+
+    /// The eight positions sharing an edge or a corner with this one.
+    pub fn neighbors8(self) -> impl Iterator<Item = Pos> {
+        Pos::SURROUNDING.into_iter().map(move |step| self + step)
+    }
+
+    pub fn manhattan(self, other: Pos) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl Add for Pos {
+    type Output = Pos;
+
+    fn add(self, rhs: Pos) -> Pos {
+        Pos::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign for Pos {
+    fn add_assign(&mut self, rhs: Pos) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Pos {
+    type Output = Pos;
+
+    fn sub(self, rhs: Pos) -> Pos {
+        Pos::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Pos {
+    type Output = Pos;
+
+    fn neg(self) -> Pos {
+        Pos::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbors() {
+        let p = Pos::new(3, 5);
+        assert_eq!(
+            p.neighbors4().collect::<Vec<_>>(),
+            vec![
+                Pos::new(2, 5),
+                Pos::new(4, 5),
+                Pos::new(3, 4),
+                Pos::new(3, 6)
+            ]
+        );
+        assert_eq!(p.neighbors8().count(), 8);
+        assert!(p.neighbors8().all(|n| n != p && n.manhattan(p) <= 2));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let mut p = Pos::new(1, 2) + Pos::RIGHT - Pos::UP;
+        assert_eq!(p, Pos::new(2, 3));
+        p += -Pos::DOWN;
+        assert_eq!(p, Pos::new(2, 2));
+        assert_eq!(p.manhattan(Pos::new(-1, 4)), 5);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::Pos;
+use common::ParseError;
+use std::collections::HashMap;
+
+/// A grid with no fixed edges that only stores the cells that have been
+/// filled in.  It keeps track of the box around everything ever inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    bounds: Option<(Pos, Pos)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads a grid with one cell per character.  Characters equal to
+    /// `empty` are left out; every other one must be accepted by `cell`.
+    pub fn parse(
+        s: &str,
+        empty: char,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut grid = SparseGrid::new();
+        for (y, line) in s.lines().enumerate() {
+            for (x, ch) in line.chars().enumerate() {
+                if ch == empty {
+                    continue;
+                }
+                let value = cell(ch)
+                    .ok_or_else(|| ParseError::new(y + 1, x + 1, ch, "unexpected character"))?;
+                grid.insert(Pos::new(x as i64, y as i64), value);
+            }
+        }
+        Ok(grid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        self.cells.contains_key(&p)
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&T> {
+        self.cells.get(&p)
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&p)
+    }
+
+    /// Fills in a cell, returning what was there before.
+    pub fn insert(&mut self, p: Pos, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (p, p),
+            Some((min, max)) => (
+                Pos::new(min.x.min(p.x), min.y.min(p.y)),
+                Pos::new(max.x.max(p.x), max.y.max(p.y)),
+            ),
+        });
+        self.cells.insert(p, value)
+    }
+
+    /// Empties a cell.  The bounds stay as they were.
+    pub fn remove(&mut self, p: Pos) -> Option<T> {
+        self.cells.remove(&p)
+    }
+
+    /// The smallest and largest corners of the box around every cell ever
+    /// inserted, or `None` if nothing has been.
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        self.bounds
+    }
+
+    /// The filled cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(&p, value)| (p, value))
+    }
+
+    /// Walks from `from` (exclusive) in steps of `step` until it leaves the
+    /// bounds, yielding empty cells as well as filled ones.  `step` must not
+    /// be zero.
+    pub fn ray(&self, from: Pos, step: Pos) -> impl Iterator<Item = (Pos, Option<&T>)> {
+        let bounds = self.bounds;
+        std::iter::successors(Some(from), move |&p| Some(p + step))
+            .skip(1)
+            .take_while(move |p| {
+                bounds.is_some_and(|(min, max)| {
+                    (min.x..=max.x).contains(&p.x) && (min.y..=max.y).contains(&p.y)
+                })
+            })
+            .map(|p| (p, self.get(p)))
+    }
+
+    /// Draws the box within the bounds as text, one line per row, with
+    /// `empty` wherever there's nothing.
+    pub fn render(&self, empty: char, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut s = String::new();
+        if let Some((min, max)) = self.bounds {
+            for y in min.y..=max.y {
+                s.extend(
+                    (min.x..=max.x).map(|x| self.get(Pos::new(x, y)).map_or(empty, &mut cell)),
+                );
+                s.push('\n');
+            }
+        }
+        s
+    }
+}
+
+impl<T> Extend<(Pos, T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = (Pos, T)>>(&mut self, iter: I) {
+        for (p, value) in iter {
+            self.insert(p, value);
+        }
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_and_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.insert(Pos::new(3, -2), 'a'), None);
+        assert_eq!(grid.insert(Pos::new(-1, 4), 'b'), None);
+        assert_eq!(grid.insert(Pos::new(3, -2), 'c'), Some('a'));
+        assert_eq!(grid.len(), 2);
+        assert_eq!(grid.bounds(), Some((Pos::new(-1, -2), Pos::new(3, 4))));
+
+        assert_eq!(grid.remove(Pos::new(-1, 4)), Some('b'));
+        assert!(!grid.contains(Pos::new(-1, 4)));
+        assert_eq!(grid.bounds(), Some((Pos::new(-1, -2), Pos::new(3, 4))));
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        let grid = SparseGrid::parse("..#\n#..\n", '.', |ch| (ch == '#').then_some(())).unwrap();
+        assert_eq!(grid.len(), 2);
+        assert!(grid.contains(Pos::new(2, 0)));
+        assert!(grid.contains(Pos::new(0, 1)));
+        assert_eq!(grid.render('.', |_| '#'), "..#\n#..\n");
+
+        assert_eq!(
+            SparseGrid::parse("..#\n#x.\n", '.', |ch| (ch == '#').then_some(())),
+            Err(ParseError::new(2, 2, "x", "unexpected character"))
+        );
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid: SparseGrid<u32> = [(Pos::new(0, 0), 1), (Pos::new(3, 0), 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            grid.ray(Pos::new(0, 0), Pos::RIGHT).collect::<Vec<_>>(),
+            vec![
+                (Pos::new(1, 0), None),
+                (Pos::new(2, 0), None),
+                (Pos::new(3, 0), Some(&2))
+            ]
+        );
+        assert_eq!(grid.ray(Pos::new(0, 0), Pos::DOWN).count(), 0);
+        assert_eq!(
+            SparseGrid::<u32>::new()
+                .ray(Pos::new(0, 0), Pos::UP)
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_render_empty() {
+        assert_eq!(SparseGrid::<char>::new().render('.', |&ch| ch), "");
+    }
+}